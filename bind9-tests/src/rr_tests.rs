@@ -15,8 +15,13 @@ macro_rules! check_bind9 {
     ($ty:ident, $rdata:expr, $text:expr, $text_comp:expr) => {
         let type_code = simple_dns::testing::type_code::<$ty>();
         let bytes = crate::text_to_wire($text, CLASS::IN as u16, type_code);
+        let from_text = simple_dns::rdata::RData::from_str(type_code.into(), $text)
+            .expect("failed to parse text");
+        assert_eq!(bytes, simple_dns::testing::get_bytes(from_text), "wire format differ");
+
         let parsed = simple_dns::testing::parse::<$ty>(&bytes);
         assert_eq!($rdata, parsed, "parsed data differ");
+        assert_eq!(*$text_comp, parsed.to_string(), "presentation format differ");
 
         let parsed_bytes = simple_dns::testing::get_bytes(parsed);
 
@@ -56,6 +61,32 @@ fn afsdb_bind9_compatible() {
     check_bind9!(AFSDB, rdata, &text);
 }
 
+#[test]
+fn apl_bind9_compatible() {
+    let text = "1:192.168.32.0/21 !1:192.168.38.0/28 2:ff00::/8";
+    let rdata = APL {
+        items: vec![
+            AplItem {
+                negation: false,
+                prefix: 21,
+                address: AplAddress::Ipv4(Ipv4Addr::new(192, 168, 32, 0)),
+            },
+            AplItem {
+                negation: true,
+                prefix: 28,
+                address: AplAddress::Ipv4(Ipv4Addr::new(192, 168, 38, 0)),
+            },
+            AplItem {
+                negation: false,
+                prefix: 8,
+                address: AplAddress::Ipv6(Ipv6Addr::new(0xff00, 0, 0, 0, 0, 0, 0, 0)),
+            },
+        ],
+    };
+
+    check_bind9!(APL, rdata, text);
+}
+
 #[test]
 fn caa_bind9_compatible() {
     let text = r#"0 issue "ca1.example.net""#;
@@ -68,6 +99,28 @@ fn caa_bind9_compatible() {
     check_bind9!(CAA, rdata, text);
 }
 
+#[test]
+fn cdnskey_bind9_compatible() {
+    let text = "0 3 0 AA==";
+    check_bind9!(CDNSKEY, CDNSKEY::delete(), text);
+}
+
+#[test]
+fn cds_bind9_compatible() {
+    let text = "12892 5 1 7AA4A3F416C2F2391FB7AB0D434F762CD62D1390";
+    let rdata = CDS(DS {
+        key_tag: 12892,
+        algorithm: 5,
+        digest_type: 1,
+        digest: Cow::Borrowed(&[
+            0x7A, 0xA4, 0xA3, 0xF4, 0x16, 0xC2, 0xF2, 0x39, 0x1F, 0xB7, 0xAB, 0x0D, 0x43, 0x4F,
+            0x76, 0x2C, 0xD6, 0x2D, 0x13, 0x90,
+        ]),
+    });
+    check_bind9!(CDS, rdata, text);
+    check_bind9!(CDS, CDS::delete(), "0 0 0 00");
+}
+
 #[test]
 fn cert_bind9_compatible() {
     let text = "65534 65535 PRIVATEOID MIG2AgEAMBAGByqGSM49AgEGBSuBBAAiBIGeMIGbAgEBBDDq5a0oiMxJ iOdwaSmkU2NPPJXOWPZVWpIGxB0kczGcCS6Xq0VinNqLe5YI9M1YwXeh ZANiAASeQ9fMKeGOSzWhj7ePMA9Ws1t/wGKbIyFwsSvnc/nqOAFmS1JD Mc8QaRW/awjzaQc/mbu4cNA7iSId8iVCWj5VkcP8tL7HLYZRFMSr/nxU NGfHXtuGhMxm61SvnX3czhg=";
@@ -81,6 +134,21 @@ fn cert_bind9_compatible() {
     check_bind9!(CERT, rdata, text);
 }
 
+#[test]
+fn csync_bind9_compatible() {
+    let text = "66 3 A NS AAAA";
+    let rdata = CSYNC {
+        serial: 66,
+        flags: CSYNC::IMMEDIATE | CSYNC::SOA_MINIMUM,
+        type_bit_maps: vec![NsecTypeBitMap {
+            window_block: 0,
+            bitmap: Cow::Borrowed(&[0x60, 0, 0, 0x08]),
+        }],
+    };
+
+    check_bind9!(CSYNC, rdata, text);
+}
+
 #[test]
 fn dhcid_bind9_compatible() {
     let text = r"AAABxLmlskllE0MVjd57zHcWmEH3pCQ6VytcKD//7es/deY=";
@@ -96,6 +164,16 @@ fn dhcid_bind9_compatible() {
     check_bind9!(DHCID, rdata, text);
 }
 
+#[test]
+fn dname_bind9_compatible() {
+    let text = "example.net.";
+    let rdata = DNAME {
+        target: Name::new_unchecked("example.net"),
+    };
+
+    check_bind9!(DNAME, rdata, text);
+}
+
 #[test]
 fn dnskey_bind9_compatible() {
     let text = "512 255 1 AQMFD5raczCJHViKtLYhWGz8hMY9UGRuniJDBzC7w0aRyzWZriO6i2od GWWQVucZqKVsENW91IOW4vqudngPZsY3GvQ/xVA8/7pyFj6b7Esga60z yGW6LFe9r8n6paHrlG5ojqf0BaqHT+8=";
@@ -119,6 +197,26 @@ fn hinfo_bind9_compatible() {
     check_bind9!(HINFO, rdata, &text);
 }
 
+#[test]
+fn hip_bind9_compatible() {
+    let text = "2 200100107B1A74DF365639CC39F1D578 AwEAAbdxyhNuSutc5EMzxTs9LBPCIkOFH8cIvM4p9+LrV4e19WzK00+CI6zBCQTdtWsuxKbWIy87UOoJTwkUs7lBu+Upr1gsNrut79ryra+bSRGQb1slImA8YVJyuIDsj7kwzG7jnERNqnWxZ48AWkskmdHaVDP4BcelrTI3rMXdXF5D rvs.example.com.";
+    let rdata = HIP {
+        public_key_algorithm: 2,
+        hit: Cow::Borrowed(&[
+            0x20, 0x01, 0x00, 0x10, 0x7B, 0x1A, 0x74, 0xDF, 0x36, 0x56, 0x39, 0xCC, 0x39, 0xF1,
+            0xD5, 0x78,
+        ]),
+        public_key: Cow::Owned(
+            BASE64_STANDARD
+                .decode("AwEAAbdxyhNuSutc5EMzxTs9LBPCIkOFH8cIvM4p9+LrV4e19WzK00+CI6zBCQTdtWsuxKbWIy87UOoJTwkUs7lBu+Upr1gsNrut79ryra+bSRGQb1slImA8YVJyuIDsj7kwzG7jnERNqnWxZ48AWkskmdHaVDP4BcelrTI3rMXdXF5D")
+                .unwrap(),
+        ),
+        rendezvous_servers: vec![Name::new_unchecked("rvs.example.com")],
+    };
+
+    check_bind9!(HIP, rdata, text);
+}
+
 #[test]
 fn ds_bind9_compatible() {
     let text = "12892 5 1 7AA4A3F416C2F2391FB7AB0D434F762CD62D1390";
@@ -179,6 +277,18 @@ fn isdn_bind9_compatible() {
     check_bind9!(ISDN, rdata, &text);
 }
 
+#[test]
+fn key_bind9_compatible() {
+    let text = "512 255 1 AQMFD5raczCJHViKtLYhWGz8hMY9UGRuniJDBzC7w0aRyzWZriO6i2od GWWQVucZqKVsENW91IOW4vqudngPZsY3GvQ/xVA8/7pyFj6b7Esga60z yGW6LFe9r8n6paHrlG5ojqf0BaqHT+8=";
+    let rdata = KEY(DNSKEY {
+        flags: 512,
+        protocol: 255,
+        algorithm: 1,
+        public_key: Cow::Owned(BASE64_STANDARD.decode("AQMFD5raczCJHViKtLYhWGz8hMY9UGRuniJDBzC7w0aRyzWZriO6i2odGWWQVucZqKVsENW91IOW4vqudngPZsY3GvQ/xVA8/7pyFj6b7Esga60zyGW6LFe9r8n6paHrlG5ojqf0BaqHT+8=").unwrap()),
+    });
+    check_bind9!(KEY, rdata, text);
+}
+
 #[test]
 fn kx_bind9_compatible() {
     let text = "10 kdc.";
@@ -281,6 +391,48 @@ fn nsec_bind9_compatible() {
     check_bind9!(NSEC, rdata, &text);
 }
 
+#[test]
+fn nsec3_bind9_compatible() {
+    let text = "1 1 12 AABBCCDD 2T7B4G4VSA5SMI47K61MV5BV1A22BOJR A RRSIG";
+    let rdata = NSEC3 {
+        hash_algorithm: 1,
+        flags: 1,
+        iterations: 12,
+        salt: Cow::Borrowed(&[0xaa, 0xbb, 0xcc, 0xdd]),
+        next_hashed_owner: Cow::Borrowed(&[
+            0x17, 0x4e, 0xb2, 0x40, 0x9f, 0xe2, 0x8b, 0xcb, 0x48, 0x87, 0xa1, 0x83, 0x6f, 0x95,
+            0x7f, 0x0a, 0x84, 0x25, 0xe2, 0x7b,
+        ]),
+        type_bit_maps: vec![NsecTypeBitMap {
+            window_block: 0,
+            bitmap: (&[0x40, 0x00, 0x00, 0x00, 0x00, 0x02]).into(),
+        }],
+    };
+    check_bind9!(NSEC3, rdata, &text);
+}
+
+#[test]
+fn nsec3param_bind9_compatible() {
+    let text = "1 0 12 AABBCCDD";
+    let rdata = NSEC3PARAM {
+        hash_algorithm: 1,
+        flags: 0,
+        iterations: 12,
+        salt: Cow::Borrowed(&[0xaa, 0xbb, 0xcc, 0xdd]),
+    };
+    check_bind9!(NSEC3PARAM, rdata, &text);
+}
+
+#[test]
+fn openpgpkey_bind9_compatible() {
+    let text = "mQENBFI=";
+    let rdata = OPENPGPKEY {
+        public_key: Cow::Borrowed(&[0x99, 0x01, 0x0d, 0x04, 0x52]),
+    };
+
+    check_bind9!(OPENPGPKEY, rdata, text);
+}
+
 #[test]
 fn route_through_bind9_compatible() {
     let text = "10 intermediate-host.sample.";
@@ -322,6 +474,25 @@ fn rrsig_bind9_compatible() {
     check_bind9!(RRSIG, rdata, text);
 }
 
+#[test]
+fn sig_bind9_compatible() {
+    let text = "NSEC 1 3 3600 20000102030405 19961211100908 2143 foo.nil. MxFcby9k/yvedMfQgKzhH5er0Mu/vILz45IkskceFGgiWCn/GxHhai6V AuHAoNUz4YoU1tVfSCSqQYn6//11U6Nld80jEeC8aTrO+KKmCaY=";
+
+    let rdata = SIG(RRSIG {
+        type_covered: 47,
+        algorithm: 1,
+        labels: 3,
+        original_ttl: 3600,
+        signature_expiration: 946782245,
+        signature_inception: 850298948,
+        key_tag: 2143,
+        signer_name: Name::new_unchecked("foo.nil"),
+        signature: BASE64_STANDARD.decode("MxFcby9k/yvedMfQgKzhH5er0Mu/vILz45IkskceFGgiWCn/GxHhai6VAuHAoNUz4YoU1tVfSCSqQYn6//11U6Nld80jEeC8aTrO+KKmCaY=").unwrap().into(),
+    });
+
+    check_bind9!(SIG, rdata, text);
+}
+
 #[test]
 fn soa_bind9_compatible() {
     let text = "a.test. hostmaster.null. 1613723740 900 300 604800 900";
@@ -351,6 +522,21 @@ fn srv_bind9_compatible() {
     check_bind9!(SRV, rdata, text);
 }
 
+#[test]
+fn sshfp_bind9_compatible() {
+    let text = "2 1 123456789ABCDEF67890123456789ABCDEF67890";
+    let rdata = SSHFP {
+        algorithm: SshfpAlgorithm::Dsa,
+        fingerprint_type: FingerprintType::Sha1,
+        fingerprint: Cow::Borrowed(&[
+            0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf6, 0x78, 0x90, 0x12, 0x34, 0x56, 0x78,
+            0x9a, 0xbc, 0xde, 0xf6, 0x78, 0x90,
+        ]),
+    };
+
+    check_bind9!(SSHFP, rdata, text);
+}
+
 #[test]
 fn svcb_bind9_compatible() {
     let text = r#"3 svc4.example.net. alpn="bar" port=8004 ech=AAPTTTQ= key667="hello\210qoo""#;
@@ -366,6 +552,23 @@ fn svcb_bind9_compatible() {
     check_bind9!(SVCB, rdata, text);
 }
 
+#[test]
+fn tlsa_bind9_compatible() {
+    let text = "3 1 1 0C72AC70B745AC19998811B131D662C9AC69DBDBE7CB23E5B514B566 64C5D3D6";
+    let rdata = TLSA {
+        certificate_usage: CertificateUsage::DaneEe,
+        selector: Selector::SubjectPublicKeyInfo,
+        matching_type: MatchingType::Sha256,
+        certificate_data: Cow::Borrowed(&[
+            0x0c, 0x72, 0xac, 0x70, 0xb7, 0x45, 0xac, 0x19, 0x99, 0x88, 0x11, 0xb1, 0x31, 0xd6,
+            0x62, 0xc9, 0xac, 0x69, 0xdb, 0xdb, 0xe7, 0xcb, 0x23, 0xe5, 0xb5, 0x14, 0xb5, 0x66,
+            0x64, 0xc5, 0xd3, 0xd6,
+        ]),
+    };
+    check_bind9!(TLSA, rdata.clone(), &text);
+    check_bind9!(SMIMEA, SMIMEA(rdata), &text);
+}
+
 #[test]
 fn txt_bind9_compatible() {
     let text = r#""\"foo\010bar\"""#;
@@ -374,6 +577,18 @@ fn txt_bind9_compatible() {
     check_bind9!(TXT, rdata, text);
 }
 
+#[test]
+fn uri_bind9_compatible() {
+    let text = r#"10 1 "ftp://ftp1.example.com/public""#;
+    let rdata = URI {
+        priority: 10,
+        weight: 1,
+        target: Cow::Borrowed(b"ftp://ftp1.example.com/public"),
+    };
+
+    check_bind9!(URI, rdata, text);
+}
+
 #[test]
 fn wks_bind9_compatible() {
     let text = "10.0.0.1 tcp telnet ftp 0 1 2";
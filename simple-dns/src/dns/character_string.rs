@@ -8,7 +8,7 @@ use crate::{
     SimpleDnsError,
 };
 
-use super::MAX_CHARACTER_STRING_LENGTH;
+use super::{presentation_format::unescape, MAX_CHARACTER_STRING_LENGTH};
 
 /// CharacterString is expressed in one or two ways:
 /// - as a contiguous set of characters without interior spaces,
@@ -34,6 +34,11 @@ impl<'a> CharacterString<'a> {
         Ok(Self { data })
     }
 
+    /// Parses a character string in presentation format, decoding escape sequences
+    pub(crate) fn from_presentation<'b>(text: &str) -> crate::Result<CharacterString<'b>> {
+        CharacterString::internal_new(Cow::Owned(unescape(text)?))
+    }
+
     /// Transforms the inner data into its owned type
     pub fn into_owned<'b>(self) -> CharacterString<'b> {
         CharacterString {
@@ -0,0 +1,26 @@
+use crate::lib::Vec;
+
+/// Cryptographic hash algorithms of the digests held or referenced by DNS records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DigestAlgorithm {
+    /// SHA-1, [RFC 3174](https://www.rfc-editor.org/rfc/rfc3174)
+    Sha1,
+    /// SHA-256, [RFC 6234](https://www.rfc-editor.org/rfc/rfc6234)
+    Sha256,
+    /// SHA-384, [RFC 6234](https://www.rfc-editor.org/rfc/rfc6234)
+    Sha384,
+    /// SHA-512, [RFC 6234](https://www.rfc-editor.org/rfc/rfc6234)
+    Sha512,
+}
+
+/// Provider of the digests used by DS, CDS, NSEC3, NSEC3PARAM, TLSA, SMIMEA and SSHFP records,
+/// and by the owner names of OPENPGPKEY and SMIMEA records
+///
+/// `simple-dns` does not implement any cryptographic algorithm, this trait must be implemented
+/// on top of a cryptographic library to create and check these records
+pub trait DigestProvider {
+    /// Returns the digest of `data`, calculated with the `algorithm`, or None if the algorithm
+    /// is not supported
+    fn digest(&self, algorithm: DigestAlgorithm, data: &[u8]) -> Option<Vec<u8>>;
+}
@@ -0,0 +1,240 @@
+use super::{ValidationError, DNSKEY_PROTOCOL, ZONE_KEY_FLAG};
+use crate::{
+    lib::Vec,
+    rdata::{RData, DNSKEY, DS},
+    DigestProvider, ResourceRecord, SimpleDnsError,
+};
+
+/// Returns the DS RRset the parent zone should publish for a delegation, given the CDS and
+/// CDNSKEY records published by the child and the `current` DS RRset, following
+/// [RFC 7344](https://www.rfc-editor.org/rfc/rfc7344#section-4) and
+/// [RFC 8078](https://www.rfc-editor.org/rfc/rfc8078).
+///
+/// - Without CDS and CDNSKEY records, the `current` DS RRset is kept.
+/// - Delete records (algorithm 0) request the removal of the DS RRset, an empty set is returned.
+///   Delete records can not be mixed with other records.
+/// - CDS records are published as they are. When CDNSKEY records are also present, each CDS
+///   record must refer to one of the keys and each key must be referred by a CDS record.
+/// - CDNSKEY records are converted to DS records using the `digest_type`.
+///
+/// The `child_rrset` must have been validated with [`verify_rrsig`](super::verify_rrsig), signed
+/// by a key referred by the `current` DS RRset. Failures are returned as
+/// [`SimpleDnsError::ValidationFailed`].
+pub fn parent_ds_set<D: DigestProvider + ?Sized>(
+    child_rrset: &[ResourceRecord<'_>],
+    current: &[DS<'_>],
+    digest_type: u8,
+    digest_provider: &D,
+) -> crate::Result<Vec<DS<'static>>> {
+    let Some(owner) = child_rrset.first().map(|record| record.name.to_canonical()) else {
+        return Ok(current.iter().map(|ds| ds.clone().into_owned()).collect());
+    };
+
+    let mut cds = Vec::new();
+    let mut cdnskey = Vec::new();
+    for record in child_rrset {
+        if record.name.to_canonical() != owner {
+            return Err(SimpleDnsError::ValidationFailed(
+                ValidationError::InvalidRRset,
+            ));
+        }
+
+        match &record.rdata {
+            RData::CDS(record) => cds.push(record),
+            RData::CDNSKEY(record) => cdnskey.push(record),
+            _ => {
+                return Err(SimpleDnsError::ValidationFailed(
+                    ValidationError::InvalidRRset,
+                ))
+            }
+        }
+    }
+
+    let deletes = cds.iter().filter(|cds| cds.is_delete()).count()
+        + cdnskey.iter().filter(|cdnskey| cdnskey.is_delete()).count();
+    if deletes > 0 {
+        return if deletes == cds.len() + cdnskey.len() {
+            Ok(Vec::new())
+        } else {
+            Err(SimpleDnsError::ValidationFailed(
+                ValidationError::InconsistentChildRRset,
+            ))
+        };
+    }
+
+    let keys: Vec<&DNSKEY> = cdnskey.iter().map(|cdnskey| &cdnskey.0).collect();
+    if keys
+        .iter()
+        .any(|key| key.flags & ZONE_KEY_FLAG == 0 || key.protocol != DNSKEY_PROTOCOL)
+    {
+        return Err(SimpleDnsError::ValidationFailed(
+            ValidationError::InvalidKey,
+        ));
+    }
+
+    if cds.is_empty() {
+        return keys
+            .iter()
+            .map(|key| DS::from_dnskey(&owner, key, digest_type, digest_provider))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(SimpleDnsError::ValidationFailed(
+                ValidationError::UnsupportedAlgorithm,
+            ));
+    }
+
+    if !keys.is_empty() {
+        let consistent = cds.iter().all(|cds| {
+            keys.iter()
+                .any(|key| cds.matches(&owner, key, digest_provider))
+        }) && keys.iter().all(|key| {
+            cds.iter()
+                .any(|cds| cds.matches(&owner, key, digest_provider))
+        });
+
+        if !consistent {
+            return Err(SimpleDnsError::ValidationFailed(
+                ValidationError::InconsistentChildRRset,
+            ));
+        }
+    }
+
+    Ok(cds.iter().map(|cds| cds.0.clone().into_owned()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::dnssec::sha::Sha;
+    use crate::{
+        lib::vec,
+        rdata::{CDNSKEY, CDS},
+        Name, CLASS,
+    };
+    use core::slice;
+
+    fn owner() -> Name<'static> {
+        Name::new_unchecked("dskey.example.com")
+    }
+
+    fn dnskey() -> DNSKEY<'static> {
+        // RFC 4034 section 5.4
+        let RData::DNSKEY(dnskey) = RData::from_str(
+            crate::TYPE::DNSKEY,
+            "256 3 5 ( AQOeiiR0GOMYkDshWoSKz9XzfwJr1AYtsmx3TGkJaNXVbfi/2pHm822aJ5iI9BMzNXxeYCmZ
+            DRD99WYwYqUSdjMmmAphXdvxegXd/M5+X7OrzKBaMbCVdFLUUh6DhweJBjEVv5f2wwjM9XzcnOf+EPbt
+            G9DMBmADjFDc2w/rljwvFw== )",
+        )
+        .unwrap() else {
+            unreachable!()
+        };
+        dnskey
+    }
+
+    fn record(rdata: RData<'static>) -> ResourceRecord<'static> {
+        ResourceRecord::new(owner(), CLASS::IN, 3600, rdata)
+    }
+
+    #[test]
+    fn keep_current_set() {
+        let current = vec![DS::from_dnskey(&owner(), &dnskey(), 1, &Sha).unwrap()];
+        assert_eq!(parent_ds_set(&[], &current, 2, &Sha).unwrap(), current);
+    }
+
+    #[test]
+    fn publish_child_records() {
+        let current = vec![DS::from_dnskey(&owner(), &dnskey(), 1, &Sha).unwrap()];
+        let expected = vec![DS::from_dnskey(&owner(), &dnskey(), 2, &Sha).unwrap()];
+
+        let cds = record(RData::CDS(CDS(expected[0].clone())));
+        assert_eq!(
+            parent_ds_set(slice::from_ref(&cds), &current, 2, &Sha).unwrap(),
+            expected
+        );
+
+        let cdnskey = record(RData::CDNSKEY(CDNSKEY(dnskey())));
+        assert_eq!(
+            parent_ds_set(slice::from_ref(&cdnskey), &current, 2, &Sha).unwrap(),
+            expected
+        );
+        assert_eq!(
+            parent_ds_set(&[cdnskey.clone(), cds], &current, 1, &Sha).unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            parent_ds_set(&[cdnskey], &current, 4, &Sha),
+            Err(SimpleDnsError::ValidationFailed(
+                ValidationError::UnsupportedAlgorithm
+            ))
+        );
+    }
+
+    #[test]
+    fn delete_delegation() {
+        let current = vec![DS::from_dnskey(&owner(), &dnskey(), 2, &Sha).unwrap()];
+        let delete = [
+            record(RData::CDS(CDS::delete())),
+            record(RData::CDNSKEY(CDNSKEY::delete())),
+        ];
+        assert!(parent_ds_set(&delete, &current, 2, &Sha)
+            .unwrap()
+            .is_empty());
+
+        let mixed = [
+            record(RData::CDS(CDS::delete())),
+            record(RData::CDNSKEY(CDNSKEY(dnskey()))),
+        ];
+        assert_eq!(
+            parent_ds_set(&mixed, &current, 2, &Sha),
+            Err(SimpleDnsError::ValidationFailed(
+                ValidationError::InconsistentChildRRset
+            ))
+        );
+    }
+
+    #[test]
+    fn reject_invalid_child_rrset() {
+        let current = vec![DS::from_dnskey(&owner(), &dnskey(), 2, &Sha).unwrap()];
+        let other_key = DNSKEY {
+            flags: 257,
+            ..dnskey()
+        };
+
+        // The CDS record refers to a different key than the CDNSKEY record
+        let contradictory = [
+            record(RData::CDS(CDS(current[0].clone()))),
+            record(RData::CDNSKEY(CDNSKEY(other_key.clone()))),
+        ];
+        assert_eq!(
+            parent_ds_set(&contradictory, &current, 2, &Sha),
+            Err(SimpleDnsError::ValidationFailed(
+                ValidationError::InconsistentChildRRset
+            ))
+        );
+
+        let not_zone_key = [record(RData::CDNSKEY(CDNSKEY(DNSKEY {
+            flags: 0,
+            ..other_key
+        })))];
+        assert_eq!(
+            parent_ds_set(&not_zone_key, &current, 2, &Sha),
+            Err(SimpleDnsError::ValidationFailed(
+                ValidationError::InvalidKey
+            ))
+        );
+
+        let mut other_owner = record(RData::CDS(CDS(current[0].clone())));
+        other_owner.name = Name::new_unchecked("example.com");
+        assert_eq!(
+            parent_ds_set(
+                &[record(RData::CDS(CDS(current[0].clone()))), other_owner],
+                &current,
+                2,
+                &Sha
+            ),
+            Err(SimpleDnsError::ValidationFailed(
+                ValidationError::InvalidRRset
+            ))
+        );
+    }
+}
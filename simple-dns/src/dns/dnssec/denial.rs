@@ -0,0 +1,590 @@
+use super::{wildcard_name, ValidationError, NSEC3_SHA1};
+use crate::{
+    lib::Vec,
+    rdata::{NsecTypeBitMap, RData, NSEC, NSEC3},
+    DigestProvider, Name, Packet, SimpleDnsError, RCODE, TYPE,
+};
+
+/// What a negative response proves, see [`verify_denial`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Denial {
+    /// The queried name does not exist (NXDOMAIN)
+    NameError,
+    /// The queried name exists, but has no records of the queried type (NODATA)
+    NoData,
+    /// The queried name does not exist, and the wildcard that would match it has no records of
+    /// the queried type
+    WildcardNoData,
+    /// The queried name is covered by an Opt-Out NSEC3 record, so it may exist as an unsigned
+    /// delegation. The response must be treated as insecure,
+    /// see [RFC 5155](https://www.rfc-editor.org/rfc/rfc5155#section-9.2)
+    OptOut,
+}
+
+/// Verifies that the NSEC or NSEC3 records in the authority section of a negative response prove
+/// the denial of existence of the first question, following
+/// [RFC 4035](https://www.rfc-editor.org/rfc/rfc4035#section-5.4) and
+/// [RFC 5155](https://www.rfc-editor.org/rfc/rfc5155#section-8).
+///
+/// NXDOMAIN responses must prove that the queried name and the wildcard at its closest encloser
+/// do not exist. NODATA responses must prove that the queried type and CNAME are absent from
+/// the queried name, or from the wildcard matching it, or that the queried name is an empty
+/// non-terminal.
+///
+/// Records from the parent side of a delegation, with the NS type and without the SOA type, only
+/// prove the absence of DS records at the delegation, they never prove the denial of other types
+/// or of names below it, see [RFC 6840](https://www.rfc-editor.org/rfc/rfc6840#section-4.1).
+///
+/// The signatures of the NSEC and NSEC3 records are not checked, they must be verified with
+/// [`verify_rrsig`](super::verify_rrsig). NSEC3 records with an unsupported hash algorithm are
+/// ignored. Failures are returned as [`SimpleDnsError::ValidationFailed`].
+///
+/// ```
+/// # use simple_dns::{dnssec::{verify_denial, Denial}, *};
+/// # struct Sha1;
+/// # impl DigestProvider for Sha1 {
+/// #     fn digest(&self, _: DigestAlgorithm, _: &[u8]) -> Option<Vec<u8>> { unimplemented!() }
+/// # }
+/// let mut packet = Packet::new_reply(1);
+/// packet.questions.push(Question::new(
+///     Name::new_unchecked("example.com"),
+///     TYPE::MX.into(),
+///     CLASS::IN.into(),
+///     false,
+/// ));
+/// packet.name_servers.push("example.com. 3600 IN NSEC www.example.com. A NS SOA RRSIG NSEC".parse()?);
+///
+/// assert_eq!(verify_denial(&packet, &Sha1)?, Denial::NoData);
+/// # Ok::<(), simple_dns::SimpleDnsError>(())
+/// ```
+pub fn verify_denial<D: DigestProvider + ?Sized>(
+    packet: &Packet<'_>,
+    digest_provider: &D,
+) -> crate::Result<Denial> {
+    let question = packet
+        .questions
+        .first()
+        .ok_or(SimpleDnsError::ValidationFailed(
+            ValidationError::NotNegativeResponse,
+        ))?;
+    let name_error = match packet.rcode() {
+        RCODE::NameError => true,
+        RCODE::NoError if packet.answers.is_empty() => false,
+        _ => {
+            return Err(SimpleDnsError::ValidationFailed(
+                ValidationError::NotNegativeResponse,
+            ))
+        }
+    };
+
+    let qname = question.qname.to_canonical();
+    let qtype = u16::from(question.qtype);
+
+    let mut nsec = Vec::new();
+    let mut nsec3 = Vec::new();
+    for record in &packet.name_servers {
+        match &record.rdata {
+            RData::NSEC(rdata) => nsec.push((record.name.to_canonical(), rdata)),
+            RData::NSEC3(rdata) if rdata.hash_algorithm == NSEC3_SHA1 => {
+                nsec3.push((record.name.to_canonical(), rdata))
+            }
+            _ => {}
+        }
+    }
+
+    let denial = if !nsec3.is_empty() {
+        Nsec3Chain {
+            records: nsec3,
+            digest_provider,
+        }
+        .verify(&qname, qtype, name_error)
+    } else if !nsec.is_empty() {
+        verify_nsec(&nsec, &qname, qtype, name_error)
+    } else {
+        Err(ValidationError::MissingDenialRecords)
+    };
+
+    denial.map_err(SimpleDnsError::ValidationFailed)
+}
+
+/// Returns true if the type bit maps have the queried type or a CNAME, which would have been
+/// returned instead
+fn has_type(type_bit_maps: &[NsecTypeBitMap<'_>], qtype: u16) -> bool {
+    NsecTypeBitMap::contains(type_bit_maps, qtype)
+        || NsecTypeBitMap::contains(type_bit_maps, TYPE::CNAME.into())
+}
+
+/// Returns true if the type bit maps belong to the parent side of a delegation
+fn is_delegation(type_bit_maps: &[NsecTypeBitMap<'_>]) -> bool {
+    NsecTypeBitMap::contains(type_bit_maps, TYPE::NS.into())
+        && !NsecTypeBitMap::contains(type_bit_maps, TYPE::SOA.into())
+}
+
+/// Returns true if a record with these type bit maps, matching the queried name, can prove the
+/// absence of the queried type
+fn proves_no_data(type_bit_maps: &[NsecTypeBitMap<'_>], qtype: u16) -> bool {
+    !is_delegation(type_bit_maps) || qtype == u16::from(TYPE::DS)
+}
+
+/// Number of trailing labels shared by both names, compared case insensitively
+fn common_labels(a: &Name<'_>, b: &Name<'_>) -> usize {
+    a.get_labels()
+        .iter()
+        .rev()
+        .zip(b.get_labels().iter().rev())
+        .take_while(|(a, b)| a.as_ref().eq_ignore_ascii_case(b.as_ref()))
+        .count()
+}
+
+fn verify_nsec(
+    records: &[(Name<'static>, &NSEC<'_>)],
+    qname: &Name<'static>,
+    qtype: u16,
+    name_error: bool,
+) -> Result<Denial, ValidationError> {
+    // A delegation record does not prove anything about the names below the delegation
+    let covering = |name: &Name<'_>| {
+        records.iter().find(|(owner, nsec)| {
+            nsec.covers(owner, name)
+                && !(is_delegation(&nsec.type_bit_maps) && name.is_subdomain_of(owner))
+        })
+    };
+
+    if !name_error {
+        if let Some((_, nsec)) = records
+            .iter()
+            .find(|(owner, nsec)| owner == qname && proves_no_data(&nsec.type_bit_maps, qtype))
+        {
+            return match has_type(&nsec.type_bit_maps, qtype) {
+                true => Err(ValidationError::TypeExists),
+                false => Ok(Denial::NoData),
+            };
+        }
+    }
+
+    let Some((owner, nsec)) = covering(qname) else {
+        return Err(match name_error {
+            true => ValidationError::NameNotCovered,
+            false => ValidationError::NameNotMatched,
+        });
+    };
+
+    // The next name is below the queried name, which is an empty non-terminal,
+    // see RFC 4035 section 3.1.3.2
+    if nsec.next_name.to_canonical().is_subdomain_of(qname) {
+        return match name_error {
+            true => Err(ValidationError::NameNotCovered),
+            false => Ok(Denial::NoData),
+        };
+    }
+
+    // The closest encloser is the longest ancestor of the queried name that exists in the zone,
+    // which is shared with either end of the covering record
+    let encloser_labels = common_labels(qname, owner).max(common_labels(qname, &nsec.next_name));
+    let labels = qname.get_labels();
+    let wildcard = wildcard_name(&labels[labels.len() - encloser_labels..]);
+
+    if name_error {
+        return match covering(&wildcard) {
+            Some(_) => Ok(Denial::NameError),
+            None => Err(ValidationError::WildcardNotProven),
+        };
+    }
+
+    match records.iter().find(|(owner, _)| *owner == wildcard) {
+        Some((_, nsec)) if has_type(&nsec.type_bit_maps, qtype) => Err(ValidationError::TypeExists),
+        Some(_) => Ok(Denial::WildcardNoData),
+        None => Err(ValidationError::WildcardNotProven),
+    }
+}
+
+struct Nsec3Chain<'r, 'a, D: ?Sized> {
+    records: Vec<(Name<'static>, &'r NSEC3<'a>)>,
+    digest_provider: &'r D,
+}
+
+impl<'r, 'a, D: DigestProvider + ?Sized> Nsec3Chain<'r, 'a, D> {
+    fn matching(&self, name: &Name<'_>) -> Option<&'r NSEC3<'a>> {
+        self.records
+            .iter()
+            .find(|(owner, nsec3)| nsec3.matches(owner, name, self.digest_provider))
+            .map(|(_, nsec3)| *nsec3)
+    }
+
+    /// Returns the record matching `name` that can prove the absence of `qtype` at it
+    fn matching_no_data(&self, name: &Name<'_>, qtype: u16) -> Option<&'r NSEC3<'a>> {
+        self.matching(name)
+            .filter(|nsec3| proves_no_data(&nsec3.type_bit_maps, qtype))
+    }
+
+    fn covering(&self, name: &Name<'_>) -> Option<&'r NSEC3<'a>> {
+        self.records
+            .iter()
+            .find(|(owner, nsec3)| nsec3.covers(owner, name, self.digest_provider))
+            .map(|(_, nsec3)| *nsec3)
+    }
+
+    /// Closest encloser proof, [RFC 5155](https://www.rfc-editor.org/rfc/rfc5155#section-8.3).
+    ///
+    /// Returns the wildcard at the closest encloser, and the record covering the next closer
+    /// name, which is the closest encloser with one more label of the queried name.
+    ///
+    /// Delegations are not closest enclosers, the names below them belong to another zone
+    fn closest_encloser(
+        &self,
+        qname: &Name<'static>,
+    ) -> Result<(Name<'static>, &'r NSEC3<'a>), ValidationError> {
+        let labels = qname.get_labels();
+        for i in 1..=labels.len() {
+            match self.matching(&Name::new_with_labels(&labels[i..])) {
+                Some(encloser) if !is_delegation(&encloser.type_bit_maps) => {}
+                _ => continue,
+            }
+
+            let next_closer = Name::new_with_labels(&labels[i - 1..]);
+            let covering = self
+                .covering(&next_closer)
+                .ok_or(ValidationError::NameNotCovered)?;
+            return Ok((wildcard_name(&labels[i..]), covering));
+        }
+
+        Err(ValidationError::ClosestEncloserNotProven)
+    }
+
+    fn verify(
+        &self,
+        qname: &Name<'static>,
+        qtype: u16,
+        name_error: bool,
+    ) -> Result<Denial, ValidationError> {
+        if name_error {
+            let (wildcard, next_closer) = self.closest_encloser(qname)?;
+            if next_closer.is_opt_out() {
+                return Ok(Denial::OptOut);
+            }
+
+            return match self.covering(&wildcard) {
+                Some(_) => Ok(Denial::NameError),
+                None => Err(ValidationError::WildcardNotProven),
+            };
+        }
+
+        if let Some(nsec3) = self.matching_no_data(qname, qtype) {
+            return match has_type(&nsec3.type_bit_maps, qtype) {
+                true => Err(ValidationError::TypeExists),
+                false => Ok(Denial::NoData),
+            };
+        }
+
+        let (wildcard, next_closer) = self.closest_encloser(qname)?;
+        // A DS query for an unsigned delegation in an Opt-Out span has no matching record
+        if qtype == u16::from(TYPE::DS) {
+            return match next_closer.is_opt_out() {
+                true => Ok(Denial::OptOut),
+                false => Err(ValidationError::NameNotMatched),
+            };
+        }
+
+        match self.matching(&wildcard) {
+            Some(nsec3) if has_type(&nsec3.type_bit_maps, qtype) => {
+                Err(ValidationError::TypeExists)
+            }
+            Some(_) => Ok(Denial::WildcardNoData),
+            None => Err(ValidationError::WildcardNotProven),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dns::dnssec::sha::Sha, Question, ResourceRecord, CLASS, QTYPE};
+
+    fn response(rcode: RCODE, qname: &str, qtype: TYPE, records: &[&str]) -> Packet<'static> {
+        let mut packet = Packet::new_reply(1);
+        *packet.rcode_mut() = rcode;
+        packet.questions.push(Question::new(
+            Name::new(qname).unwrap().into_owned(),
+            QTYPE::TYPE(qtype),
+            CLASS::IN.into(),
+            false,
+        ));
+        packet.name_servers = records
+            .iter()
+            .map(|text| text.parse::<ResourceRecord>().unwrap())
+            .collect();
+        packet
+    }
+
+    fn expect_failure(packet: &Packet, error: ValidationError) {
+        assert_eq!(
+            verify_denial(packet, &Sha),
+            Err(SimpleDnsError::ValidationFailed(error))
+        );
+    }
+
+    const NSEC_ZONE: &[&str] = &[
+        "example. 3600 IN NSEC a.example. NS SOA MX RRSIG NSEC DNSKEY",
+        "a.example. 3600 IN NSEC ai.example. NS DS RRSIG NSEC",
+        "ai.example. 3600 IN NSEC *.w.example. A HINFO AAAA RRSIG NSEC",
+        "*.w.example. 3600 IN NSEC x.w.example. MX RRSIG NSEC",
+        "x.w.example. 3600 IN NSEC xx.example. MX RRSIG NSEC",
+        "xx.example. 3600 IN NSEC example. A HINFO AAAA RRSIG NSEC",
+    ];
+
+    #[test]
+    fn nsec_name_error() {
+        // ai.example covers b.example, example covers the wildcard *.example
+        let packet = response(
+            RCODE::NameError,
+            "b.example",
+            TYPE::A,
+            &[NSEC_ZONE[2], NSEC_ZONE[0]],
+        );
+        assert_eq!(verify_denial(&packet, &Sha), Ok(Denial::NameError));
+
+        let packet = response(RCODE::NameError, "b.example", TYPE::A, &[NSEC_ZONE[2]]);
+        expect_failure(&packet, ValidationError::WildcardNotProven);
+
+        let packet = response(RCODE::NameError, "b.example", TYPE::A, &[NSEC_ZONE[0]]);
+        expect_failure(&packet, ValidationError::NameNotCovered);
+
+        // The record matching the wildcard proves that it exists
+        let packet = response(
+            RCODE::NameError,
+            "z.w.example",
+            TYPE::A,
+            &[NSEC_ZONE[4], NSEC_ZONE[3]],
+        );
+        expect_failure(&packet, ValidationError::WildcardNotProven);
+    }
+
+    #[test]
+    fn nsec_no_data() {
+        let packet = response(RCODE::NoError, "AI.example", TYPE::MX, &[NSEC_ZONE[2]]);
+        assert_eq!(verify_denial(&packet, &Sha), Ok(Denial::NoData));
+
+        let packet = response(RCODE::NoError, "ai.example", TYPE::AAAA, &[NSEC_ZONE[2]]);
+        expect_failure(&packet, ValidationError::TypeExists);
+
+        let packet = response(RCODE::NoError, "b.example", TYPE::MX, &[NSEC_ZONE[0]]);
+        expect_failure(&packet, ValidationError::NameNotMatched);
+
+        // x.w.example covers z.w.example, which is matched by *.w.example
+        let packet = response(
+            RCODE::NoError,
+            "z.w.example",
+            TYPE::AAAA,
+            &[NSEC_ZONE[4], NSEC_ZONE[3]],
+        );
+        assert_eq!(verify_denial(&packet, &Sha), Ok(Denial::WildcardNoData));
+
+        let packet = response(
+            RCODE::NoError,
+            "z.w.example",
+            TYPE::MX,
+            &[NSEC_ZONE[4], NSEC_ZONE[3]],
+        );
+        expect_failure(&packet, ValidationError::TypeExists);
+
+        let packet = response(RCODE::NoError, "z.w.example", TYPE::AAAA, &[NSEC_ZONE[4]]);
+        expect_failure(&packet, ValidationError::WildcardNotProven);
+
+        // Empty non-terminal, ai.example covers w.example and its next name is below it
+        let packet = response(RCODE::NoError, "w.example", TYPE::A, &[NSEC_ZONE[2]]);
+        assert_eq!(verify_denial(&packet, &Sha), Ok(Denial::NoData));
+
+        let packet = response(RCODE::NameError, "w.example", TYPE::A, &[NSEC_ZONE[2]]);
+        expect_failure(&packet, ValidationError::NameNotCovered);
+    }
+
+    #[test]
+    fn nsec_delegation() {
+        // a.example is a delegation, its record only proves the absence of DS records
+        let packet = response(RCODE::NoError, "a.example", TYPE::A, &[NSEC_ZONE[1]]);
+        expect_failure(&packet, ValidationError::NameNotMatched);
+
+        let packet = response(RCODE::NoError, "a.example", TYPE::DS, &[NSEC_ZONE[1]]);
+        expect_failure(&packet, ValidationError::TypeExists);
+
+        let delegation = "a.example. 3600 IN NSEC ai.example. NS RRSIG NSEC";
+        let packet = response(RCODE::NoError, "a.example", TYPE::DS, &[delegation]);
+        assert_eq!(verify_denial(&packet, &Sha), Ok(Denial::NoData));
+
+        let packet = response(
+            RCODE::NameError,
+            "b.a.example",
+            TYPE::A,
+            &[NSEC_ZONE[1], NSEC_ZONE[0]],
+        );
+        expect_failure(&packet, ValidationError::NameNotCovered);
+    }
+
+    // NSEC3 chain of RFC 5155 appendix A, without the Opt-Out flag except for ai.example
+    const NSEC3_ZONE: &[&str] = &[
+        // example
+        "0p9mhaveqvm6t7vbl5lop2u3t2rp3tom.example. 3600 IN NSEC3 1 0 12 aabbccdd 2t7b4g4vsa5smi47k61mv5bv1a22bojr NS SOA MX RRSIG DNSKEY NSEC3PARAM",
+        // ns1.example
+        "2t7b4g4vsa5smi47k61mv5bv1a22bojr.example. 3600 IN NSEC3 1 0 12 aabbccdd 2vptu5timamqttgl4luu9kg21e0aor3s A RRSIG",
+        // a.example
+        "35mthgpgcu1qg68fab165klnsnk3dpvl.example. 3600 IN NSEC3 1 0 12 aabbccdd b4um86eghhds6nea196smvmlo4ors995 NS DS RRSIG",
+        // x.w.example
+        "b4um86eghhds6nea196smvmlo4ors995.example. 3600 IN NSEC3 1 0 12 aabbccdd gjeqe526plbf1g8mklp59enfd789njgi MX RRSIG",
+        // ai.example
+        "gjeqe526plbf1g8mklp59enfd789njgi.example. 3600 IN NSEC3 1 1 12 aabbccdd ji6neoaepv8b5o6k4ev33abha8ht9fgc A HINFO AAAA RRSIG",
+        // y.w.example
+        "ji6neoaepv8b5o6k4ev33abha8ht9fgc.example. 3600 IN NSEC3 1 0 12 aabbccdd k8udemvp1j2f7eg6jebps17vp3n8i58h",
+        // w.example
+        "k8udemvp1j2f7eg6jebps17vp3n8i58h.example. 3600 IN NSEC3 1 0 12 aabbccdd q04jkcevqvmu85r014c7dkba38o0ji5r",
+        // ns2.example
+        "q04jkcevqvmu85r014c7dkba38o0ji5r.example. 3600 IN NSEC3 1 0 12 aabbccdd r53bq7cc2uvmubfu5ocmm6pers9tk9en A RRSIG",
+        // *.w.example
+        "r53bq7cc2uvmubfu5ocmm6pers9tk9en.example. 3600 IN NSEC3 1 0 12 aabbccdd t644ebqk9bibcna874givr6joj62mlhv MX RRSIG",
+    ];
+
+    #[test]
+    fn nsec3_name_error() {
+        // x.w.example is the closest encloser, example covers the next closer c.x.w.example and
+        // a.example covers the wildcard *.x.w.example
+        let packet = response(
+            RCODE::NameError,
+            "a.c.x.w.example",
+            TYPE::A,
+            &[NSEC3_ZONE[3], NSEC3_ZONE[0], NSEC3_ZONE[2]],
+        );
+        assert_eq!(verify_denial(&packet, &Sha), Ok(Denial::NameError));
+
+        let packet = response(
+            RCODE::NameError,
+            "a.c.x.w.example",
+            TYPE::A,
+            &[NSEC3_ZONE[2]],
+        );
+        expect_failure(&packet, ValidationError::ClosestEncloserNotProven);
+
+        // w.example is the closest encloser of a.z.w.example, the wildcard *.w.example exists
+        let packet = response(
+            RCODE::NameError,
+            "a.z.w.example",
+            TYPE::A,
+            &[NSEC3_ZONE[6], NSEC3_ZONE[7], NSEC3_ZONE[8]],
+        );
+        expect_failure(&packet, ValidationError::WildcardNotProven);
+
+        // The next closer name b.example is covered by the Opt-Out record of ai.example
+        let packet = response(
+            RCODE::NameError,
+            "b.example",
+            TYPE::A,
+            &[NSEC3_ZONE[0], NSEC3_ZONE[4]],
+        );
+        assert_eq!(verify_denial(&packet, &Sha), Ok(Denial::OptOut));
+
+        let packet = response(RCODE::NameError, "b.example", TYPE::A, &[NSEC3_ZONE[0]]);
+        expect_failure(&packet, ValidationError::NameNotCovered);
+    }
+
+    #[test]
+    fn nsec3_no_data() {
+        let packet = response(RCODE::NoError, "ns1.example", TYPE::MX, &[NSEC3_ZONE[1]]);
+        assert_eq!(verify_denial(&packet, &Sha), Ok(Denial::NoData));
+
+        let packet = response(RCODE::NoError, "ns1.example", TYPE::A, &[NSEC3_ZONE[1]]);
+        expect_failure(&packet, ValidationError::TypeExists);
+
+        // Empty non-terminal
+        let packet = response(RCODE::NoError, "y.w.example", TYPE::A, &[NSEC3_ZONE[5]]);
+        assert_eq!(verify_denial(&packet, &Sha), Ok(Denial::NoData));
+
+        // Unsigned delegation in an Opt-Out span
+        let packet = response(
+            RCODE::NoError,
+            "b.example",
+            TYPE::DS,
+            &[NSEC3_ZONE[0], NSEC3_ZONE[4]],
+        );
+        assert_eq!(verify_denial(&packet, &Sha), Ok(Denial::OptOut));
+
+        let packet = response(
+            RCODE::NoError,
+            "a.c.x.w.example",
+            TYPE::DS,
+            &[NSEC3_ZONE[3], NSEC3_ZONE[0]],
+        );
+        expect_failure(&packet, ValidationError::NameNotMatched);
+
+        // w.example is the closest encloser, ns2.example covers the next closer z.w.example
+        let packet = response(
+            RCODE::NoError,
+            "a.z.w.example",
+            TYPE::AAAA,
+            &[NSEC3_ZONE[6], NSEC3_ZONE[7], NSEC3_ZONE[8]],
+        );
+        assert_eq!(verify_denial(&packet, &Sha), Ok(Denial::WildcardNoData));
+
+        let packet = response(
+            RCODE::NoError,
+            "a.z.w.example",
+            TYPE::MX,
+            &[NSEC3_ZONE[6], NSEC3_ZONE[7], NSEC3_ZONE[8]],
+        );
+        expect_failure(&packet, ValidationError::TypeExists);
+
+        let packet = response(
+            RCODE::NoError,
+            "a.z.w.example",
+            TYPE::AAAA,
+            &[NSEC3_ZONE[6], NSEC3_ZONE[7]],
+        );
+        expect_failure(&packet, ValidationError::WildcardNotProven);
+    }
+
+    #[test]
+    fn nsec3_delegation() {
+        // a.example is a delegation, its record only proves the absence of DS records
+        let packet = response(
+            RCODE::NoError,
+            "a.example",
+            TYPE::A,
+            &[NSEC3_ZONE[2], NSEC3_ZONE[0]],
+        );
+        expect_failure(&packet, ValidationError::NameNotCovered);
+
+        let packet = response(RCODE::NoError, "a.example", TYPE::DS, &[NSEC3_ZONE[2]]);
+        expect_failure(&packet, ValidationError::TypeExists);
+
+        // The record of a.example matches the closest encloser of c.a.example, and covers both
+        // the next closer c.a.example and the wildcard *.a.example
+        let packet = response(RCODE::NameError, "c.a.example", TYPE::A, &[NSEC3_ZONE[2]]);
+        expect_failure(&packet, ValidationError::ClosestEncloserNotProven);
+    }
+
+    #[test]
+    fn not_negative_response() {
+        let packet = response(RCODE::NoError, "example", TYPE::A, &[]);
+        expect_failure(&packet, ValidationError::MissingDenialRecords);
+
+        let mut packet = response(RCODE::NoError, "example", TYPE::A, NSEC_ZONE);
+        packet
+            .answers
+            .push("example. 3600 IN A 192.0.2.1".parse().unwrap());
+        expect_failure(&packet, ValidationError::NotNegativeResponse);
+
+        let packet = response(RCODE::ServerFailure, "example", TYPE::A, NSEC_ZONE);
+        expect_failure(&packet, ValidationError::NotNegativeResponse);
+
+        let mut packet = response(RCODE::NameError, "example", TYPE::A, NSEC_ZONE);
+        packet.questions.clear();
+        expect_failure(&packet, ValidationError::NotNegativeResponse);
+
+        // NSEC3 records with unknown hash algorithms are ignored
+        let unknown_algorithm = NSEC3_ZONE[1].replace("NSEC3 1 0", "NSEC3 2 0");
+        let packet = response(
+            RCODE::NoError,
+            "ns1.example",
+            TYPE::MX,
+            &[&unknown_algorithm],
+        );
+        expect_failure(&packet, ValidationError::MissingDenialRecords);
+    }
+}
@@ -0,0 +1,126 @@
+//! DNSSEC support, see [RFC 4033](https://www.rfc-editor.org/rfc/rfc4033),
+//! [RFC 4034](https://www.rfc-editor.org/rfc/rfc4034) and [RFC 4035](https://www.rfc-editor.org/rfc/rfc4035)
+//!
+//! This crate does not implement any cryptographic algorithm. The cryptographic operations are
+//! delegated to a backend supplied by the caller, like [`SignatureVerifier`], so it can be
+//! implemented on top of any cryptographic library.
+
+use crate::{
+    lib::{fmt, vec},
+    Label, Name,
+};
+
+mod verify;
+pub use verify::{signed_data, verify_rrsig, SignatureVerifier};
+
+mod sign;
+pub use sign::{Signer, ZoneSigner};
+
+mod nsec3;
+pub use nsec3::{nsec3_hash, NSEC3_SHA1};
+
+mod denial;
+pub use denial::{verify_denial, Denial};
+
+mod delegation;
+pub use delegation::parent_ds_set;
+
+mod sig0;
+
+#[cfg(test)]
+pub(crate) mod sha;
+
+/// Flag of the DNSKEY records that hold a DNSSEC zone key
+const ZONE_KEY_FLAG: u16 = 0x0100;
+
+/// Protocol value of every DNSKEY record, [RFC 4034](https://www.rfc-editor.org/rfc/rfc4034#section-2.1.2)
+const DNSKEY_PROTOCOL: u8 = 3;
+
+/// Reasons for a signature to fail validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// The RRset is empty, or its records do not share the owner name, class and the type covered by the signature
+    InvalidRRset,
+    /// The signer name is not the owner of the key or is not an ancestor of the RRset owner name
+    SignerMismatch,
+    /// The labels field is greater than the number of labels of the RRset owner name
+    InvalidLabels,
+    /// The algorithm or the key tag of the key do not match the signature
+    KeyMismatch,
+    /// The key is not a DNSSEC zone key
+    InvalidKey,
+    /// The signature inception is in the future
+    SignatureNotYetValid,
+    /// The signature expiration is in the past
+    SignatureExpired,
+    /// The signature algorithm is not supported by the cryptographic backend
+    UnsupportedAlgorithm,
+    /// The signature does not match the signed data
+    InvalidSignature,
+    /// The response is not a negative response (NXDOMAIN or NODATA) to a question
+    NotNegativeResponse,
+    /// The authority section has no NSEC or NSEC3 records
+    MissingDenialRecords,
+    /// No record proves that the queried name, or the next closer name, does not exist
+    NameNotCovered,
+    /// No record matches the queried name of a NODATA response
+    NameNotMatched,
+    /// No NSEC3 record matches an ancestor of the queried name
+    ClosestEncloserNotProven,
+    /// The wildcard at the closest encloser is not proven to be absent, for NXDOMAIN responses,
+    /// or present, for wildcard NODATA responses
+    WildcardNotProven,
+    /// The queried type, or a CNAME, is present in the type bit maps
+    TypeExists,
+    /// The CDS and CDNSKEY records of the child do not refer to the same keys, or delete records
+    /// are mixed with other records
+    InconsistentChildRRset,
+    /// The message has no SIG(0) record at the end of the additional records
+    MissingSignature,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::InvalidRRset => write!(f, "RRset does not match the signature"),
+            ValidationError::SignerMismatch => write!(f, "Signer name does not match the key"),
+            ValidationError::InvalidLabels => write!(f, "Labels field is invalid"),
+            ValidationError::KeyMismatch => write!(f, "Key does not match the signature"),
+            ValidationError::InvalidKey => write!(f, "Key is not a zone key"),
+            ValidationError::SignatureNotYetValid => write!(f, "Signature is not valid yet"),
+            ValidationError::SignatureExpired => write!(f, "Signature is expired"),
+            ValidationError::UnsupportedAlgorithm => write!(f, "Algorithm is not supported"),
+            ValidationError::InvalidSignature => write!(f, "Signature is invalid"),
+            ValidationError::NotNegativeResponse => write!(f, "Response is not negative"),
+            ValidationError::MissingDenialRecords => write!(f, "Missing NSEC or NSEC3 records"),
+            ValidationError::NameNotCovered => write!(f, "Name is not proven to not exist"),
+            ValidationError::NameNotMatched => write!(f, "Name is not proven to exist"),
+            ValidationError::ClosestEncloserNotProven => {
+                write!(f, "Closest encloser is not proven")
+            }
+            ValidationError::WildcardNotProven => write!(f, "Wildcard is not proven"),
+            ValidationError::TypeExists => write!(f, "Type exists"),
+            ValidationError::InconsistentChildRRset => {
+                write!(f, "CDS and CDNSKEY records are inconsistent")
+            }
+            ValidationError::MissingSignature => write!(f, "Message is not signed"),
+        }
+    }
+}
+
+/// Number of labels of a name, as used by the labels field of the RRSIG, which does not count
+/// the wildcard label
+fn label_count(labels: &[Label<'_>]) -> usize {
+    match labels.first() {
+        Some(label) if label.as_ref() == b"*" => labels.len() - 1,
+        _ => labels.len(),
+    }
+}
+
+/// Returns the wildcard name whose closest encloser is formed by `labels`
+fn wildcard_name(labels: &[Label<'_>]) -> Name<'static> {
+    let mut wildcard = vec![Label::new_unchecked(&b"*"[..])];
+    wildcard.extend(labels.iter().map(|label| label.clone().into_owned()));
+    Name::new_with_labels(&wildcard)
+}
@@ -0,0 +1,68 @@
+use crate::dns::WireFormat;
+use crate::lib::Vec;
+use crate::Name;
+use crate::{DigestAlgorithm, DigestProvider};
+
+/// Hash algorithm number of SHA-1, the only one defined for NSEC3 records,
+/// [RFC 5155](https://www.rfc-editor.org/rfc/rfc5155#section-11)
+pub const NSEC3_SHA1: u8 = 1;
+
+/// Calculates the hashed owner name of `name`, as defined by
+/// [RFC 5155](https://www.rfc-editor.org/rfc/rfc5155#section-5)
+///
+/// The name is hashed in canonical form, the salt is appended to every iteration and the hash is
+/// applied `iterations` additional times. The SHA-1 digest is calculated by the
+/// `digest_provider`, None is returned if it does not support SHA-1
+pub fn nsec3_hash<D: DigestProvider + ?Sized>(
+    name: &Name<'_>,
+    salt: &[u8],
+    iterations: u16,
+    digest_provider: &D,
+) -> Option<Vec<u8>> {
+    let mut data = Vec::with_capacity(name.len() + salt.len());
+    for label in name.get_labels() {
+        data.push(label.len() as u8);
+        data.extend(label.as_ref().iter().map(u8::to_ascii_lowercase));
+    }
+    data.push(0);
+    data.extend_from_slice(salt);
+
+    let mut hash = digest_provider.digest(DigestAlgorithm::Sha1, &data)?;
+    for _ in 0..iterations {
+        data.clear();
+        data.extend_from_slice(&hash);
+        data.extend_from_slice(salt);
+        hash = digest_provider.digest(DigestAlgorithm::Sha1, &data)?;
+    }
+
+    Some(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::dnssec::sha::Sha;
+    use crate::encoding::base32hex;
+
+    #[test]
+    fn hash_rfc5155_names() {
+        // RFC 5155 appendix A, salt aabbccdd and 12 iterations
+        let hash = |name| {
+            base32hex::encode(
+                &nsec3_hash(
+                    &Name::new_unchecked(name),
+                    &[0xaa, 0xbb, 0xcc, 0xdd],
+                    12,
+                    &Sha,
+                )
+                .unwrap(),
+            )
+        };
+
+        assert_eq!(hash("example"), "0P9MHAVEQVM6T7VBL5LOP2U3T2RP3TOM");
+        assert_eq!(hash("a.example"), "35MTHGPGCU1QG68FAB165KLNSNK3DPVL");
+        assert_eq!(hash("ai.example"), "GJEQE526PLBF1G8MKLP59ENFD789NJGI");
+        assert_eq!(hash("*.w.example"), "R53BQ7CC2UVMUBFU5OCMM6PERS9TK9EN");
+        assert_eq!(hash("XX.Example"), "T644EBQK9BIBCNA874GIVR6JOJ62MLHV");
+    }
+}
@@ -0,0 +1,303 @@
+use super::{signed_data, SignatureVerifier, Signer, ValidationError};
+use crate::{
+    lib::{Cow, Vec},
+    rdata::{RData, KEY, RRSIG, SIG},
+    Name, Packet, ResourceRecord, SimpleDnsError, CLASS,
+};
+
+/// Flags of the KEY records that do not hold a key, [RFC 2535](https://www.rfc-editor.org/rfc/rfc2535#section-3.1.2)
+const NO_KEY_FLAGS: u16 = 0xC000;
+
+/// Protocol values of the KEY records that can be used by SIG(0), DNSSEC and any protocol,
+/// [RFC 2535](https://www.rfc-editor.org/rfc/rfc2535#section-3.1.3)
+const SIG0_KEY_PROTOCOLS: [u8; 2] = [3, 255];
+
+impl<'a> Packet<'a> {
+    /// Signs this packet with the private key of the `key` record owned by `signer_name`,
+    /// appending a SIG(0) record to the additional records, as defined by
+    /// [RFC 2931](https://www.rfc-editor.org/rfc/rfc2931#section-3), and returns the signed
+    /// message in wire format, with compression enabled.
+    ///
+    /// The signature is valid from `inception` until `expiration`, in seconds since Jan 1 1970.
+    /// When signing a response, `request` holds the request as it was received, which is also
+    /// covered by the signature. A previous SIG(0) record at the end of the additional records
+    /// is replaced.
+    ///
+    /// The packet must not be changed after it is signed, the returned bytes must be sent as
+    /// they are
+    pub fn sign_sig0<S: Signer + ?Sized>(
+        &mut self,
+        signer_name: Name<'a>,
+        key: &KEY<'_>,
+        inception: u32,
+        expiration: u32,
+        request: Option<&[u8]>,
+        signer: &S,
+    ) -> crate::Result<Vec<u8>> {
+        if self.additional_records.last().is_some_and(is_sig0) {
+            self.additional_records.pop();
+        }
+        let message = self.build_bytes_vec_compressed()?;
+
+        let mut sig = RRSIG {
+            type_covered: 0,
+            algorithm: key.algorithm,
+            labels: 0,
+            original_ttl: 0,
+            signature_expiration: expiration,
+            signature_inception: inception,
+            key_tag: key.key_tag(),
+            signer_name,
+            signature: Cow::Borrowed(&[]),
+        };
+        let data = sig0_signed_data(&sig, request, &message)?;
+        sig.signature = signer.sign(sig.algorithm, &data)?.into();
+
+        self.additional_records.push(ResourceRecord::new(
+            Name::new_unchecked(""),
+            CLASS::IN,
+            0,
+            RData::SIG(SIG(sig)),
+        ));
+        self.build_bytes_vec_compressed()
+    }
+
+    /// Parses a SIG(0) signed `message` and verifies its signature with the public key of the
+    /// `key` record, as defined by [RFC 2931](https://www.rfc-editor.org/rfc/rfc2931#section-3).
+    ///
+    /// The SIG(0) record must be the last additional record. `now` is the current time in
+    /// seconds since Jan 1 1970, the signature validity period is compared using serial number
+    /// arithmetic. When verifying a response, `request` holds the request as it was sent.
+    ///
+    /// Returns the parsed packet, with the SIG(0) record as its last additional record, or
+    /// [`SimpleDnsError::ValidationFailed`] with the reason of the failure
+    pub fn verify_sig0<V: SignatureVerifier + ?Sized>(
+        message: &'a [u8],
+        key: &ResourceRecord<'_>,
+        now: u32,
+        request: Option<&[u8]>,
+        verifier: &V,
+    ) -> crate::Result<Self> {
+        let failed = SimpleDnsError::ValidationFailed;
+
+        let packet = Self::parse(message)?;
+        let rr = packet
+            .additional_records
+            .last()
+            .filter(|rr| is_sig0(rr))
+            .ok_or(failed(ValidationError::MissingSignature))?;
+        let RData::SIG(SIG(sig)) = &rr.rdata else {
+            unreachable!()
+        };
+
+        if sig.signer_name.to_canonical() != key.name.to_canonical() {
+            return Err(failed(ValidationError::SignerMismatch));
+        }
+
+        if (now.wrapping_sub(sig.signature_inception) as i32) < 0 {
+            return Err(failed(ValidationError::SignatureNotYetValid));
+        }
+        if (sig.signature_expiration.wrapping_sub(now) as i32) < 0 {
+            return Err(failed(ValidationError::SignatureExpired));
+        }
+
+        let RData::KEY(key) = &key.rdata else {
+            return Err(failed(ValidationError::InvalidKey));
+        };
+        if key.flags & NO_KEY_FLAGS == NO_KEY_FLAGS || !SIG0_KEY_PROTOCOLS.contains(&key.protocol) {
+            return Err(failed(ValidationError::InvalidKey));
+        }
+        if key.algorithm != sig.algorithm || key.key_tag() != sig.key_tag {
+            return Err(failed(ValidationError::KeyMismatch));
+        }
+
+        if !verifier.supports_algorithm(sig.algorithm) {
+            return Err(failed(ValidationError::UnsupportedAlgorithm));
+        }
+
+        let unsigned = Self::unsigned_message(message, rr)?;
+        let data = sig0_signed_data(sig, request, &unsigned)?;
+        if !verifier.verify(sig.algorithm, &key.public_key, &data, &sig.signature) {
+            return Err(failed(ValidationError::InvalidSignature));
+        }
+
+        Ok(packet)
+    }
+}
+
+/// Returns true if the record is a SIG(0) transaction signature, owned by the root
+fn is_sig0(record: &ResourceRecord<'_>) -> bool {
+    matches!(&record.rdata, RData::SIG(sig) if sig.is_sig0()) && record.name.get_labels().is_empty()
+}
+
+/// Builds the data covered by a SIG(0) signature: the SIG RDATA without the signature, followed
+/// by the request, for responses, and the message without the SIG(0) record
+fn sig0_signed_data(
+    sig: &RRSIG<'_>,
+    request: Option<&[u8]>,
+    message: &[u8],
+) -> crate::Result<Vec<u8>> {
+    let mut data = signed_data(&[], sig)?;
+    if let Some(request) = request {
+        data.extend_from_slice(request);
+    }
+    data.extend_from_slice(message);
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Question, TYPE};
+
+    const INCEPTION: u32 = 1_700_000_000;
+    const EXPIRATION: u32 = INCEPTION + 300;
+
+    /// Signs with the signed data itself, so the verifier can check what was signed
+    struct Echo;
+
+    impl Signer for Echo {
+        fn sign(&self, _algorithm: u8, data: &[u8]) -> crate::Result<Vec<u8>> {
+            Ok(data.to_vec())
+        }
+    }
+
+    impl SignatureVerifier for Echo {
+        fn supports_algorithm(&self, algorithm: u8) -> bool {
+            algorithm == 15
+        }
+
+        fn verify(&self, _algorithm: u8, _key: &[u8], data: &[u8], signature: &[u8]) -> bool {
+            data == signature
+        }
+    }
+
+    fn key_record() -> ResourceRecord<'static> {
+        "host.example.com. 3600 IN KEY 512 3 15 l02Woi0iS8Aa25FQkUd9RMzZHJpBoRQwAQEX1SxZJA4="
+            .parse()
+            .unwrap()
+    }
+
+    fn update_packet() -> Packet<'static> {
+        let mut update = Packet::new_query(0x1234);
+        *update.opcode_mut() = crate::OPCODE::Update;
+        update.questions.push(Question::new(
+            Name::new_unchecked("example.com"),
+            TYPE::SOA.into(),
+            CLASS::IN.into(),
+            false,
+        ));
+        update
+    }
+
+    fn sign(packet: &mut Packet<'static>, request: Option<&[u8]>) -> Vec<u8> {
+        let RData::KEY(key) = key_record().rdata else {
+            unreachable!()
+        };
+        packet
+            .sign_sig0(
+                Name::new_unchecked("host.example.com"),
+                &key,
+                INCEPTION,
+                EXPIRATION,
+                request,
+                &Echo,
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn sign_and_verify_sig0() {
+        let mut update = update_packet();
+        let signed = sign(&mut update, None);
+
+        let sig = update.additional_records.last().unwrap();
+        assert!(sig.name.get_labels().is_empty());
+        let RData::SIG(sig) = &sig.rdata else {
+            unreachable!()
+        };
+        assert!(sig.is_sig0());
+        let RData::KEY(key) = key_record().rdata else {
+            unreachable!()
+        };
+        assert_eq!(sig.key_tag, key.key_tag());
+
+        // The signature covers the SIG RDATA and the message without the SIG(0) record
+        let unsigned = update_packet().build_bytes_vec_compressed().unwrap();
+        assert!(sig.signature.ends_with(&unsigned));
+
+        let parsed =
+            Packet::verify_sig0(&signed, &key_record(), INCEPTION + 10, None, &Echo).unwrap();
+        assert_eq!(parsed.additional_records.len(), 1);
+
+        // Signing again replaces the signature
+        assert_eq!(sign(&mut update, None), signed);
+        assert_eq!(update.additional_records.len(), 1);
+
+        let mut tampered = signed.clone();
+        tampered[2] ^= 0x01;
+        assert_eq!(
+            Packet::verify_sig0(&tampered, &key_record(), INCEPTION, None, &Echo).unwrap_err(),
+            SimpleDnsError::ValidationFailed(ValidationError::InvalidSignature)
+        );
+        assert_eq!(
+            Packet::verify_sig0(&signed, &key_record(), EXPIRATION + 1, None, &Echo).unwrap_err(),
+            SimpleDnsError::ValidationFailed(ValidationError::SignatureExpired)
+        );
+
+        let mut other_key = key_record();
+        other_key.name = Name::new_unchecked("other.example.com");
+        assert_eq!(
+            Packet::verify_sig0(&signed, &other_key, INCEPTION, None, &Echo).unwrap_err(),
+            SimpleDnsError::ValidationFailed(ValidationError::SignerMismatch)
+        );
+
+        let unsigned = update_packet().build_bytes_vec().unwrap();
+        assert_eq!(
+            Packet::verify_sig0(&unsigned, &key_record(), INCEPTION, None, &Echo).unwrap_err(),
+            SimpleDnsError::ValidationFailed(ValidationError::MissingSignature)
+        );
+    }
+
+    #[test]
+    fn sign_and_verify_sig0_response() {
+        let mut update = update_packet();
+        let request = sign(&mut update, None);
+
+        // The SIG(0) record of the request is replaced
+        let mut response = update.into_reply();
+        let signed = sign(&mut response, Some(&request));
+
+        assert!(
+            Packet::verify_sig0(&signed, &key_record(), INCEPTION, Some(&request), &Echo).is_ok()
+        );
+        assert_eq!(
+            Packet::verify_sig0(&signed, &key_record(), INCEPTION, None, &Echo).unwrap_err(),
+            SimpleDnsError::ValidationFailed(ValidationError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn reject_invalid_keys() {
+        let mut update = update_packet();
+        let signed = sign(&mut update, None);
+
+        let mut no_key = key_record();
+        if let RData::KEY(key) = &mut no_key.rdata {
+            key.0.flags |= NO_KEY_FLAGS;
+        }
+        assert_eq!(
+            Packet::verify_sig0(&signed, &no_key, INCEPTION, None, &Echo).unwrap_err(),
+            SimpleDnsError::ValidationFailed(ValidationError::InvalidKey)
+        );
+
+        let mut dnskey = key_record();
+        if let RData::KEY(key) = dnskey.rdata {
+            dnskey.rdata = RData::DNSKEY(key.0);
+        }
+        assert_eq!(
+            Packet::verify_sig0(&signed, &dnskey, INCEPTION, None, &Echo).unwrap_err(),
+            SimpleDnsError::ValidationFailed(ValidationError::InvalidKey)
+        );
+    }
+}
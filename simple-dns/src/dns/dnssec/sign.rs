@@ -0,0 +1,364 @@
+use super::{label_count, signed_data, DNSKEY_PROTOCOL, ZONE_KEY_FLAG};
+use crate::{
+    lib::{BTreeMap, Cow, Vec},
+    rdata::{RData, RRSIG},
+    Name, ResourceRecord, SimpleDnsError, TYPE,
+};
+
+/// Cryptographic backend used to create DNSSEC signatures.
+///
+/// `simple-dns` does not implement any cryptographic algorithm, this trait must be implemented
+/// on top of a cryptographic library to sign records with a [`ZoneSigner`]
+pub trait Signer {
+    /// Signs `data` with the private key of the DNSKEY used by the [`ZoneSigner`], or of the KEY
+    /// used by [`Packet::sign_sig0`](crate::Packet::sign_sig0), returning the signature in the
+    /// format defined for the `algorithm`.
+    ///
+    /// Failures are expected to be returned as [`SimpleDnsError::SigningFailed`]
+    fn sign(&self, algorithm: u8, data: &[u8]) -> crate::Result<Vec<u8>>;
+}
+
+/// Creates the RRSIG records of the RRsets of a zone, as defined by
+/// [RFC 4035](https://www.rfc-editor.org/rfc/rfc4035#section-2.2).
+///
+/// The owner of the DNSKEY record is the zone apex and the signer name of every signature.
+///
+/// ```
+/// # use simple_dns::{dnssec::{Signer, ZoneSigner}, zone::ZoneParser, rdata::RData, ResourceRecord};
+/// # struct Ed25519;
+/// # impl Signer for Ed25519 {
+/// #     fn sign(&self, _: u8, _: &[u8]) -> simple_dns::Result<Vec<u8>> { Ok(vec![0; 64]) }
+/// # }
+/// let dnskey: ResourceRecord = "example.com. 3600 IN DNSKEY 257 3 15 \
+///     l02Woi0iS8Aa25FQkUd9RMzZHJpBoRQwAQEX1SxZJA4=".parse()?;
+/// let signer = ZoneSigner::new(&dnskey, Ed25519, 1438207200, 1440021600)?;
+///
+/// let zone = "$ORIGIN example.com.\n$TTL 3600\n@ MX 10 mail\nmail A 192.0.2.1\nmail A 192.0.2.2";
+/// let records = ZoneParser::new(zone).collect::<Result<Vec<_>, _>>()?;
+///
+/// let signatures = signer.sign_zone(&records)?;
+/// assert_eq!(signatures.len(), 2);
+/// assert_eq!(signatures[1].name.to_string(), "mail.example.com");
+///
+/// let RData::RRSIG(rrsig) = &signatures[1].rdata else { unreachable!() };
+/// assert_eq!(rrsig.labels, 3);
+/// assert_eq!(rrsig.key_tag, 3613);
+/// # Ok::<(), simple_dns::SimpleDnsError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ZoneSigner<S> {
+    zone: Name<'static>,
+    algorithm: u8,
+    key_tag: u16,
+    inception: u32,
+    expiration: u32,
+    signer: S,
+}
+
+impl<S: Signer> ZoneSigner<S> {
+    /// Creates a signer for the zone of the `dnskey` record. The signatures are valid from
+    /// `inception` until `expiration`, in seconds since Jan 1 1970.
+    ///
+    /// Returns [`SimpleDnsError::AttemptedInvalidOperation`] if the record is not a DNSSEC zone key
+    pub fn new(
+        dnskey: &ResourceRecord<'_>,
+        signer: S,
+        inception: u32,
+        expiration: u32,
+    ) -> crate::Result<Self> {
+        let RData::DNSKEY(key) = &dnskey.rdata else {
+            return Err(SimpleDnsError::AttemptedInvalidOperation);
+        };
+        if key.protocol != DNSKEY_PROTOCOL || key.flags & ZONE_KEY_FLAG == 0 {
+            return Err(SimpleDnsError::AttemptedInvalidOperation);
+        }
+
+        Ok(Self {
+            zone: dnskey.name.to_canonical(),
+            algorithm: key.algorithm,
+            key_tag: key.key_tag(),
+            inception,
+            expiration,
+            signer,
+        })
+    }
+
+    /// Signs a single RRset, returning its RRSIG record.
+    ///
+    /// The records must share the owner name, class and type, and the owner name must be within
+    /// the zone, otherwise [`SimpleDnsError::AttemptedInvalidOperation`] is returned.
+    /// The TTL of the RRSIG is the TTL of the RRset, or the smallest TTL if the records disagree
+    pub fn sign_rrset(
+        &self,
+        rrset: &[ResourceRecord<'_>],
+    ) -> crate::Result<ResourceRecord<'static>> {
+        let Some(first) = rrset.first() else {
+            return Err(SimpleDnsError::AttemptedInvalidOperation);
+        };
+        let owner = first.name.to_canonical();
+        let type_code = first.rdata.type_code();
+
+        let is_same_rrset = |record: &ResourceRecord| {
+            record.name.to_canonical() == owner
+                && record.class == first.class
+                && record.rdata.type_code() == type_code
+        };
+        if matches!(type_code, TYPE::RRSIG | TYPE::OPT)
+            || !rrset.iter().all(is_same_rrset)
+            || !self.is_in_zone(&owner)
+        {
+            return Err(SimpleDnsError::AttemptedInvalidOperation);
+        }
+
+        let ttl = rrset
+            .iter()
+            .map(|record| record.ttl)
+            .min()
+            .unwrap_or(first.ttl);
+        let mut rrsig = RRSIG {
+            type_covered: type_code.into(),
+            algorithm: self.algorithm,
+            labels: label_count(owner.get_labels()) as u8,
+            original_ttl: ttl,
+            signature_expiration: self.expiration,
+            signature_inception: self.inception,
+            key_tag: self.key_tag,
+            signer_name: self.zone.clone(),
+            signature: Cow::Borrowed(&[]),
+        };
+
+        let data = signed_data(rrset, &rrsig)?;
+        rrsig.signature = Cow::Owned(self.signer.sign(self.algorithm, &data)?);
+
+        Ok(ResourceRecord::new(
+            first.name.clone().into_owned(),
+            first.class,
+            ttl,
+            RData::RRSIG(rrsig),
+        ))
+    }
+
+    /// Groups the `records` in RRsets by owner name, type and class, and signs every RRset,
+    /// returning the RRSIG records in the order the RRsets first appear.
+    ///
+    /// Only the authoritative data of the zone is signed. Records outside of the zone, RRSIG and
+    /// OPT records, the records of delegation points other than DS and NSEC, and the records
+    /// below delegation points (glue) are skipped.
+    pub fn sign_zone<'r, 'b: 'r>(
+        &self,
+        records: impl IntoIterator<Item = &'r ResourceRecord<'b>>,
+    ) -> crate::Result<Vec<ResourceRecord<'static>>> {
+        let records: Vec<&ResourceRecord> = records.into_iter().collect();
+
+        let delegations: Vec<Name<'static>> = records
+            .iter()
+            .filter(|record| record.rdata.type_code() == TYPE::NS)
+            .map(|record| record.name.to_canonical())
+            .filter(|owner| *owner != self.zone)
+            .collect();
+
+        let mut rrsets: Vec<Vec<ResourceRecord>> = Vec::new();
+        let mut positions: BTreeMap<(Name, u16, u16), usize> = BTreeMap::new();
+
+        for record in records {
+            let type_code = record.rdata.type_code();
+            let owner = record.name.to_canonical();
+
+            let is_authoritative = self.is_in_zone(&owner)
+                && !delegations
+                    .iter()
+                    .any(|delegation| owner.is_subdomain_of(delegation))
+                && (!delegations.contains(&owner) || matches!(type_code, TYPE::DS | TYPE::NSEC));
+            if !is_authoritative || matches!(type_code, TYPE::RRSIG | TYPE::OPT) {
+                continue;
+            }
+
+            let key = (owner, record.class as u16, type_code.into());
+            let position = *positions.entry(key).or_insert(rrsets.len());
+            if position == rrsets.len() {
+                rrsets.push(Vec::new());
+            }
+            rrsets[position].push(record.clone());
+        }
+
+        rrsets.iter().map(|rrset| self.sign_rrset(rrset)).collect()
+    }
+
+    fn is_in_zone(&self, owner: &Name<'_>) -> bool {
+        *owner == self.zone || owner.is_subdomain_of(&self.zone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dnssec::{verify_rrsig, SignatureVerifier},
+        zone::ZoneParser,
+    };
+
+    /// Deterministic signer, the signature is the signed data itself
+    struct FakeSigner;
+
+    impl Signer for FakeSigner {
+        fn sign(&self, _algorithm: u8, data: &[u8]) -> crate::Result<Vec<u8>> {
+            Ok(data.to_vec())
+        }
+    }
+
+    impl SignatureVerifier for FakeSigner {
+        fn supports_algorithm(&self, _algorithm: u8) -> bool {
+            true
+        }
+
+        fn verify(&self, _algorithm: u8, _key: &[u8], data: &[u8], signature: &[u8]) -> bool {
+            data == signature
+        }
+    }
+
+    const INCEPTION: u32 = 1438207200;
+    const EXPIRATION: u32 = 1440021600;
+
+    fn dnskey() -> ResourceRecord<'static> {
+        "example.com. 3600 IN DNSKEY 257 3 15 l02Woi0iS8Aa25FQkUd9RMzZHJpBoRQwAQEX1SxZJA4="
+            .parse()
+            .unwrap()
+    }
+
+    fn signer() -> ZoneSigner<FakeSigner> {
+        ZoneSigner::new(&dnskey(), FakeSigner, INCEPTION, EXPIRATION).unwrap()
+    }
+
+    fn parse(zone: &str) -> Vec<ResourceRecord<'static>> {
+        ZoneParser::new(zone)
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap()
+    }
+
+    fn rrsig_of(record: &ResourceRecord<'static>) -> RRSIG<'static> {
+        match &record.rdata {
+            RData::RRSIG(rrsig) => rrsig.clone(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn sign_rrset() {
+        let rrset = parse("example.com. 3600 IN MX 10 mail.example.com.");
+        let signature = signer().sign_rrset(&rrset).unwrap();
+
+        assert_eq!(signature.name, rrset[0].name);
+        assert_eq!(signature.class, rrset[0].class);
+        assert_eq!(signature.ttl, 3600);
+
+        // Same fields as the RFC 8080 example
+        let rrsig = rrsig_of(&signature);
+        assert_eq!(rrsig.type_covered, 15);
+        assert_eq!(rrsig.algorithm, 15);
+        assert_eq!(rrsig.labels, 2);
+        assert_eq!(rrsig.original_ttl, 3600);
+        assert_eq!(rrsig.signature_expiration, EXPIRATION);
+        assert_eq!(rrsig.signature_inception, INCEPTION);
+        assert_eq!(rrsig.key_tag, 3613);
+        assert_eq!(rrsig.signer_name, Name::new_unchecked("example.com"));
+        assert_eq!(*rrsig.signature, signed_data(&rrset, &rrsig).unwrap());
+
+        assert!(verify_rrsig(&rrset, &rrsig, &dnskey(), INCEPTION, &FakeSigner).is_ok());
+    }
+
+    #[test]
+    fn sign_labels_and_ttl() {
+        let rrset = parse(
+            "WWW.Example.com. 300 IN A 192.0.2.1\n\
+            www.example.com. 60 IN A 192.0.2.2",
+        );
+        let signature = signer().sign_rrset(&rrset).unwrap();
+        assert_eq!(signature.name, rrset[0].name);
+        assert_eq!(signature.ttl, 60);
+
+        let rrsig = rrsig_of(&signature);
+        assert_eq!(rrsig.labels, 3);
+        assert_eq!(rrsig.original_ttl, 60);
+        assert!(verify_rrsig(&rrset, &rrsig, &dnskey(), INCEPTION, &FakeSigner).is_ok());
+
+        let rrset = parse("*.example.com. 300 IN A 192.0.2.1");
+        let rrsig = rrsig_of(&signer().sign_rrset(&rrset).unwrap());
+        assert_eq!(rrsig.labels, 2);
+
+        // Records synthesized from the wildcard are verified with the wildcard signature
+        let expanded = parse("a.b.example.com. 300 IN A 192.0.2.1");
+        assert!(verify_rrsig(&expanded, &rrsig, &dnskey(), INCEPTION, &FakeSigner).is_ok());
+    }
+
+    #[test]
+    fn sign_invalid_rrsets() {
+        let signer = signer();
+        assert!(signer.sign_rrset(&[]).is_err());
+        assert!(signer
+            .sign_rrset(&parse("example.org. 300 IN A 192.0.2.1"))
+            .is_err());
+        assert!(signer
+            .sign_rrset(&parse(
+                "a.example.com. 300 IN A 192.0.2.1\nb.example.com. 300 IN A 192.0.2.1"
+            ))
+            .is_err());
+        assert!(signer
+            .sign_rrset(&parse(
+                "a.example.com. 300 IN A 192.0.2.1\na.example.com. 300 IN AAAA ::1"
+            ))
+            .is_err());
+
+        let not_zone_key = "example.com. 3600 IN DNSKEY 1 3 15 AA==".parse().unwrap();
+        assert!(ZoneSigner::new(&not_zone_key, FakeSigner, INCEPTION, EXPIRATION).is_err());
+        let not_dnskey = "example.com. 3600 IN A 192.0.2.1".parse().unwrap();
+        assert!(ZoneSigner::new(&not_dnskey, FakeSigner, INCEPTION, EXPIRATION).is_err());
+    }
+
+    #[test]
+    fn sign_zone() {
+        let records = parse(
+            "$ORIGIN example.com.\n\
+            $TTL 3600\n\
+            @ SOA ns1 hostmaster 1 7200 3600 1209600 300\n\
+            @ NS ns1\n\
+            ns1 A 192.0.2.1\n\
+            www A 192.0.2.2\n\
+            @ NS ns2\n\
+            www A 192.0.2.3\n\
+            www RRSIG A 15 3 3600 20150820000000 20150729220000 3613 example.com. AA==\n\
+            sub NS ns.sub\n\
+            sub DS 60485 5 1 2BB183AF5F22588179A53B0A98631FAD1A292118\n\
+            ns.sub A 192.0.2.4\n\
+            example.org. A 192.0.2.5",
+        );
+
+        let signatures = signer().sign_zone(&records).unwrap();
+        let signed: Vec<_> = signatures
+            .iter()
+            .map(|signature| (signature.name.to_string(), rrsig_of(signature).type_covered))
+            .collect();
+        assert_eq!(
+            signed,
+            [
+                ("example.com".into(), TYPE::SOA.into()),
+                ("example.com".into(), TYPE::NS.into()),
+                ("ns1.example.com".into(), TYPE::A.into()),
+                ("www.example.com".into(), TYPE::A.into()),
+                ("sub.example.com".into(), TYPE::DS.into()),
+            ]
+        );
+
+        for signature in &signatures {
+            let rrsig = rrsig_of(signature);
+            let rrset: Vec<_> = records
+                .iter()
+                .filter(|record| {
+                    record.name == signature.name
+                        && u16::from(record.rdata.type_code()) == rrsig.type_covered
+                })
+                .cloned()
+                .collect();
+            assert!(verify_rrsig(&rrset, &rrsig, &dnskey(), INCEPTION, &FakeSigner).is_ok());
+        }
+    }
+}
@@ -0,0 +1,412 @@
+use super::{label_count, wildcard_name, ValidationError, DNSKEY_PROTOCOL, ZONE_KEY_FLAG};
+use crate::{
+    dns::WireFormat,
+    lib::Vec,
+    rdata::{RData, RRSIG},
+    Name, ResourceRecord, SimpleDnsError,
+};
+
+/// Cryptographic backend used to verify DNSSEC signatures.
+///
+/// `simple-dns` does not implement any cryptographic algorithm, this trait must be implemented
+/// on top of a cryptographic library to verify signatures with [`verify_rrsig`]
+pub trait SignatureVerifier {
+    /// Returns true if signatures made with the `algorithm` can be verified.
+    ///
+    /// RRsets signed only with unsupported algorithms are to be treated as insecure instead of
+    /// bogus, see [RFC 4035](https://www.rfc-editor.org/rfc/rfc4035#section-5.2)
+    fn supports_algorithm(&self, algorithm: u8) -> bool;
+
+    /// Verifies the `signature` over `data`, using the public key of a DNSKEY record in the
+    /// format defined for the `algorithm`. Returns true if the signature is valid
+    fn verify(&self, algorithm: u8, public_key: &[u8], data: &[u8], signature: &[u8]) -> bool;
+}
+
+/// Verifies that `rrsig` is a valid signature of the `rrset`, made with the key of the `dnskey`
+/// record, following the steps of [RFC 4035](https://www.rfc-editor.org/rfc/rfc4035#section-5.3).
+///
+/// `now` is the current time in seconds since Jan 1 1970, the signature validity period is
+/// compared using serial number arithmetic. Failures are returned as
+/// [`SimpleDnsError::ValidationFailed`].
+///
+/// ```
+/// # use simple_dns::{dnssec::{verify_rrsig, SignatureVerifier}, rdata::RData, ResourceRecord};
+/// # struct Ed25519;
+/// # impl SignatureVerifier for Ed25519 {
+/// #     fn supports_algorithm(&self, algorithm: u8) -> bool { algorithm == 15 }
+/// #     fn verify(&self, _: u8, _: &[u8], _: &[u8], _: &[u8]) -> bool { true }
+/// # }
+/// let rrset = ["example.com. 3600 IN MX 10 mail.example.com.".parse::<ResourceRecord>()?];
+/// let dnskey: ResourceRecord = "example.com. 3600 IN DNSKEY 257 3 15 \
+///     l02Woi0iS8Aa25FQkUd9RMzZHJpBoRQwAQEX1SxZJA4=".parse()?;
+/// let rrsig = RData::from_str(simple_dns::TYPE::RRSIG, "MX 15 2 3600 1440021600 1438207200 \
+///     3613 example.com. oL9krJun7xfBOIWcGHi7mag5/hdZrKWw15jPGrHpjQeRAvTdszaPD+QLs3fx8A4M3e23mRZ9VrbpMngwcrqNAg==")?;
+/// let RData::RRSIG(rrsig) = rrsig else { unreachable!() };
+///
+/// verify_rrsig(&rrset, &rrsig, &dnskey, 1439000000, &Ed25519)?;
+/// # Ok::<(), simple_dns::SimpleDnsError>(())
+/// ```
+pub fn verify_rrsig<V: SignatureVerifier + ?Sized>(
+    rrset: &[ResourceRecord<'_>],
+    rrsig: &RRSIG<'_>,
+    dnskey: &ResourceRecord<'_>,
+    now: u32,
+    verifier: &V,
+) -> crate::Result<()> {
+    let failed = SimpleDnsError::ValidationFailed;
+
+    let Some(first) = rrset.first() else {
+        return Err(failed(ValidationError::InvalidRRset));
+    };
+    let owner = first.name.to_canonical();
+    let is_same_rrset = |record: &ResourceRecord| {
+        record.name.to_canonical() == owner
+            && record.class == first.class
+            && u16::from(record.rdata.type_code()) == rrsig.type_covered
+    };
+    if !rrset.iter().all(is_same_rrset) {
+        return Err(failed(ValidationError::InvalidRRset));
+    }
+
+    let signer = rrsig.signer_name.to_canonical();
+    if signer != dnskey.name.to_canonical() || !(owner == signer || owner.is_subdomain_of(&signer))
+    {
+        return Err(failed(ValidationError::SignerMismatch));
+    }
+
+    if rrsig.labels as usize > label_count(owner.get_labels()) {
+        return Err(failed(ValidationError::InvalidLabels));
+    }
+
+    if (now.wrapping_sub(rrsig.signature_inception) as i32) < 0 {
+        return Err(failed(ValidationError::SignatureNotYetValid));
+    }
+    if (rrsig.signature_expiration.wrapping_sub(now) as i32) < 0 {
+        return Err(failed(ValidationError::SignatureExpired));
+    }
+
+    let RData::DNSKEY(key) = &dnskey.rdata else {
+        return Err(failed(ValidationError::InvalidKey));
+    };
+    if key.protocol != DNSKEY_PROTOCOL || key.flags & ZONE_KEY_FLAG == 0 {
+        return Err(failed(ValidationError::InvalidKey));
+    }
+    if key.algorithm != rrsig.algorithm || key.key_tag() != rrsig.key_tag {
+        return Err(failed(ValidationError::KeyMismatch));
+    }
+
+    if !verifier.supports_algorithm(rrsig.algorithm) {
+        return Err(failed(ValidationError::UnsupportedAlgorithm));
+    }
+
+    let data = signed_data(rrset, rrsig)?;
+    if !verifier.verify(rrsig.algorithm, &key.public_key, &data, &rrsig.signature) {
+        return Err(failed(ValidationError::InvalidSignature));
+    }
+
+    Ok(())
+}
+
+/// Builds the data covered by the signature of an RRSIG record, as defined by
+/// [RFC 4034](https://www.rfc-editor.org/rfc/rfc4034#section-3.1.8.1).
+///
+/// The data is the RRSIG RDATA without the signature, followed by the records of the `rrset` in
+/// canonical form and canonical order, using the original TTL from the RRSIG.
+/// Records are expected to share the owner name, class and type, which is not verified here.
+pub fn signed_data(rrset: &[ResourceRecord<'_>], rrsig: &RRSIG<'_>) -> crate::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    data.extend_from_slice(&rrsig.type_covered.to_be_bytes());
+    data.extend_from_slice(&[rrsig.algorithm, rrsig.labels]);
+    data.extend_from_slice(&rrsig.original_ttl.to_be_bytes());
+    data.extend_from_slice(&rrsig.signature_expiration.to_be_bytes());
+    data.extend_from_slice(&rrsig.signature_inception.to_be_bytes());
+    data.extend_from_slice(&rrsig.key_tag.to_be_bytes());
+    rrsig.signer_name.to_canonical().write_to(&mut data)?;
+
+    let Some(first) = rrset.first() else {
+        return Ok(data);
+    };
+    let owner = signed_owner(&first.name, rrsig.labels);
+
+    let mut rdatas = rrset
+        .iter()
+        .map(|record| {
+            let mut rdata = Vec::new();
+            record.rdata.write_canonical_to(&mut rdata)?;
+            Ok(rdata)
+        })
+        .collect::<crate::Result<Vec<_>>>()?;
+    rdatas.sort();
+    rdatas.dedup();
+
+    for rdata in rdatas {
+        owner.write_to(&mut data)?;
+        data.extend_from_slice(&rrsig.type_covered.to_be_bytes());
+        data.extend_from_slice(&(first.class as u16).to_be_bytes());
+        data.extend_from_slice(&rrsig.original_ttl.to_be_bytes());
+        data.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+        data.extend_from_slice(&rdata);
+    }
+
+    Ok(data)
+}
+
+/// Returns the owner name in canonical form. When the labels field of the signature is smaller
+/// than the number of labels of the owner, the record was synthesized from a wildcard and the
+/// wildcard name is returned instead
+fn signed_owner(owner: &Name<'_>, labels: u8) -> Name<'static> {
+    let owner = owner.to_canonical();
+    let owner_labels = owner.get_labels();
+    let labels = labels as usize;
+    if labels >= label_count(owner_labels) {
+        return owner;
+    }
+
+    wildcard_name(&owner_labels[owner_labels.len() - labels..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TYPE;
+
+    /// Accepts a signature only if the signed data matches the expected data
+    struct ExpectedData(Vec<u8>);
+
+    impl SignatureVerifier for ExpectedData {
+        fn supports_algorithm(&self, algorithm: u8) -> bool {
+            algorithm == 15
+        }
+
+        fn verify(&self, _algorithm: u8, _key: &[u8], data: &[u8], _signature: &[u8]) -> bool {
+            data == self.0
+        }
+    }
+
+    fn record(text: &str) -> ResourceRecord<'static> {
+        text.parse().unwrap()
+    }
+
+    fn parse_rrsig(text: &str) -> RRSIG<'static> {
+        match RData::from_str(TYPE::RRSIG, text).unwrap() {
+            RData::RRSIG(rrsig) => rrsig,
+            _ => unreachable!(),
+        }
+    }
+
+    fn rfc8080_example() -> (
+        ResourceRecord<'static>,
+        RRSIG<'static>,
+        ResourceRecord<'static>,
+    ) {
+        (
+            record("example.com. 3600 IN MX 10 mail.example.com."),
+            parse_rrsig(
+                "MX 15 2 3600 1440021600 1438207200 3613 example.com. \
+                oL9krJun7xfBOIWcGHi7mag5/hdZrKWw15jPGrHpjQeRAvTdszaPD+QLs3fx8A4M3e23mRZ9VrbpMngwcrqNAg==",
+            ),
+            record("example.com. 3600 IN DNSKEY 257 3 15 l02Woi0iS8Aa25FQkUd9RMzZHJpBoRQwAQEX1SxZJA4="),
+        )
+    }
+
+    fn expect_failure(result: crate::Result<()>, error: ValidationError) {
+        assert_eq!(result, Err(SimpleDnsError::ValidationFailed(error)));
+    }
+
+    #[test]
+    fn verify_rfc8080_example() {
+        let (mx, rrsig, dnskey) = rfc8080_example();
+        let rrset = [mx];
+
+        let expected = [
+            // RRSIG RDATA without the signature
+            &b"\x00\x0f\x0f\x02\x00\x00\x0e\x10\x55\xd4\xfc\x60\x55\xb9\x4c\xe0\x0e\x1d"[..],
+            b"\x07example\x03com\x00",
+            // MX record with the original TTL
+            b"\x07example\x03com\x00\x00\x0f\x00\x01\x00\x00\x0e\x10\x00\x14",
+            b"\x00\x0a\x04mail\x07example\x03com\x00",
+        ]
+        .concat();
+        assert_eq!(signed_data(&rrset, &rrsig).unwrap(), expected);
+
+        let verifier = ExpectedData(expected);
+        assert!(verify_rrsig(&rrset, &rrsig, &dnskey, 1439000000, &verifier).is_ok());
+        // The inception and expiration times are inclusive
+        assert!(verify_rrsig(&rrset, &rrsig, &dnskey, 1438207200, &verifier).is_ok());
+        assert!(verify_rrsig(&rrset, &rrsig, &dnskey, 1440021600, &verifier).is_ok());
+    }
+
+    #[test]
+    fn canonical_form_and_order() {
+        let rrsig = parse_rrsig("A 15 3 300 1440021600 1438207200 3613 Example.COM. AA==");
+
+        let rrset = [
+            record("WWW.example.com. 60 IN A 192.0.2.2"),
+            record("www.Example.com. 30 IN A 192.0.2.1"),
+            record("www.example.com. 60 IN A 192.0.2.2"),
+        ];
+
+        let expected = [
+            &b"\x00\x01\x0f\x03\x00\x00\x01\x2c\x55\xd4\xfc\x60\x55\xb9\x4c\xe0\x0e\x1d"[..],
+            b"\x07example\x03com\x00",
+            b"\x03www\x07example\x03com\x00\x00\x01\x00\x01\x00\x00\x01\x2c\x00\x04\xc0\x00\x02\x01",
+            b"\x03www\x07example\x03com\x00\x00\x01\x00\x01\x00\x00\x01\x2c\x00\x04\xc0\x00\x02\x02",
+        ]
+        .concat();
+        assert_eq!(signed_data(&rrset, &rrsig).unwrap(), expected);
+
+        let rrset = [
+            record("example.com. 60 IN NS NS2.Example.com."),
+            record("example.com. 60 IN NS ns1.example.com."),
+        ];
+        let rrsig = parse_rrsig("NS 15 2 60 1440021600 1438207200 3613 example.com. AA==");
+        let data = signed_data(&rrset, &rrsig).unwrap();
+        let ns1 = data.windows(4).position(|w| w == b"\x03ns1").unwrap();
+        let ns2 = data.windows(4).position(|w| w == b"\x03ns2").unwrap();
+        assert!(ns1 < ns2);
+    }
+
+    #[test]
+    fn verify_wildcard_expansion() {
+        let (_, _, dnskey) = rfc8080_example();
+        let rrsig = parse_rrsig("A 15 2 300 1440021600 1438207200 3613 example.com. AA==");
+
+        let wildcard = [record("*.example.com. 300 IN A 192.0.2.1")];
+        let expanded = [record("a.b.example.com. 300 IN A 192.0.2.1")];
+
+        let expected = signed_data(&wildcard, &rrsig).unwrap();
+        assert_eq!(signed_data(&expanded, &rrsig).unwrap(), expected);
+
+        let verifier = ExpectedData(expected);
+        assert!(verify_rrsig(&wildcard, &rrsig, &dnskey, 1439000000, &verifier).is_ok());
+        assert!(verify_rrsig(&expanded, &rrsig, &dnskey, 1439000000, &verifier).is_ok());
+
+        let rrsig = parse_rrsig("A 15 3 300 1440021600 1438207200 3613 example.com. AA==");
+        expect_failure(
+            verify_rrsig(&wildcard, &rrsig, &dnskey, 1439000000, &verifier),
+            ValidationError::InvalidLabels,
+        );
+    }
+
+    #[test]
+    fn verify_validity_period() {
+        let (mx, rrsig, dnskey) = rfc8080_example();
+        let rrset = [mx];
+        let verifier = ExpectedData(signed_data(&rrset, &rrsig).unwrap());
+
+        expect_failure(
+            verify_rrsig(&rrset, &rrsig, &dnskey, 1438207199, &verifier),
+            ValidationError::SignatureNotYetValid,
+        );
+        expect_failure(
+            verify_rrsig(&rrset, &rrsig, &dnskey, 1440021601, &verifier),
+            ValidationError::SignatureExpired,
+        );
+
+        // Serial number arithmetic, the validity period wraps around
+        let rrsig = RRSIG {
+            signature_inception: u32::MAX - 100,
+            signature_expiration: 100,
+            ..rrsig
+        };
+        let verifier = ExpectedData(signed_data(&rrset, &rrsig).unwrap());
+        assert!(verify_rrsig(&rrset, &rrsig, &dnskey, 50, &verifier).is_ok());
+        assert!(verify_rrsig(&rrset, &rrsig, &dnskey, u32::MAX, &verifier).is_ok());
+        expect_failure(
+            verify_rrsig(&rrset, &rrsig, &dnskey, 101, &verifier),
+            ValidationError::SignatureExpired,
+        );
+    }
+
+    #[test]
+    fn verify_key_and_signer() {
+        let (mx, rrsig, _) = rfc8080_example();
+        let rrset = [mx];
+        let verifier = ExpectedData(signed_data(&rrset, &rrsig).unwrap());
+
+        let other_key = record(
+            "example.com. 3600 IN DNSKEY 257 3 15 zPnZ/QwEe7S8C5SPz2OfS5RR40ATk2/rYnE9xHIEijs=",
+        );
+        expect_failure(
+            verify_rrsig(&rrset, &rrsig, &other_key, 1439000000, &verifier),
+            ValidationError::KeyMismatch,
+        );
+
+        let not_zone_key = record(
+            "example.com. 3600 IN DNSKEY 1 3 15 l02Woi0iS8Aa25FQkUd9RMzZHJpBoRQwAQEX1SxZJA4=",
+        );
+        expect_failure(
+            verify_rrsig(&rrset, &rrsig, &not_zone_key, 1439000000, &verifier),
+            ValidationError::InvalidKey,
+        );
+
+        let other_owner = record(
+            "example.org. 3600 IN DNSKEY 257 3 15 l02Woi0iS8Aa25FQkUd9RMzZHJpBoRQwAQEX1SxZJA4=",
+        );
+        expect_failure(
+            verify_rrsig(&rrset, &rrsig, &other_owner, 1439000000, &verifier),
+            ValidationError::SignerMismatch,
+        );
+
+        let unsupported = RRSIG {
+            algorithm: 16,
+            ..rrsig.clone()
+        };
+        let unsupported_key = record(
+            "example.com. 3600 IN DNSKEY 257 3 16 l02Woi0iS8Aa25FQkUd9RMzZHJpBoRQwAQEX1SxZJA4=",
+        );
+        let RData::DNSKEY(key) = &unsupported_key.rdata else {
+            unreachable!()
+        };
+        let unsupported = RRSIG {
+            key_tag: key.key_tag(),
+            ..unsupported
+        };
+        expect_failure(
+            verify_rrsig(
+                &rrset,
+                &unsupported,
+                &unsupported_key,
+                1439000000,
+                &verifier,
+            ),
+            ValidationError::UnsupportedAlgorithm,
+        );
+    }
+
+    #[test]
+    fn verify_rrset_and_signature() {
+        let (mx, rrsig, dnskey) = rfc8080_example();
+        let verifier = ExpectedData(signed_data(core::slice::from_ref(&mx), &rrsig).unwrap());
+
+        expect_failure(
+            verify_rrsig(&[], &rrsig, &dnskey, 1439000000, &verifier),
+            ValidationError::InvalidRRset,
+        );
+
+        let mixed = [
+            mx.clone(),
+            record("www.example.com. 3600 IN MX 10 mail.example.com."),
+        ];
+        expect_failure(
+            verify_rrsig(&mixed, &rrsig, &dnskey, 1439000000, &verifier),
+            ValidationError::InvalidRRset,
+        );
+
+        let other_type = [record("example.com. 3600 IN A 192.0.2.1")];
+        expect_failure(
+            verify_rrsig(&other_type, &rrsig, &dnskey, 1439000000, &verifier),
+            ValidationError::InvalidRRset,
+        );
+
+        let tampered = [record("example.com. 3600 IN MX 20 mail.example.com.")];
+        expect_failure(
+            verify_rrsig(&tampered, &rrsig, &dnskey, 1439000000, &verifier),
+            ValidationError::InvalidSignature,
+        );
+
+        // The TTL of the records is replaced by the original TTL
+        let ttl_changed = [record("EXAMPLE.com. 60 IN MX 10 MAIL.example.com.")];
+        assert!(verify_rrsig(&ttl_changed, &rrsig, &dnskey, 1439000000, &verifier).is_ok());
+    }
+}
@@ -0,0 +1,351 @@
+use super::EdnsOption;
+use crate::{
+    bytes_buffer::BytesBuffer,
+    lib::{IpAddr, Ipv4Addr, Ipv6Addr, Write},
+    Name, Packet, SimpleDnsError, QTYPE,
+};
+
+/// Address family of IPv4 addresses
+const FAMILY_IPV4: u16 = 1;
+/// Address family of IPv6 addresses
+const FAMILY_IPV6: u16 = 2;
+
+/// The client subnet option, holds the network of the client that originated a query,
+/// see [RFC 7871](https://www.rfc-editor.org/rfc/rfc7871#section-6)
+///
+/// Clients create the option with [`ClientSubnet::new`], servers answer it with
+/// [`echo_client_subnet`] and responses are cached per subnet with [`SubnetCacheKey`].
+/// Options with a family, prefix length or address length mismatch fail to parse, queries
+/// holding them must be answered with FORMERR
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct ClientSubnet {
+    /// Number of leftmost bits of the address sent by the client
+    pub source_prefix_length: u8,
+    /// Number of leftmost bits of the address the response covers, 0 in queries
+    pub scope_prefix_length: u8,
+    /// The address of the network, only the leftmost octets covered by the source prefix are sent
+    pub address: IpAddr,
+}
+
+impl ClientSubnet {
+    /// Creates the option sent in a query for the network of `address`, truncated to its
+    /// leftmost `source_prefix_length` bits. Returns [`SimpleDnsError::InvalidDnsPacket`] if
+    /// the prefix is longer than the address
+    pub fn new(address: IpAddr, source_prefix_length: u8) -> crate::Result<Self> {
+        if source_prefix_length > max_prefix_length(address) {
+            return Err(SimpleDnsError::InvalidDnsPacket);
+        }
+
+        Ok(Self {
+            source_prefix_length,
+            scope_prefix_length: 0,
+            address: truncate(address, source_prefix_length),
+        })
+    }
+
+    /// Returns the option sent in the response to a query holding this option, with the
+    /// `scope_prefix_length` chosen by the server: the number of leftmost bits of the address the
+    /// response covers, see [RFC 7871](https://www.rfc-editor.org/rfc/rfc7871#section-7.2.1)
+    pub fn to_response(&self, scope_prefix_length: u8) -> crate::Result<Self> {
+        if scope_prefix_length > max_prefix_length(self.address) {
+            return Err(SimpleDnsError::InvalidDnsPacket);
+        }
+
+        Ok(Self {
+            scope_prefix_length,
+            ..*self
+        })
+    }
+
+    pub(super) fn parse_data(data: &[u8]) -> crate::Result<Self> {
+        let mut data = BytesBuffer::new(data);
+        let family = data.get_u16()?;
+        let source_prefix_length = data.get_u8()?;
+        let scope_prefix_length = data.get_u8()?;
+        let address = data.get_remaining();
+
+        let max_prefix_length = match family {
+            FAMILY_IPV4 => 32,
+            FAMILY_IPV6 => 128,
+            _ => return Err(SimpleDnsError::InvalidDnsPacket),
+        };
+        if source_prefix_length > max_prefix_length
+            || scope_prefix_length > max_prefix_length
+            || address.len() != prefix_octets(source_prefix_length)
+        {
+            return Err(SimpleDnsError::InvalidDnsPacket);
+        }
+
+        let address = if family == FAMILY_IPV4 {
+            let mut octets = [0; 4];
+            octets[..address.len()].copy_from_slice(address);
+            IpAddr::V4(Ipv4Addr::from(octets))
+        } else {
+            let mut octets = [0; 16];
+            octets[..address.len()].copy_from_slice(address);
+            IpAddr::V6(Ipv6Addr::from(octets))
+        };
+
+        // The bits beyond the source prefix must be zero
+        if truncate(address, source_prefix_length) != address {
+            return Err(SimpleDnsError::InvalidDnsPacket);
+        }
+
+        Ok(Self {
+            source_prefix_length,
+            scope_prefix_length,
+            address,
+        })
+    }
+
+    pub(super) fn write_data<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        let (family, max_prefix_length) = match self.address {
+            IpAddr::V4(_) => (FAMILY_IPV4, 32),
+            IpAddr::V6(_) => (FAMILY_IPV6, 128),
+        };
+        if self.source_prefix_length > max_prefix_length
+            || self.scope_prefix_length > max_prefix_length
+        {
+            return Err(SimpleDnsError::InvalidDnsPacket);
+        }
+
+        out.write_all(&family.to_be_bytes())?;
+        out.write_all(&[self.source_prefix_length, self.scope_prefix_length])?;
+        let octets = prefix_octets(self.source_prefix_length);
+        match truncate(self.address, self.source_prefix_length) {
+            IpAddr::V4(address) => out.write_all(&address.octets()[..octets])?,
+            IpAddr::V6(address) => out.write_all(&address.octets()[..octets])?,
+        }
+        Ok(())
+    }
+
+    pub(super) fn data_len(&self) -> usize {
+        4 + prefix_octets(self.source_prefix_length)
+    }
+}
+
+/// Adds the client subnet option of `query` to `response`, with the `scope_prefix_length` chosen
+/// by the server, see [RFC 7871](https://www.rfc-editor.org/rfc/rfc7871#section-7.2.1).
+/// The [OPT](crate::rdata::OPT) record of the response is created if it is missing.
+///
+/// Returns the option added to the response, or None if the query has no client subnet option.
+/// Returns [`SimpleDnsError::InvalidDnsPacket`] if the option of the query is malformed, the
+/// query must then be answered with FORMERR
+pub fn echo_client_subnet(
+    query: &Packet<'_>,
+    response: &mut Packet<'_>,
+    scope_prefix_length: u8,
+) -> crate::Result<Option<ClientSubnet>> {
+    let Some(subnet) = query
+        .opt()
+        .map(|opt| opt.client_subnet())
+        .transpose()?
+        .flatten()
+    else {
+        return Ok(None);
+    };
+
+    let subnet = subnet.to_response(scope_prefix_length)?;
+    response
+        .opt_or_default()
+        .set_option(&EdnsOption::ClientSubnet(subnet))?;
+    Ok(Some(subnet))
+}
+
+/// Key of a cached response, which depends on the subnet of the client when the response holds a
+/// client subnet option, see [RFC 7871](https://www.rfc-editor.org/rfc/rfc7871#section-7.3.1)
+///
+/// The network is the address truncated to the scope prefix length, or to the source prefix
+/// length when it is shorter. Responses with a scope of 0 are valid for every client and
+/// share the key of responses without the option
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SubnetCacheKey {
+    qname: Name<'static>,
+    qtype: u16,
+    network: Option<(IpAddr, u8)>,
+}
+
+impl SubnetCacheKey {
+    /// Creates the key of a response to the question `qname` and `qtype`, with the client
+    /// `subnet` option of the response, if any. The name is compared case-insensitively
+    pub fn new(qname: &Name<'_>, qtype: QTYPE, subnet: Option<&ClientSubnet>) -> Self {
+        let network = subnet
+            .map(|subnet| {
+                let prefix = subnet.scope_prefix_length.min(subnet.source_prefix_length);
+                (truncate(subnet.address, prefix), prefix)
+            })
+            .filter(|(_, prefix)| *prefix > 0);
+
+        Self {
+            qname: qname.to_canonical(),
+            qtype: qtype.into(),
+            network,
+        }
+    }
+
+    /// Returns the network the cached response is valid for, and its prefix length, or None
+    /// if it is valid for every client
+    pub fn network(&self) -> Option<(IpAddr, u8)> {
+        self.network
+    }
+}
+
+/// Returns the number of bits of the address
+fn max_prefix_length(address: IpAddr) -> u8 {
+    match address {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+/// Keeps the leftmost `prefix_length` bits of the address, the others are set to zero
+fn truncate(address: IpAddr, prefix_length: u8) -> IpAddr {
+    match address {
+        IpAddr::V4(address) => {
+            let mask = u32::MAX
+                .checked_shl(32u32.saturating_sub(prefix_length as u32))
+                .unwrap_or(0);
+            IpAddr::V4(Ipv4Addr::from(u32::from(address) & mask))
+        }
+        IpAddr::V6(address) => {
+            let mask = u128::MAX
+                .checked_shl(128u32.saturating_sub(prefix_length as u32))
+                .unwrap_or(0);
+            IpAddr::V6(Ipv6Addr::from(u128::from(address) & mask))
+        }
+    }
+}
+
+/// Number of octets needed to hold a prefix of the given length
+fn prefix_octets(prefix_length: u8) -> usize {
+    (prefix_length as usize).div_ceil(8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::Vec;
+
+    #[test]
+    fn parse_and_write_ipv6_subnet() {
+        let data = [0, 2, 56, 48, 0x20, 0x01, 0x0d, 0xb8, 0, 1, 0];
+        let subnet = ClientSubnet::parse_data(&data).unwrap();
+        assert_eq!(subnet.source_prefix_length, 56);
+        assert_eq!(subnet.scope_prefix_length, 48);
+        assert_eq!(subnet.address, "2001:db8:1::".parse::<Ipv6Addr>().unwrap());
+
+        let mut out = Vec::new();
+        subnet.write_data(&mut out).unwrap();
+        assert_eq!(out, data);
+        assert_eq!(out.len(), subnet.data_len());
+    }
+
+    #[test]
+    fn reject_invalid_subnets() {
+        // Unknown family
+        assert!(ClientSubnet::parse_data(&[0, 3, 0, 0]).is_err());
+        // Prefix longer than the address
+        assert!(ClientSubnet::parse_data(&[0, 1, 33, 0, 1, 2, 3, 4, 5]).is_err());
+        // Address longer than the prefix
+        assert!(ClientSubnet::parse_data(&[0, 1, 8, 0, 1, 2]).is_err());
+
+        let subnet = ClientSubnet {
+            source_prefix_length: 33,
+            scope_prefix_length: 0,
+            address: Ipv4Addr::LOCALHOST.into(),
+        };
+        assert!(subnet.write_data(&mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn create_truncated_subnet() {
+        let subnet = ClientSubnet::new(Ipv4Addr::new(192, 0, 2, 130).into(), 25).unwrap();
+        assert_eq!(subnet.address, Ipv4Addr::new(192, 0, 2, 128));
+        assert_eq!(subnet.scope_prefix_length, 0);
+
+        let mut out = Vec::new();
+        subnet.write_data(&mut out).unwrap();
+        assert_eq!(out, [0, 1, 25, 0, 192, 0, 2, 128]);
+
+        let address: IpAddr = "2001:db8:1:2:3:4:5:6".parse().unwrap();
+        let subnet = ClientSubnet::new(address, 0).unwrap();
+        assert_eq!(subnet.address, Ipv6Addr::UNSPECIFIED);
+        assert_eq!(ClientSubnet::new(address, 128).unwrap().address, address);
+
+        assert!(ClientSubnet::new(Ipv4Addr::LOCALHOST.into(), 33).is_err());
+        assert!(subnet.to_response(129).is_err());
+
+        // Bits beyond the source prefix must be zero
+        assert!(ClientSubnet::parse_data(&[0, 1, 25, 0, 192, 0, 2, 129]).is_err());
+    }
+
+    #[test]
+    fn echo_subnet_and_cache_per_scope() {
+        let mut query = Packet::new_query(1);
+        let subnet = ClientSubnet::new(Ipv4Addr::new(192, 0, 2, 1).into(), 24).unwrap();
+        query.set_client_subnet(subnet).unwrap();
+
+        let mut response = query.clone().into_reply();
+        *response.opt_mut() = None;
+        let echoed = echo_client_subnet(&query, &mut response, 16)
+            .unwrap()
+            .unwrap();
+        assert_eq!(echoed.source_prefix_length, 24);
+        assert_eq!(echoed.scope_prefix_length, 16);
+        assert_eq!(echoed.address, subnet.address);
+        assert_eq!(
+            response.opt().unwrap().client_subnet().unwrap(),
+            Some(echoed)
+        );
+
+        let qname = Name::new_unchecked("www.example.com");
+        let key = SubnetCacheKey::new(&qname, crate::TYPE::A.into(), Some(&echoed));
+        assert_eq!(
+            key.network(),
+            Some((Ipv4Addr::new(192, 0, 0, 0).into(), 16))
+        );
+
+        // Clients of the same /16 share the cached response
+        let other = ClientSubnet::new(Ipv4Addr::new(192, 0, 200, 1).into(), 24)
+            .unwrap()
+            .to_response(16)
+            .unwrap();
+        let upper = Name::new_unchecked("WWW.example.com");
+        assert_eq!(
+            SubnetCacheKey::new(&upper, crate::TYPE::A.into(), Some(&other)),
+            key
+        );
+
+        // A scope longer than the source prefix is limited to the source prefix
+        let longer = subnet.to_response(32).unwrap();
+        let key = SubnetCacheKey::new(&qname, crate::TYPE::A.into(), Some(&longer));
+        assert_eq!(key.network().unwrap().1, 24);
+
+        // A scope of 0 is valid for every client
+        let global = subnet.to_response(0).unwrap();
+        assert_eq!(
+            SubnetCacheKey::new(&qname, crate::TYPE::A.into(), Some(&global)),
+            SubnetCacheKey::new(&qname, crate::TYPE::A.into(), None)
+        );
+
+        let mut response = Packet::new_reply(1);
+        assert_eq!(
+            echo_client_subnet(&Packet::new_query(1), &mut response, 0).unwrap(),
+            None
+        );
+        assert!(response.opt().is_none());
+
+        let mut malformed = Packet::new_query(1);
+        malformed
+            .opt_or_default()
+            .opt_codes
+            .push(crate::rdata::OPTCode {
+                code: super::super::CLIENT_SUBNET,
+                data: crate::lib::Cow::Borrowed(&[0, 3, 0, 0]),
+            });
+        assert_eq!(
+            echo_client_subnet(&malformed, &mut response, 0).unwrap_err(),
+            SimpleDnsError::InvalidDnsPacket
+        );
+    }
+}
@@ -0,0 +1,340 @@
+use super::siphash::siphash24;
+use crate::{
+    lib::{Cow, IpAddr, Vec, Write},
+    Packet, SimpleDnsError,
+};
+
+/// Length of the client cookie
+const CLIENT_COOKIE_LEN: usize = 8;
+/// Minimum and maximum length of the server cookie
+const SERVER_COOKIE_LEN: (usize, usize) = (8, 32);
+
+/// Version of the server cookies defined by RFC 9018
+const SERVER_COOKIE_VERSION: u8 = 1;
+/// Age, in seconds, after which a valid server cookie is replaced in responses
+const SERVER_COOKIE_RENEW_AGE: i32 = 1800;
+/// Age, in seconds, after which a server cookie is invalid
+const SERVER_COOKIE_MAX_AGE: i32 = 3600;
+/// How far in the future, in seconds, the timestamp of a valid server cookie may be
+const SERVER_COOKIE_MAX_SKEW: i32 = 300;
+
+/// The cookie option, holds the client cookie and, once it is known by the client, the server
+/// cookie, see [RFC 7873](https://www.rfc-editor.org/rfc/rfc7873#section-4)
+///
+/// Clients create their cookie with [`Cookie::new_client`], and send the server cookie they
+/// learned from the last response of the server along with it. Servers create their cookies in
+/// the interoperable format of [RFC 9018](https://www.rfc-editor.org/rfc/rfc9018), with
+/// [`Cookie::new_server`], and decide how to answer a query with [`check_query_cookie`]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Cookie<'a> {
+    /// The client cookie
+    pub client: [u8; CLIENT_COOKIE_LEN],
+    /// The server cookie, from 8 to 32 octets
+    pub server: Option<Cow<'a, [u8]>>,
+}
+
+impl<'a> Cookie<'a> {
+    /// Transforms the inner data into its owned type
+    pub fn into_owned<'b>(self) -> Cookie<'b> {
+        Cookie {
+            client: self.client,
+            server: self.server.map(|server| server.into_owned().into()),
+        }
+    }
+
+    pub(super) fn parse_data(data: &'a [u8]) -> crate::Result<Self> {
+        if data.len() < CLIENT_COOKIE_LEN {
+            return Err(SimpleDnsError::InvalidDnsPacket);
+        }
+
+        let (client, server) = data.split_at(CLIENT_COOKIE_LEN);
+        let server = match server.len() {
+            0 => None,
+            len if is_valid_server_len(len) => Some(Cow::Borrowed(server)),
+            _ => return Err(SimpleDnsError::InvalidDnsPacket),
+        };
+
+        Ok(Self {
+            client: client.try_into()?,
+            server,
+        })
+    }
+
+    pub(super) fn write_data<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        out.write_all(&self.client)?;
+        if let Some(server) = &self.server {
+            if !is_valid_server_len(server.len()) {
+                return Err(SimpleDnsError::InvalidDnsPacket);
+            }
+            out.write_all(server)?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn data_len(&self) -> usize {
+        CLIENT_COOKIE_LEN + self.server.as_ref().map_or(0, |server| server.len())
+    }
+}
+
+impl Cookie<'static> {
+    /// Creates the cookie sent by a client to the server at `server_ip`, without server cookie.
+    ///
+    /// The client cookie is the SipHash-2-4 of the server address with the client `secret`. As
+    /// recommended by [RFC 9018](https://www.rfc-editor.org/rfc/rfc9018#section-3), it does not
+    /// depend on the client address, the secret should be changed when the client address
+    /// changes
+    pub fn new_client(secret: &[u8; 16], server_ip: IpAddr) -> Self {
+        Self {
+            client: siphash24(secret, &ip_octets(server_ip)),
+            server: None,
+        }
+    }
+
+    /// Creates the cookie sent by a server in response to the `client` cookie of a query from
+    /// `client_ip`, with a server cookie created with the server `secret` at `timestamp`,
+    /// in seconds since Jan 1 1970, see [RFC 9018](https://www.rfc-editor.org/rfc/rfc9018#section-4)
+    pub fn new_server(
+        client: [u8; CLIENT_COOKIE_LEN],
+        secret: &[u8; 16],
+        client_ip: IpAddr,
+        timestamp: u32,
+    ) -> Self {
+        let mut server = Vec::with_capacity(16);
+        server.extend_from_slice(&[SERVER_COOKIE_VERSION, 0, 0, 0]);
+        server.extend_from_slice(&timestamp.to_be_bytes());
+        let hash = server_cookie_hash(&client, &server, secret, client_ip);
+        server.extend_from_slice(&hash);
+
+        Self {
+            client,
+            server: Some(Cow::Owned(server)),
+        }
+    }
+}
+
+impl Cookie<'_> {
+    /// Returns true if this cookie holds a server cookie created by [`Cookie::new_server`] with
+    /// the server `secret`, for the client cookie and `client_ip`, and its timestamp is no older
+    /// than one hour and no more than five minutes in the future, at `now`.
+    ///
+    /// When the server secret is replaced, cookies should also be checked with the previous
+    /// secret for a while, as described by [RFC 9018](https://www.rfc-editor.org/rfc/rfc9018#section-4.3)
+    pub fn has_valid_server_cookie(&self, secret: &[u8; 16], client_ip: IpAddr, now: u32) -> bool {
+        self.server_cookie_age(secret, client_ip, now).is_some()
+    }
+
+    /// Returns the age, in seconds, of a valid server cookie
+    fn server_cookie_age(&self, secret: &[u8; 16], client_ip: IpAddr, now: u32) -> Option<i32> {
+        let server = self.server.as_deref()?;
+        if server.len() != 16 || server[0] != SERVER_COOKIE_VERSION {
+            return None;
+        }
+
+        let timestamp = u32::from_be_bytes(server[4..8].try_into().ok()?);
+        let age = now.wrapping_sub(timestamp) as i32;
+        if !(-SERVER_COOKIE_MAX_SKEW..=SERVER_COOKIE_MAX_AGE).contains(&age) {
+            return None;
+        }
+
+        // Every octet is compared, so the time taken does not depend on the position of a mismatch
+        let hash = server_cookie_hash(&self.client, &server[..8], secret, client_ip);
+        let diff = hash
+            .iter()
+            .zip(&server[8..])
+            .fold(0, |diff, (a, b)| diff | (a ^ b));
+        (diff == 0).then_some(age)
+    }
+}
+
+/// How a server answers a query, according to its cookie, see
+/// [RFC 7873](https://www.rfc-editor.org/rfc/rfc7873#section-5.2)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CookieAction {
+    /// The query has no cookie, it is processed normally and the response has no cookie
+    NoCookie,
+    /// The cookie option is malformed, the response is a FORMERR
+    Malformed,
+    /// The query is not processed, the response is a [`RCODE::BADCOOKIE`](crate::RCODE::BADCOOKIE)
+    /// holding this cookie, with a new server cookie
+    BadCookie(Cookie<'static>),
+    /// The query is processed normally, the response holds this cookie
+    Process(Cookie<'static>),
+}
+
+/// Decides how to answer a `query` received from `client_ip` at `now`, in seconds since
+/// Jan 1 1970, according to its cookie, see [RFC 7873](https://www.rfc-editor.org/rfc/rfc7873#section-5.2).
+///
+/// Queries with a valid server cookie are processed, their server cookie is kept in the response
+/// unless it is older than half an hour. Queries with only a client cookie, or with an invalid
+/// server cookie, get a BADCOOKIE when `require_server_cookie` is true, like UDP queries of
+/// a server under attack, otherwise they are processed and get a new server cookie.
+///
+/// The cookie is added to the response with [`Packet::set_cookie`]
+pub fn check_query_cookie(
+    query: &Packet<'_>,
+    secret: &[u8; 16],
+    client_ip: IpAddr,
+    now: u32,
+    require_server_cookie: bool,
+) -> CookieAction {
+    let cookie = match query.opt().map(|opt| opt.cookie()) {
+        None | Some(Ok(None)) => return CookieAction::NoCookie,
+        Some(Err(_)) => return CookieAction::Malformed,
+        Some(Ok(Some(cookie))) => cookie,
+    };
+
+    let fresh = || Cookie::new_server(cookie.client, secret, client_ip, now);
+    match cookie.server_cookie_age(secret, client_ip, now) {
+        Some(age) if age <= SERVER_COOKIE_RENEW_AGE => CookieAction::Process(cookie.into_owned()),
+        Some(_) => CookieAction::Process(fresh()),
+        None if require_server_cookie => CookieAction::BadCookie(fresh()),
+        None => CookieAction::Process(fresh()),
+    }
+}
+
+/// Hash of a server cookie: SipHash-2-4 of the client cookie, the version, reserved and
+/// timestamp fields, and the client address
+fn server_cookie_hash(
+    client: &[u8; CLIENT_COOKIE_LEN],
+    header: &[u8],
+    secret: &[u8; 16],
+    client_ip: IpAddr,
+) -> [u8; 8] {
+    let mut data = Vec::with_capacity(32);
+    data.extend_from_slice(client);
+    data.extend_from_slice(header);
+    data.extend_from_slice(&ip_octets(client_ip));
+    siphash24(secret, &data)
+}
+
+fn ip_octets(ip: IpAddr) -> Vec<u8> {
+    match ip {
+        IpAddr::V4(ip) => ip.octets().to_vec(),
+        IpAddr::V6(ip) => ip.octets().to_vec(),
+    }
+}
+
+fn is_valid_server_len(len: usize) -> bool {
+    (SERVER_COOKIE_LEN.0..=SERVER_COOKIE_LEN.1).contains(&len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::Ipv4Addr;
+
+    #[test]
+    fn parse_and_write_server_cookie() {
+        let mut data = [2; 24];
+        data[..8].copy_from_slice(&[1; 8]);
+        let cookie = Cookie::parse_data(&data).unwrap();
+        assert_eq!(cookie.client, [1; 8]);
+        assert_eq!(cookie.server.as_deref(), Some(&[2; 16][..]));
+
+        let mut out = Vec::new();
+        cookie.write_data(&mut out).unwrap();
+        assert_eq!(out, data);
+        assert_eq!(out.len(), cookie.data_len());
+
+        assert!(Cookie::parse_data(&[1; 7]).is_err());
+        assert!(Cookie::parse_data(&[1; 15]).is_err());
+        assert!(Cookie::parse_data(&[1; 41]).is_err());
+    }
+
+    const SECRET: [u8; 16] = [
+        0xe5, 0xe9, 0x73, 0xe5, 0xa6, 0xb2, 0xa4, 0x3f, 0x48, 0xe7, 0xdc, 0x84, 0x9e, 0x37, 0xbf,
+        0xcf,
+    ];
+    const CLIENT: [u8; 8] = [0x24, 0x64, 0xc4, 0xab, 0xcf, 0x10, 0xc9, 0x57];
+    const NOW: u32 = 1_559_731_985;
+
+    fn client_ip() -> IpAddr {
+        Ipv4Addr::new(198, 51, 100, 100).into()
+    }
+
+    fn query_with(cookie: Cookie<'_>) -> Packet<'static> {
+        let mut query = Packet::new_query(1);
+        query.set_cookie(cookie).unwrap();
+        query
+    }
+
+    #[test]
+    fn create_server_cookie() {
+        // RFC 9018 appendix A.1
+        let cookie = Cookie::new_server(CLIENT, &SECRET, client_ip(), NOW);
+        assert_eq!(
+            cookie.server.as_deref(),
+            Some(
+                &[
+                    0x01, 0x00, 0x00, 0x00, 0x5c, 0xf7, 0x9f, 0x11, 0x1f, 0x81, 0x30, 0xc3, 0xee,
+                    0xe2, 0x94, 0x80
+                ][..]
+            )
+        );
+
+        assert!(cookie.has_valid_server_cookie(&SECRET, client_ip(), NOW));
+        assert!(cookie.has_valid_server_cookie(&SECRET, client_ip(), NOW + 3600));
+        assert!(cookie.has_valid_server_cookie(&SECRET, client_ip(), NOW - 300));
+        assert!(!cookie.has_valid_server_cookie(&SECRET, client_ip(), NOW + 3601));
+        assert!(!cookie.has_valid_server_cookie(&SECRET, client_ip(), NOW - 301));
+        assert!(!cookie.has_valid_server_cookie(&[0; 16], client_ip(), NOW));
+        assert!(!cookie.has_valid_server_cookie(&SECRET, Ipv4Addr::LOCALHOST.into(), NOW));
+
+        let client = Cookie::new_client(&SECRET, Ipv4Addr::new(192, 0, 2, 53).into());
+        assert_ne!(
+            client.client,
+            Cookie::new_client(&SECRET, Ipv4Addr::new(192, 0, 2, 54).into()).client
+        );
+        assert!(!client.has_valid_server_cookie(&SECRET, client_ip(), NOW));
+    }
+
+    #[test]
+    fn check_cookies_of_queries() {
+        let check = |query: &Packet<'_>, now, require| {
+            check_query_cookie(query, &SECRET, client_ip(), now, require)
+        };
+        let fresh = |now| Cookie::new_server(CLIENT, &SECRET, client_ip(), now);
+
+        assert_eq!(
+            check(&Packet::new_query(1), NOW, true),
+            CookieAction::NoCookie
+        );
+
+        let client_only = query_with(Cookie {
+            client: CLIENT,
+            server: None,
+        });
+        assert_eq!(
+            check(&client_only, NOW, false),
+            CookieAction::Process(fresh(NOW))
+        );
+        assert_eq!(
+            check(&client_only, NOW, true),
+            CookieAction::BadCookie(fresh(NOW))
+        );
+
+        let valid = query_with(fresh(NOW));
+        assert_eq!(
+            check(&valid, NOW + 60, true),
+            CookieAction::Process(fresh(NOW))
+        );
+        assert_eq!(
+            check(&valid, NOW + 1801, true),
+            CookieAction::Process(fresh(NOW + 1801))
+        );
+        assert_eq!(
+            check(&valid, NOW + 3601, true),
+            CookieAction::BadCookie(fresh(NOW + 3601))
+        );
+
+        let mut malformed = Packet::new_query(1);
+        malformed
+            .opt_or_default()
+            .opt_codes
+            .push(crate::rdata::OPTCode {
+                code: super::super::COOKIE,
+                data: Cow::Borrowed(&[1; 12]),
+            });
+        assert_eq!(check(&malformed, NOW, false), CookieAction::Malformed);
+    }
+}
@@ -0,0 +1,237 @@
+use crate::{
+    bytes_buffer::BytesBuffer,
+    lib::{fmt, Cow, String, Write},
+};
+
+/// The extended DNS error option, gives the reason of an error, or of an unexpected answer,
+/// see [RFC 8914](https://www.rfc-editor.org/rfc/rfc8914#section-2)
+///
+/// Use [`Packet::extended_errors`](crate::Packet::extended_errors) and
+/// [`Packet::add_extended_error`](crate::Packet::add_extended_error) to read and write them
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct ExtendedError<'a> {
+    /// The info code of the error
+    pub info_code: ExtendedErrorCode,
+    /// Additional information for humans, UTF-8 encoded
+    pub extra_text: Option<Cow<'a, str>>,
+}
+
+impl<'a> ExtendedError<'a> {
+    /// Creates an extended error without extra text
+    pub fn new(info_code: ExtendedErrorCode) -> Self {
+        Self {
+            info_code,
+            extra_text: None,
+        }
+    }
+
+    /// Adds the extra text to this error
+    pub fn with_extra_text(mut self, extra_text: impl Into<Cow<'a, str>>) -> Self {
+        self.extra_text = Some(extra_text.into());
+        self
+    }
+
+    /// Transforms the inner data into its owned type
+    pub fn into_owned<'b>(self) -> ExtendedError<'b> {
+        ExtendedError {
+            info_code: self.info_code,
+            extra_text: self.extra_text.map(|text| text.into_owned().into()),
+        }
+    }
+
+    // The extra text is read leniently, as recommended by RFC 8914: invalid UTF-8 sequences are
+    // replaced and a trailing NUL, sent by some implementations, is removed
+    pub(super) fn parse_data(data: &'a [u8]) -> crate::Result<Self> {
+        let mut data = BytesBuffer::new(data);
+        let info_code = data.get_u16()?.into();
+        let mut extra_text = data.get_remaining();
+        while let [text @ .., 0] = extra_text {
+            extra_text = text;
+        }
+
+        let extra_text = match String::from_utf8_lossy(extra_text) {
+            text if text.is_empty() => None,
+            text => Some(text),
+        };
+
+        Ok(Self {
+            info_code,
+            extra_text,
+        })
+    }
+
+    pub(super) fn write_data<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        out.write_all(&u16::from(self.info_code).to_be_bytes())?;
+        if let Some(extra_text) = &self.extra_text {
+            out.write_all(extra_text.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn data_len(&self) -> usize {
+        2 + self.extra_text.as_ref().map_or(0, |text| text.len())
+    }
+}
+
+impl fmt::Display for ExtendedError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.info_code)?;
+        if let Some(extra_text) = &self.extra_text {
+            write!(f, ": {extra_text}")?;
+        }
+        Ok(())
+    }
+}
+
+macro_rules! extended_error_codes {
+    ($($(#[doc = $doc:literal])* $variant:ident = $code:literal => $purpose:literal,)+) => {
+        /// Info code of an extended DNS error, as registered in the
+        /// [IANA registry](https://www.iana.org/assignments/dns-parameters/dns-parameters.xhtml#extended-dns-error-codes)
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum ExtendedErrorCode {
+            $(
+                $(#[doc = $doc])*
+                $variant,
+            )+
+            /// Unassigned info code
+            Unknown(u16),
+        }
+
+        impl From<u16> for ExtendedErrorCode {
+            fn from(value: u16) -> Self {
+                match value {
+                    $($code => ExtendedErrorCode::$variant,)+
+                    v => ExtendedErrorCode::Unknown(v),
+                }
+            }
+        }
+
+        impl From<ExtendedErrorCode> for u16 {
+            fn from(value: ExtendedErrorCode) -> Self {
+                match value {
+                    $(ExtendedErrorCode::$variant => $code,)+
+                    ExtendedErrorCode::Unknown(v) => v,
+                }
+            }
+        }
+
+        impl fmt::Display for ExtendedErrorCode {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    $(ExtendedErrorCode::$variant => f.write_str($purpose),)+
+                    ExtendedErrorCode::Unknown(v) => write!(f, "Extended DNS Error {v}"),
+                }
+            }
+        }
+    };
+}
+
+extended_error_codes! {
+    /// The error does not match any other code, the extra text gives the details
+    Other = 0 => "Other Error",
+    /// The zone is signed with an unsupported DNSKEY algorithm
+    UnsupportedDnskeyAlgorithm = 1 => "Unsupported DNSKEY Algorithm",
+    /// The DS records use an unsupported digest type
+    UnsupportedDsDigestType = 2 => "Unsupported DS Digest Type",
+    /// The answer was served from stale cached data
+    StaleAnswer = 3 => "Stale Answer",
+    /// The answer was forged by policy
+    ForgedAnswer = 4 => "Forged Answer",
+    /// DNSSEC validation ended in the indeterminate state
+    DnssecIndeterminate = 5 => "DNSSEC Indeterminate",
+    /// DNSSEC validation ended in the bogus state
+    DnssecBogus = 6 => "DNSSEC Bogus",
+    /// The signatures have expired
+    SignatureExpired = 7 => "Signature Expired",
+    /// The signatures are not yet valid
+    SignatureNotYetValid = 8 => "Signature Not Yet Valid",
+    /// No DNSKEY record matches the DS records of the zone
+    DnskeyMissing = 9 => "DNSKEY Missing",
+    /// The RRSIG records expected for the answer are missing
+    RrsigsMissing = 10 => "RRSIGs Missing",
+    /// No DNSKEY record has the zone key bit set
+    NoZoneKeyBitSet = 11 => "No Zone Key Bit Set",
+    /// The NSEC or NSEC3 records expected for a denial of existence are missing
+    NsecMissing = 12 => "NSEC Missing",
+    /// The error was served from the cache
+    CachedError = 13 => "Cached Error",
+    /// The server is not ready to serve the query
+    NotReady = 14 => "Not Ready",
+    /// The domain is on a blocklist of the operator
+    Blocked = 15 => "Blocked",
+    /// The domain is blocked by an external requirement
+    Censored = 16 => "Censored",
+    /// The domain is on a blocklist requested by the client
+    Filtered = 17 => "Filtered",
+    /// The client is not authorized to query the server
+    Prohibited = 18 => "Prohibited",
+    /// The NXDOMAIN answer was served from stale cached data
+    StaleNxdomainAnswer = 19 => "Stale NXDomain Answer",
+    /// The server is not authoritative for the zone and does not recurse
+    NotAuthoritative = 20 => "Not Authoritative",
+    /// The operation or the query type is not supported
+    NotSupported = 21 => "Not Supported",
+    /// No authoritative server of the zone could be reached
+    NoReachableAuthority = 22 => "No Reachable Authority",
+    /// An unrecoverable network error happened
+    NetworkError = 23 => "Network Error",
+    /// The data of the zone is invalid
+    InvalidData = 24 => "Invalid Data",
+    /// The signature expiration is before its inception
+    SignatureExpiredBeforeValid = 25 => "Signature Expired before Valid",
+    /// The early data of a DNS over QUIC session was rejected,
+    /// [RFC 9250](https://www.rfc-editor.org/rfc/rfc9250)
+    TooEarly = 26 => "Too Early",
+    /// The NSEC3 iterations value is not supported,
+    /// [RFC 9276](https://www.rfc-editor.org/rfc/rfc9276)
+    UnsupportedNsec3Iterations = 27 => "Unsupported NSEC3 Iterations Value",
+    /// The server is unable to conform to the policy of the client
+    UnableToConformToPolicy = 28 => "Unable to conform to policy",
+    /// The answer was synthesized from cached data
+    Synthesized = 29 => "Synthesized",
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::{ToString, Vec};
+
+    #[test]
+    fn parse_and_write_extended_error() {
+        let error =
+            ExtendedError::new(ExtendedErrorCode::Blocked).with_extra_text("blocked by policy");
+        let mut data = Vec::new();
+        error.write_data(&mut data).unwrap();
+        assert_eq!(data[..2], [0, 15]);
+        assert_eq!(data.len(), error.data_len());
+        assert_eq!(ExtendedError::parse_data(&data).unwrap(), error);
+        assert_eq!(error.to_string(), "Blocked: blocked by policy");
+
+        let error = ExtendedError::parse_data(&[0, 6]).unwrap();
+        assert_eq!(error, ExtendedError::new(ExtendedErrorCode::DnssecBogus));
+
+        for code in 0..=30 {
+            assert_eq!(u16::from(ExtendedErrorCode::from(code)), code);
+        }
+        assert_eq!(
+            ExtendedErrorCode::from(49152),
+            ExtendedErrorCode::Unknown(49152)
+        );
+    }
+
+    #[test]
+    fn read_malformed_extra_text() {
+        let error = ExtendedError::parse_data(b"\0\x03stale\0").unwrap();
+        assert_eq!(error.info_code, ExtendedErrorCode::StaleAnswer);
+        assert_eq!(error.extra_text.as_deref(), Some("stale"));
+
+        let error = ExtendedError::parse_data(b"\0\x03bad \xff text").unwrap();
+        assert_eq!(error.extra_text.as_deref(), Some("bad \u{fffd} text"));
+
+        assert_eq!(
+            ExtendedError::parse_data(b"\0\x03\0").unwrap().extra_text,
+            None
+        );
+        assert!(ExtendedError::parse_data(&[0]).is_err());
+    }
+}
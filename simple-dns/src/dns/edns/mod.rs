@@ -0,0 +1,364 @@
+//! Typed EDNS(0) options, carried by the [`OPT`](crate::rdata::OPT) pseudo record,
+//! see [RFC 6891](https://www.rfc-editor.org/rfc/rfc6891#section-6.1.2)
+//!
+//! The options of an OPT record are kept as raw [`OPTCode`] values, [`EdnsOption`] parses and
+//! writes the options known by this crate. The typed options are read and written with the
+//! accessors of [`OPT`](crate::rdata::OPT), like [`OPT::client_subnet`](crate::rdata::OPT::client_subnet)
+//! and [`OPT::set_option`](crate::rdata::OPT::set_option)
+
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::WireFormat,
+    lib::{Cow, Vec, Write},
+    rdata::OPTCode,
+    Name, SimpleDnsError,
+};
+
+mod client_subnet;
+pub use client_subnet::{echo_client_subnet, ClientSubnet, SubnetCacheKey};
+
+mod cookie;
+pub use cookie::{check_query_cookie, Cookie, CookieAction};
+
+mod extended_error;
+pub use extended_error::{ExtendedError, ExtendedErrorCode};
+
+mod siphash;
+
+/// Option code of the name server identifier option, [RFC 5001](https://www.rfc-editor.org/rfc/rfc5001)
+pub const NSID: u16 = 3;
+/// Option code of the client subnet option, [RFC 7871](https://www.rfc-editor.org/rfc/rfc7871)
+pub const CLIENT_SUBNET: u16 = 8;
+/// Option code of the zone expire option, [RFC 7314](https://www.rfc-editor.org/rfc/rfc7314)
+pub const EXPIRE: u16 = 9;
+/// Option code of the cookie option, [RFC 7873](https://www.rfc-editor.org/rfc/rfc7873)
+pub const COOKIE: u16 = 10;
+/// Option code of the TCP keepalive option, [RFC 7828](https://www.rfc-editor.org/rfc/rfc7828)
+pub const TCP_KEEPALIVE: u16 = 11;
+/// Option code of the padding option, [RFC 7830](https://www.rfc-editor.org/rfc/rfc7830)
+pub const PADDING: u16 = 12;
+/// Option code of the chain query option, [RFC 7901](https://www.rfc-editor.org/rfc/rfc7901)
+pub const CHAIN: u16 = 13;
+/// Option code of the extended DNS error option, [RFC 8914](https://www.rfc-editor.org/rfc/rfc8914)
+pub const EXTENDED_ERROR: u16 = 15;
+/// Option code of the zone version option, [RFC 9660](https://www.rfc-editor.org/rfc/rfc9660)
+pub const ZONE_VERSION: u16 = 19;
+
+/// Block length of padded queries, as recommended by [RFC 8467](https://www.rfc-editor.org/rfc/rfc8467#section-4.1)
+pub const QUERY_BLOCK_LENGTH: u16 = 128;
+/// Block length of padded responses, as recommended by [RFC 8467](https://www.rfc-editor.org/rfc/rfc8467#section-4.1)
+pub const RESPONSE_BLOCK_LENGTH: u16 = 468;
+
+/// How messages are padded by [`Packet::build_bytes_vec_padded`](crate::Packet::build_bytes_vec_padded),
+/// see [RFC 8467](https://www.rfc-editor.org/rfc/rfc8467)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaddingPolicy {
+    /// Pads queries to a multiple of [`QUERY_BLOCK_LENGTH`] octets and responses to a multiple
+    /// of [`RESPONSE_BLOCK_LENGTH`] octets
+    Recommended,
+    /// Pads messages to a multiple of the given number of octets
+    BlockLength(u16),
+}
+
+impl PaddingPolicy {
+    /// Returns the block length of a query, or of a response when `is_response` is true
+    pub fn block_length(&self, is_response: bool) -> u16 {
+        match self {
+            PaddingPolicy::Recommended if is_response => RESPONSE_BLOCK_LENGTH,
+            PaddingPolicy::Recommended => QUERY_BLOCK_LENGTH,
+            PaddingPolicy::BlockLength(block_length) => *block_length,
+        }
+    }
+}
+
+/// An EDNS(0) option
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum EdnsOption<'a> {
+    /// Name server identifier, empty in queries
+    Nsid(Cow<'a, [u8]>),
+    /// The network of the client that originated the query
+    ClientSubnet(ClientSubnet),
+    /// The expire timer of the zone, in seconds, None in queries
+    Expire(Option<u32>),
+    /// Client and server cookies
+    Cookie(Cookie<'a>),
+    /// The idle timeout of the TCP connection, in units of 100 milliseconds, None in queries
+    TcpKeepalive(Option<u16>),
+    /// Padding, holds the number of padding octets
+    Padding(u16),
+    /// The closest trust point of a chain query
+    Chain(Name<'a>),
+    /// Extended DNS error
+    ExtendedError(ExtendedError<'a>),
+    /// The version of the zone, None in queries
+    ZoneVersion(Option<ZoneVersion<'a>>),
+    /// An option not known by this crate, kept as it was received
+    Unknown(OPTCode<'a>),
+}
+
+/// The version of a zone, sent in the zone version option,
+/// see [RFC 9660](https://www.rfc-editor.org/rfc/rfc9660#section-2)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct ZoneVersion<'a> {
+    /// Number of labels of the zone name, not counting the root label
+    pub label_count: u8,
+    /// Type of the version, see [`ZoneVersion::SOA_SERIAL`]
+    pub version_type: u8,
+    /// The version, its format depends on the type
+    pub version: Cow<'a, [u8]>,
+}
+
+impl ZoneVersion<'_> {
+    /// Version type of the SOA serial, the version holds the serial as 4 octets
+    pub const SOA_SERIAL: u8 = 0;
+
+    /// Transforms the inner data into its owned type
+    pub fn into_owned<'b>(self) -> ZoneVersion<'b> {
+        ZoneVersion {
+            label_count: self.label_count,
+            version_type: self.version_type,
+            version: self.version.into_owned().into(),
+        }
+    }
+}
+
+impl<'a> EdnsOption<'a> {
+    /// Returns the option code of this option
+    pub fn code(&self) -> u16 {
+        match self {
+            EdnsOption::Nsid(_) => NSID,
+            EdnsOption::ClientSubnet(_) => CLIENT_SUBNET,
+            EdnsOption::Expire(_) => EXPIRE,
+            EdnsOption::Cookie(_) => COOKIE,
+            EdnsOption::TcpKeepalive(_) => TCP_KEEPALIVE,
+            EdnsOption::Padding(_) => PADDING,
+            EdnsOption::Chain(_) => CHAIN,
+            EdnsOption::ExtendedError(_) => EXTENDED_ERROR,
+            EdnsOption::ZoneVersion(_) => ZONE_VERSION,
+            EdnsOption::Unknown(option) => option.code,
+        }
+    }
+
+    /// Parses the data of an option with the given option code
+    pub fn parse_data(code: u16, data: &'a [u8]) -> crate::Result<Self> {
+        let mut buffer = BytesBuffer::new(data);
+        let option = match code {
+            NSID => EdnsOption::Nsid(Cow::Borrowed(data)),
+            CLIENT_SUBNET => EdnsOption::ClientSubnet(ClientSubnet::parse_data(data)?),
+            EXPIRE if data.is_empty() => EdnsOption::Expire(None),
+            EXPIRE => EdnsOption::Expire(Some(buffer.get_u32()?)),
+            COOKIE => EdnsOption::Cookie(Cookie::parse_data(data)?),
+            TCP_KEEPALIVE if data.is_empty() => EdnsOption::TcpKeepalive(None),
+            TCP_KEEPALIVE => EdnsOption::TcpKeepalive(Some(buffer.get_u16()?)),
+            PADDING => EdnsOption::Padding(
+                u16::try_from(data.len()).map_err(|_| SimpleDnsError::InvalidDnsPacket)?,
+            ),
+            CHAIN => EdnsOption::Chain(Name::parse(&mut buffer)?),
+            EXTENDED_ERROR => EdnsOption::ExtendedError(ExtendedError::parse_data(data)?),
+            ZONE_VERSION if data.is_empty() => EdnsOption::ZoneVersion(None),
+            ZONE_VERSION => EdnsOption::ZoneVersion(Some(ZoneVersion {
+                label_count: buffer.get_u8()?,
+                version_type: buffer.get_u8()?,
+                version: Cow::Borrowed(buffer.get_remaining()),
+            })),
+            code => {
+                return Ok(EdnsOption::Unknown(OPTCode {
+                    code,
+                    data: Cow::Borrowed(data),
+                }))
+            }
+        };
+
+        // Options read from the buffer must not have trailing data
+        if buffer.has_remaining() && matches!(code, EXPIRE | TCP_KEEPALIVE | CHAIN) {
+            return Err(SimpleDnsError::InvalidDnsPacket);
+        }
+        Ok(option)
+    }
+
+    /// Writes the data of this option, without the option code and length
+    pub fn write_data<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        match self {
+            EdnsOption::Nsid(data) => out.write_all(data)?,
+            EdnsOption::ClientSubnet(subnet) => subnet.write_data(out)?,
+            EdnsOption::Expire(expire) => {
+                if let Some(expire) = expire {
+                    out.write_all(&expire.to_be_bytes())?;
+                }
+            }
+            EdnsOption::Cookie(cookie) => cookie.write_data(out)?,
+            EdnsOption::TcpKeepalive(timeout) => {
+                if let Some(timeout) = timeout {
+                    out.write_all(&timeout.to_be_bytes())?;
+                }
+            }
+            EdnsOption::Padding(len) => {
+                for _ in 0..*len {
+                    out.write_all(&[0])?;
+                }
+            }
+            EdnsOption::Chain(name) => name.write_to(out)?,
+            EdnsOption::ExtendedError(error) => error.write_data(out)?,
+            EdnsOption::ZoneVersion(version) => {
+                if let Some(version) = version {
+                    out.write_all(&[version.label_count, version.version_type])?;
+                    out.write_all(&version.version)?;
+                }
+            }
+            EdnsOption::Unknown(option) => out.write_all(&option.data)?,
+        }
+        Ok(())
+    }
+
+    /// Returns the length of the data of this option, without the option code and length
+    pub fn data_len(&self) -> usize {
+        match self {
+            EdnsOption::Nsid(data) => data.len(),
+            EdnsOption::ClientSubnet(subnet) => subnet.data_len(),
+            EdnsOption::Expire(expire) => expire.map_or(0, |_| 4),
+            EdnsOption::Cookie(cookie) => cookie.data_len(),
+            EdnsOption::TcpKeepalive(timeout) => timeout.map_or(0, |_| 2),
+            EdnsOption::Padding(len) => *len as usize,
+            EdnsOption::Chain(name) => name.len(),
+            EdnsOption::ExtendedError(error) => error.data_len(),
+            EdnsOption::ZoneVersion(version) => version.as_ref().map_or(0, |v| v.version.len() + 2),
+            EdnsOption::Unknown(option) => option.data.len(),
+        }
+    }
+
+    /// Transforms this option into a raw [`OPTCode`]
+    pub fn to_opt_code(&self) -> crate::Result<OPTCode<'static>> {
+        let mut data = Vec::with_capacity(self.data_len());
+        self.write_data(&mut data)?;
+        if data.len() > u16::MAX as usize {
+            return Err(SimpleDnsError::InvalidDnsPacket);
+        }
+
+        Ok(OPTCode {
+            code: self.code(),
+            data: Cow::Owned(data),
+        })
+    }
+
+    /// Transforms the inner data into its owned type
+    pub fn into_owned<'b>(self) -> EdnsOption<'b> {
+        match self {
+            EdnsOption::Nsid(data) => EdnsOption::Nsid(data.into_owned().into()),
+            EdnsOption::ClientSubnet(subnet) => EdnsOption::ClientSubnet(subnet),
+            EdnsOption::Expire(expire) => EdnsOption::Expire(expire),
+            EdnsOption::Cookie(cookie) => EdnsOption::Cookie(cookie.into_owned()),
+            EdnsOption::TcpKeepalive(timeout) => EdnsOption::TcpKeepalive(timeout),
+            EdnsOption::Padding(len) => EdnsOption::Padding(len),
+            EdnsOption::Chain(name) => EdnsOption::Chain(name.into_owned()),
+            EdnsOption::ExtendedError(error) => EdnsOption::ExtendedError(error.into_owned()),
+            EdnsOption::ZoneVersion(version) => {
+                EdnsOption::ZoneVersion(version.map(|v| v.into_owned()))
+            }
+            EdnsOption::Unknown(option) => EdnsOption::Unknown(option.into_owned()),
+        }
+    }
+}
+
+impl<'a> WireFormat<'a> for EdnsOption<'a> {
+    const MINIMUM_LEN: usize = 4;
+
+    fn parse(data: &mut BytesBuffer<'a>) -> crate::Result<Self>
+    where
+        Self: Sized,
+    {
+        let code = data.get_u16()?;
+        let length = data.get_u16()? as usize;
+        Self::parse_data(code, data.get_slice(length)?)
+    }
+
+    fn write_to<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        let length =
+            u16::try_from(self.data_len()).map_err(|_| SimpleDnsError::InvalidDnsPacket)?;
+        out.write_all(&self.code().to_be_bytes())?;
+        out.write_all(&length.to_be_bytes())?;
+        self.write_data(out)
+    }
+
+    fn len(&self) -> usize {
+        self.data_len() + Self::MINIMUM_LEN
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::{vec, Ipv4Addr};
+
+    fn round_trip(option: EdnsOption<'_>, expected: &[u8]) {
+        let mut data = Vec::new();
+        option.write_to(&mut data).unwrap();
+        assert_eq!(data, expected);
+        assert_eq!(data.len(), option.len());
+        assert_eq!(EdnsOption::parse(&mut data[..].into()).unwrap(), option);
+    }
+
+    #[test]
+    fn parse_and_write_options() {
+        round_trip(EdnsOption::Nsid(Cow::Borrowed(b"ns1")), b"\0\x03\0\x03ns1");
+        round_trip(EdnsOption::Expire(None), &[0, 9, 0, 0]);
+        round_trip(
+            EdnsOption::Expire(Some(3600)),
+            &[0, 9, 0, 4, 0, 0, 0x0e, 0x10],
+        );
+        round_trip(EdnsOption::TcpKeepalive(Some(300)), &[0, 11, 0, 2, 1, 0x2c]);
+        round_trip(EdnsOption::Padding(3), &[0, 12, 0, 3, 0, 0, 0]);
+        round_trip(
+            EdnsOption::Chain(Name::new_unchecked("example")),
+            b"\0\x0d\0\x09\x07example\0",
+        );
+        round_trip(
+            EdnsOption::ZoneVersion(Some(ZoneVersion {
+                label_count: 2,
+                version_type: ZoneVersion::SOA_SERIAL,
+                version: Cow::Borrowed(&[0, 0, 0, 1]),
+            })),
+            &[0, 19, 0, 6, 2, 0, 0, 0, 0, 1],
+        );
+        round_trip(
+            EdnsOption::ClientSubnet(ClientSubnet {
+                source_prefix_length: 24,
+                scope_prefix_length: 0,
+                address: Ipv4Addr::new(192, 0, 2, 0).into(),
+            }),
+            &[0, 8, 0, 7, 0, 1, 24, 0, 192, 0, 2],
+        );
+        round_trip(
+            EdnsOption::Cookie(Cookie {
+                client: [1; 8],
+                server: None,
+            }),
+            &[0, 10, 0, 8, 1, 1, 1, 1, 1, 1, 1, 1],
+        );
+        round_trip(
+            EdnsOption::ExtendedError(
+                ExtendedError::new(ExtendedErrorCode::Blocked).with_extra_text("blocked"),
+            ),
+            b"\0\x0f\0\x09\0\x0fblocked",
+        );
+    }
+
+    #[test]
+    fn keep_unknown_options() {
+        let option = EdnsOption::Unknown(OPTCode {
+            code: 65001,
+            data: Cow::Owned(vec![1, 2, 3]),
+        });
+        round_trip(option.clone(), &[0xfd, 0xe9, 0, 3, 1, 2, 3]);
+        assert_eq!(option.code(), 65001);
+        assert_eq!(option.to_opt_code().unwrap().data[..], [1, 2, 3]);
+    }
+
+    #[test]
+    fn reject_invalid_options() {
+        assert!(EdnsOption::parse_data(EXPIRE, &[0, 0]).is_err());
+        assert!(EdnsOption::parse_data(EXPIRE, &[0, 0, 0, 0, 0]).is_err());
+        assert!(EdnsOption::parse_data(TCP_KEEPALIVE, &[0, 0, 0]).is_err());
+        assert!(EdnsOption::parse_data(CHAIN, b"\x07example\0\0").is_err());
+        assert!(EdnsOption::parse_data(ZONE_VERSION, &[2]).is_err());
+    }
+}
@@ -0,0 +1,77 @@
+//! SipHash-2-4, see [the SipHash paper](https://www.aumasson.jp/siphash/siphash.pdf)
+//!
+//! Used to create the DNS cookies defined by [RFC 9018](https://www.rfc-editor.org/rfc/rfc9018),
+//! implemented here so cookies do not need any cryptographic library
+
+/// Returns the SipHash-2-4 of `data` with the 128 bits `key`, as 8 little endian octets
+pub(crate) fn siphash24(key: &[u8; 16], data: &[u8]) -> [u8; 8] {
+    let k0 = u64::from_le_bytes(key[..8].try_into().unwrap());
+    let k1 = u64::from_le_bytes(key[8..].try_into().unwrap());
+    let mut state = [
+        k0 ^ 0x736f6d6570736575,
+        k1 ^ 0x646f72616e646f6d,
+        k0 ^ 0x6c7967656e657261,
+        k1 ^ 0x7465646279746573,
+    ];
+
+    let mut blocks = data.chunks_exact(8);
+    for block in &mut blocks {
+        compress(&mut state, u64::from_le_bytes(block.try_into().unwrap()));
+    }
+
+    // The last block holds the remaining octets and the length of the data in its top octet
+    let mut last = [0; 8];
+    let remainder = blocks.remainder();
+    last[..remainder.len()].copy_from_slice(remainder);
+    last[7] = data.len() as u8;
+    compress(&mut state, u64::from_le_bytes(last));
+
+    state[2] ^= 0xff;
+    for _ in 0..4 {
+        round(&mut state);
+    }
+
+    (state[0] ^ state[1] ^ state[2] ^ state[3]).to_le_bytes()
+}
+
+fn compress(state: &mut [u64; 4], block: u64) {
+    state[3] ^= block;
+    round(state);
+    round(state);
+    state[0] ^= block;
+}
+
+fn round([v0, v1, v2, v3]: &mut [u64; 4]) {
+    *v0 = v0.wrapping_add(*v1);
+    *v1 = v1.rotate_left(13) ^ *v0;
+    *v0 = v0.rotate_left(32);
+    *v2 = v2.wrapping_add(*v3);
+    *v3 = v3.rotate_left(16) ^ *v2;
+    *v0 = v0.wrapping_add(*v3);
+    *v3 = v3.rotate_left(21) ^ *v0;
+    *v2 = v2.wrapping_add(*v1);
+    *v1 = v1.rotate_left(17) ^ *v2;
+    *v2 = v2.rotate_left(32);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reference_vectors() {
+        // Appendix A of the SipHash paper, with the key 00 01 .. 0f
+        let key: [u8; 16] = core::array::from_fn(|i| i as u8);
+        let data: [u8; 15] = core::array::from_fn(|i| i as u8);
+
+        assert_eq!(
+            siphash24(&key, &[]),
+            [0x31, 0x0e, 0x0e, 0xdd, 0x47, 0xdb, 0x6f, 0x72]
+        );
+        assert_eq!(siphash24(&key, &data), 0xa129ca6149be45e5u64.to_le_bytes());
+        assert_eq!(
+            siphash24(&key, &data[..8]),
+            [0x62, 0x24, 0x93, 0x9a, 0x79, 0xf5, 0xf5, 0x93]
+        );
+    }
+}
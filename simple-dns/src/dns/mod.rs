@@ -6,6 +6,9 @@ pub use character_string::CharacterString;
 mod name;
 pub use name::{Label, Name};
 
+mod digest;
+pub use digest::{DigestAlgorithm, DigestProvider};
+
 mod packet;
 pub use packet::Packet;
 
@@ -17,6 +20,8 @@ pub mod header_buffer;
 mod wire_format;
 pub(crate) use wire_format::WireFormat;
 
+mod presentation_format;
+
 mod question;
 pub use question::Question;
 
@@ -26,6 +31,16 @@ pub use rdata::TYPE;
 mod resource_record;
 pub use resource_record::ResourceRecord;
 
+pub mod zone;
+
+pub mod dnssec;
+
+pub mod tsig;
+
+pub mod tkey;
+
+pub mod edns;
+
 use crate::lib::TryFrom;
 use bitflags::bitflags;
 
@@ -108,6 +123,19 @@ impl TryFrom<u16> for QTYPE {
     }
 }
 
+impl crate::lib::fmt::Display for QTYPE {
+    fn fmt(&self, f: &mut crate::lib::fmt::Formatter<'_>) -> crate::lib::fmt::Result {
+        match self {
+            QTYPE::TYPE(ty) => write!(f, "{ty}"),
+            QTYPE::IXFR => f.write_str("IXFR"),
+            QTYPE::AXFR => f.write_str("AXFR"),
+            QTYPE::MAILB => f.write_str("MAILB"),
+            QTYPE::MAILA => f.write_str("MAILA"),
+            QTYPE::ANY => f.write_str("ANY"),
+        }
+    }
+}
+
 impl From<QTYPE> for u16 {
     fn from(val: QTYPE) -> Self {
         match val {
@@ -153,6 +181,47 @@ impl TryFrom<u16> for CLASS {
     }
 }
 
+impl crate::lib::FromStr for CLASS {
+    type Err = crate::SimpleDnsError;
+
+    /// Parses a class from its mnemonic or from the generic `CLASSnnn` format,
+    /// [RFC 3597](https://datatracker.ietf.org/doc/html/rfc3597#section-5)
+    fn from_str(s: &str) -> crate::Result<Self> {
+        use self::CLASS::*;
+        let class = [IN, CS, CH, HS, NONE]
+            .into_iter()
+            .find(|class| class.mnemonic().eq_ignore_ascii_case(s));
+
+        match (class, s.get(..5)) {
+            (Some(class), _) => Ok(class),
+            (None, Some(prefix)) if prefix.eq_ignore_ascii_case("CLASS") => s[5..]
+                .parse::<u16>()
+                .map_err(|_| crate::SimpleDnsError::InvalidPresentationFormat)
+                .and_then(CLASS::try_from),
+            _ => Err(crate::SimpleDnsError::InvalidPresentationFormat),
+        }
+    }
+}
+
+impl CLASS {
+    /// Returns the mnemonic used to represent this class in presentation format
+    pub(crate) fn mnemonic(&self) -> &'static str {
+        match self {
+            CLASS::IN => "IN",
+            CLASS::CS => "CS",
+            CLASS::CH => "CH",
+            CLASS::HS => "HS",
+            CLASS::NONE => "NONE",
+        }
+    }
+}
+
+impl crate::lib::fmt::Display for CLASS {
+    fn fmt(&self, f: &mut crate::lib::fmt::Formatter<'_>) -> crate::lib::fmt::Result {
+        f.write_str(self.mnemonic())
+    }
+}
+
 /// Possible QCLASS values for a Question in a DNS packet  
 /// Each value is described according to its own RFC
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -180,6 +249,15 @@ impl TryFrom<u16> for QCLASS {
     }
 }
 
+impl crate::lib::fmt::Display for QCLASS {
+    fn fmt(&self, f: &mut crate::lib::fmt::Formatter<'_>) -> crate::lib::fmt::Result {
+        match self {
+            QCLASS::CLASS(class) => write!(f, "{class}"),
+            QCLASS::ANY => f.write_str("ANY"),
+        }
+    }
+}
+
 impl From<QCLASS> for u16 {
     fn from(val: QCLASS) -> Self {
         match val {
@@ -259,6 +337,9 @@ pub enum RCODE {
     /// EDNS Version not supported by the responder
     /// [RFC 6891](https://datatracker.ietf.org/doc/html/rfc6891)
     BADVERS = 16,
+    /// The server cookie is missing or invalid, the response holds a new one
+    /// [RFC 7873](https://datatracker.ietf.org/doc/html/rfc7873)
+    BADCOOKIE = 23,
 
     /// Reserved for future use.
     Reserved,
@@ -280,6 +361,7 @@ impl From<u16> for RCODE {
             9 => NOTAUTH,
             10 => NOTZONE,
             16 => BADVERS,
+            23 => BADCOOKIE,
             _ => RCODE::Reserved,
         }
     }
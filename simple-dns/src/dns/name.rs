@@ -6,7 +6,9 @@ use crate::{
     },
 };
 
-use super::{WireFormat, MAX_LABEL_LENGTH, MAX_NAME_LENGTH};
+use core::cmp::Ordering;
+
+use super::{presentation_format::unescape, WireFormat, MAX_LABEL_LENGTH, MAX_NAME_LENGTH};
 
 const POINTER_MASK: u8 = 0b1100_0000;
 const POINTER_MASK_U16: u16 = 0b1100_0000_0000_0000;
@@ -119,6 +121,114 @@ impl<'a> Name<'a> {
         }
     }
 
+    /// Replaces the `owner` suffix of self with `target`, as done when following a DNAME record,
+    /// see [RFC 6672](https://www.rfc-editor.org/rfc/rfc6672#section-2.2).
+    /// Labels are compared ignoring the case of US-ASCII letters.
+    ///
+    /// Returns [`crate::SimpleDnsError::AttemptedInvalidOperation`] if self is not a subdomain of
+    /// `owner`, the DNAME owner itself is not redirected.
+    /// Returns [`crate::SimpleDnsError::NameTooLong`] if the resulting name exceeds 255
+    /// octets, in which case a server must answer with [`crate::RCODE::YXDOMAIN`]
+    ///
+    /// Example:
+    /// ```
+    /// # use simple_dns::Name;
+    /// let name = Name::new_unchecked("a.b.example.com");
+    /// let substituted = name
+    ///     .substitute_dname(&Name::new_unchecked("example.com"), &Name::new_unchecked("example.net"))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(substituted.to_string(), "a.b.example.net");
+    /// ```
+    pub fn substitute_dname(&self, owner: &Name, target: &Name) -> crate::Result<Name<'static>> {
+        let is_descendant = self.labels.len() > owner.labels.len()
+            && owner
+                .iter()
+                .rev()
+                .zip(self.iter().rev())
+                .all(|(o, s)| o.data.eq_ignore_ascii_case(&s.data));
+
+        if !is_descendant {
+            return Err(crate::SimpleDnsError::AttemptedInvalidOperation);
+        }
+
+        let prefix = &self.labels[..self.labels.len() - owner.labels.len()];
+        let name = Name {
+            labels: prefix
+                .iter()
+                .chain(target.iter())
+                .map(|label| label.clone().into_owned())
+                .collect(),
+        };
+
+        if name.len() > MAX_NAME_LENGTH {
+            Err(crate::SimpleDnsError::NameTooLong)
+        } else {
+            Ok(name)
+        }
+    }
+
+    /// Parses a name in presentation format, decoding escape sequences.
+    ///
+    /// Names that do not end with a dot are relative and are completed with `origin`,
+    /// `@` represents the origin itself.
+    pub(crate) fn from_presentation<'b>(
+        text: &str,
+        origin: Option<&Name<'_>>,
+    ) -> crate::Result<Name<'b>> {
+        if text == "@" {
+            return origin
+                .map(|origin| origin.clone().into_owned())
+                .ok_or(crate::SimpleDnsError::InvalidPresentationFormat);
+        }
+
+        let mut labels = Vec::new();
+        if text != "." {
+            let bytes = text.as_bytes();
+            let mut start = 0;
+            let mut i = 0;
+            let mut absolute = false;
+
+            while i <= bytes.len() {
+                match bytes.get(i) {
+                    Some(b'\\') => i = (i + 2).min(bytes.len()),
+                    Some(b'.') | None => {
+                        if i == start {
+                            if i == bytes.len() && !labels.is_empty() {
+                                absolute = true;
+                                break;
+                            }
+                            return Err(crate::SimpleDnsError::InvalidPresentationFormat);
+                        }
+
+                        let data = unescape(&text[start..i])?;
+                        if data.len() > MAX_LABEL_LENGTH {
+                            return Err(crate::SimpleDnsError::InvalidServiceLabel);
+                        }
+
+                        labels.push(Label::new_unchecked(data));
+                        i += 1;
+                        start = i;
+                    }
+                    Some(_) => i += 1,
+                }
+            }
+
+            if !absolute {
+                if let Some(origin) = origin {
+                    labels.extend(origin.labels.iter().map(|label| label.clone().into_owned()));
+                }
+            }
+        }
+
+        let name = Name { labels };
+        if name.len() > MAX_NAME_LENGTH {
+            Err(crate::SimpleDnsError::InvalidServiceName)
+        } else {
+            Ok(name)
+        }
+    }
+
     /// Get the labels that compose this name
     pub fn get_labels(&'_ self) -> &'_ [Label<'a>] {
         &self.labels[..]
@@ -171,6 +281,18 @@ impl<'a> Name<'a> {
     pub fn as_bytes(&self) -> impl Iterator<Item = &[u8]> {
         self.labels.iter().map(|label| label.as_ref())
     }
+
+    /// Returns the canonical form of this name, where every uppercase US-ASCII letter is replaced
+    /// by its lowercase counterpart, see [RFC 4034](https://www.rfc-editor.org/rfc/rfc4034#section-6.2)
+    pub fn to_canonical(&self) -> Name<'static> {
+        Name {
+            labels: self
+                .labels
+                .iter()
+                .map(|label| Label::new_unchecked(label.data.to_ascii_lowercase()))
+                .collect(),
+        }
+    }
 }
 
 impl<'a> WireFormat<'a> for Name<'a> {
@@ -307,6 +429,38 @@ impl PartialEq for Name<'_> {
     }
 }
 
+/// Names are sorted in canonical order, as defined by [RFC 4034](https://www.rfc-editor.org/rfc/rfc4034#section-6.1):
+/// label by label starting from the rightmost label, ignoring the case of US-ASCII letters.
+///
+/// Names that differ only by case are then sorted by their original bytes, to remain consistent
+/// with [`PartialEq`]
+impl Ord for Name<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        for (a, b) in self.labels.iter().rev().zip(other.labels.iter().rev()) {
+            match a
+                .data
+                .iter()
+                .map(u8::to_ascii_lowercase)
+                .cmp(b.data.iter().map(u8::to_ascii_lowercase))
+            {
+                Ordering::Equal => continue,
+                ordering => return ordering,
+            }
+        }
+
+        self.labels
+            .len()
+            .cmp(&other.labels.len())
+            .then_with(|| self.labels.iter().rev().cmp(other.labels.iter().rev()))
+    }
+}
+
+impl PartialOrd for Name<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Hash for Name<'_> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.labels.hash(state);
@@ -456,6 +610,39 @@ mod tests {
     use crate::lib::Cursor;
     use crate::{lib::Vec, SimpleDnsError};
 
+    #[test]
+    fn parse_presentation_format() {
+        let origin = Name::new_unchecked("example.com");
+
+        let name = Name::from_presentation("www", Some(&origin)).unwrap();
+        assert_eq!(name, Name::new_unchecked("www.example.com"));
+
+        let name = Name::from_presentation("www.other.", Some(&origin)).unwrap();
+        assert_eq!(name, Name::new_unchecked("www.other"));
+
+        let name = Name::from_presentation("@", Some(&origin)).unwrap();
+        assert_eq!(name, origin);
+
+        let name = Name::from_presentation(".", Some(&origin)).unwrap();
+        assert_eq!(name.get_labels().len(), 0);
+
+        let name = Name::from_presentation("a\\.b\\065\\\\.", None).unwrap();
+        assert_eq!(name.get_labels(), [Label::new_unchecked(&b"a.bA\\"[..])]);
+
+        assert!(Name::from_presentation("@", None).is_err());
+        assert!(Name::from_presentation("", None).is_err());
+        assert!(Name::from_presentation("a..b", None).is_err());
+        assert!(Name::from_presentation(".a", None).is_err());
+        assert_eq!(
+            Name::from_presentation(&"a".repeat(64), None),
+            Err(SimpleDnsError::InvalidServiceLabel)
+        );
+        assert_eq!(
+            Name::from_presentation(&"a.".repeat(128), None),
+            Err(SimpleDnsError::InvalidServiceName)
+        );
+    }
+
     #[test]
     fn construct_valid_names() {
         assert!(Name::new("some").is_ok());
@@ -752,6 +939,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn substitute_dname() {
+        // RFC 6672 section 2.2
+        let owner = Name::new_unchecked("example.com");
+        let target = Name::new_unchecked("example.net");
+
+        assert_eq!(
+            Name::new_unchecked("a.b.EXAMPLE.com")
+                .substitute_dname(&owner, &target)
+                .unwrap(),
+            Name::new_unchecked("a.b.example.net")
+        );
+        assert_eq!(
+            owner.substitute_dname(&owner, &target),
+            Err(SimpleDnsError::AttemptedInvalidOperation)
+        );
+        assert_eq!(
+            Name::new_unchecked("a.example.org").substitute_dname(&owner, &target),
+            Err(SimpleDnsError::AttemptedInvalidOperation)
+        );
+
+        // Substitution is not possible if the result is longer than 255 octets
+        let label = "a".repeat(63);
+        let long_name = format!("{label}.{label}.{label}.{}.example.com", "b".repeat(40));
+        let name = Name::new(&long_name).unwrap();
+        assert!(name
+            .substitute_dname(&owner, &Name::new_unchecked("example.net"))
+            .is_ok());
+        assert_eq!(
+            name.substitute_dname(&owner, &Name::new_unchecked("longer-target.example.net")),
+            Err(SimpleDnsError::NameTooLong)
+        );
+    }
+
     #[test]
     fn display_invalid_label() {
         let input = b"invalid\xF0\x90\x80label";
@@ -794,4 +1015,43 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn to_canonical() {
+        let name = Name::new_unchecked("WWW.Example.COM");
+        assert_eq!(name.to_canonical(), Name::new_unchecked("www.example.com"));
+
+        let name = Name::new_with_labels(&[Label::new_unchecked(&b"\xc9T\xc9"[..])]);
+        assert_eq!(
+            name.to_canonical().get_labels(),
+            [Label::new_unchecked(&b"\xc9t\xc9"[..])]
+        );
+    }
+
+    #[test]
+    fn canonical_order() {
+        // RFC 4034 section 6.1
+        let label = |data: &'static [u8]| Label::new_unchecked(data);
+        let sorted = [
+            Name::new_unchecked("example"),
+            Name::new_unchecked("a.example"),
+            Name::new_unchecked("yljkjljk.a.example"),
+            Name::new_unchecked("Z.a.example"),
+            Name::new_unchecked("zABC.a.EXAMPLE"),
+            Name::new_unchecked("z.example"),
+            Name::new_with_labels(&[label(b"\x01"), label(b"z"), label(b"example")]),
+            Name::new_unchecked("*.z.example"),
+            Name::new_with_labels(&[label(b"\xc8"), label(b"z"), label(b"example")]),
+        ];
+
+        let mut names = sorted.to_vec();
+        names.reverse();
+        names.sort();
+        assert_eq!(names, sorted);
+
+        // Names that differ only by case are not equal
+        let upper = Name::new_unchecked("EXAMPLE");
+        assert_eq!(upper.cmp(&sorted[0]), Ordering::Less);
+        assert_eq!(sorted[0].cmp(&sorted[0].clone()), Ordering::Equal);
+    }
 }
@@ -1,14 +1,22 @@
-use super::{Header, PacketFlag, Question, ResourceRecord, WireFormat, OPCODE};
+use super::{Header, Name, PacketFlag, Question, ResourceRecord, WireFormat, CLASS, OPCODE};
 use crate::{
     bytes_buffer::BytesBuffer,
-    lib::{Seek, Vec, Write},
-    rdata::OPT,
-    RCODE,
+    edns::{self, ClientSubnet, Cookie, EdnsOption, ExtendedError, PaddingPolicy},
+    lib::{Cow, Seek, Vec, Write},
+    rdata::{RData, OPT, TSIG},
+    tsig::{self, HmacProvider, TsigError, TsigMessage},
+    SimpleDnsError, RCODE, TYPE,
 };
 
+/// UDP payload size of the OPT records added to packets, as recommended by the DNS flag day 2020
+const DEFAULT_UDP_PAYLOAD_SIZE: u16 = 1232;
+
 /// Represents a DNS message packet
 ///
 /// When working with EDNS packets, use [Packet::opt] and [Packet::opt_mut] to add or access [OPT] packet information
+///
+/// TSIG signed messages are created with [Packet::sign_tsig] and verified with [Packet::verify_tsig],
+/// the TSIG record is always the last additional record
 #[derive(Debug, Clone)]
 pub struct Packet<'a> {
     /// Packet header
@@ -22,6 +30,8 @@ pub struct Packet<'a> {
     /// Aditional records section.  
     /// DO NOT use this field to add OPT record, use [`Packet::opt_mut`] instead
     pub additional_records: Vec<ResourceRecord<'a>>,
+    /// TSIG record, written after the additional records
+    tsig: Option<ResourceRecord<'a>>,
 }
 
 impl<'a> Packet<'a> {
@@ -33,6 +43,7 @@ impl<'a> Packet<'a> {
             answers: Vec::new(),
             name_servers: Vec::new(),
             additional_records: Vec::new(),
+            tsig: None,
         }
     }
 
@@ -44,6 +55,7 @@ impl<'a> Packet<'a> {
             answers: Vec::new(),
             name_servers: Vec::new(),
             additional_records: Vec::new(),
+            tsig: None,
         }
     }
 
@@ -104,9 +116,55 @@ impl<'a> Packet<'a> {
         &mut self.header.opt
     }
 
+    /// Returns the OPT record of this packet, adding one when it is missing
+    pub(crate) fn opt_or_default(&mut self) -> &mut OPT<'a> {
+        self.header.opt.get_or_insert_with(|| OPT {
+            opt_codes: Vec::new(),
+            udp_packet_size: DEFAULT_UDP_PAYLOAD_SIZE,
+            version: 0,
+        })
+    }
+
+    /// Returns the extended DNS errors of this packet, see [RFC 8914](https://www.rfc-editor.org/rfc/rfc8914).
+    /// Options too short to hold an info code are skipped
+    pub fn extended_errors(&self) -> impl Iterator<Item = ExtendedError<'_>> {
+        self.opt()
+            .into_iter()
+            .flat_map(|opt| opt.extended_errors().filter_map(Result::ok))
+    }
+
+    /// Sets the cookie option of this packet, creating the [OPT] record if it is missing,
+    /// see [RFC 7873](https://www.rfc-editor.org/rfc/rfc7873)
+    pub fn set_cookie(&mut self, cookie: Cookie<'_>) -> crate::Result<()> {
+        self.opt_or_default()
+            .set_option(&EdnsOption::Cookie(cookie))
+    }
+
+    /// Sets the client subnet option of this packet, creating the [OPT] record if it is missing,
+    /// see [RFC 7871](https://www.rfc-editor.org/rfc/rfc7871)
+    pub fn set_client_subnet(&mut self, subnet: ClientSubnet) -> crate::Result<()> {
+        self.opt_or_default()
+            .set_option(&EdnsOption::ClientSubnet(subnet))
+    }
+
+    /// Adds an extended DNS error to this packet, creating the [OPT] record if it is missing.
+    /// A packet may hold more than one extended error
+    pub fn add_extended_error(&mut self, error: ExtendedError<'_>) -> crate::Result<()> {
+        self.opt_or_default()
+            .add_option(&EdnsOption::ExtendedError(error))
+    }
+
+    /// Get the TSIG resource record for this packet, if present. Its rdata is always a [TSIG]
+    pub fn tsig(&self) -> Option<&ResourceRecord<'a>> {
+        self.tsig.as_ref()
+    }
+
     /// Changes this packet into a reply packet by replacing its header
+    ///
+    /// The TSIG record of the query is removed, the reply must be signed again
     pub fn into_reply(mut self) -> Self {
         self.header = Header::new_reply(self.header.id, self.header.opcode);
+        self.tsig = None;
         self
     }
 
@@ -121,6 +179,15 @@ impl<'a> Packet<'a> {
         let mut additional_records: Vec<ResourceRecord> =
             Self::parse_section(&mut data, header.additional_records)?;
 
+        let tsig = match additional_records
+            .iter()
+            .position(|rr| rr.rdata.type_code() == TYPE::TSIG)
+        {
+            Some(i) if i == additional_records.len() - 1 => additional_records.pop(),
+            Some(_) => return Err(SimpleDnsError::InvalidDnsPacket),
+            None => None,
+        };
+
         header.extract_info_from_opt_rr(
             additional_records
                 .iter()
@@ -134,6 +201,7 @@ impl<'a> Packet<'a> {
             answers,
             name_servers,
             additional_records,
+            tsig,
         })
     }
 
@@ -171,6 +239,43 @@ impl<'a> Packet<'a> {
         Ok(out.into_inner())
     }
 
+    /// Creates a new [Vec`<u8>`](`Vec<T>`) and write the contents of this package in wire format
+    /// with compression enabled, padded to a multiple of the block length of the `policy`,
+    /// see [RFC 7830](https://www.rfc-editor.org/rfc/rfc7830) and [RFC 8467](https://www.rfc-editor.org/rfc/rfc8467)
+    ///
+    /// The length of the message is calculated with compression and a padding option is added
+    /// as the last option of the [OPT] record, which is created if it is missing. A padding option
+    /// already present is replaced. This packet is not changed.
+    ///
+    /// Signed messages can not be padded after signing, this function returns
+    /// [`SimpleDnsError::InvalidDnsPacket`] for packets holding a TSIG record, or a SIG(0) record
+    /// at the end of the additional records. A block length of 0 adds an empty padding option
+    pub fn build_bytes_vec_padded(&self, policy: PaddingPolicy) -> crate::Result<Vec<u8>> {
+        let sig0 = matches!(
+            self.additional_records.last(),
+            Some(ResourceRecord { rdata: RData::SIG(sig), .. }) if sig.is_sig0()
+        );
+        if self.tsig.is_some() || sig0 {
+            return Err(SimpleDnsError::InvalidDnsPacket);
+        }
+
+        let mut padded = self.clone();
+        let opt = padded.opt_or_default();
+        opt.remove_option(edns::PADDING);
+        opt.add_option(&EdnsOption::Padding(0))?;
+        let unpadded_len = padded.build_bytes_vec_compressed()?.len();
+
+        let block_length = policy.block_length(self.has_flags(PacketFlag::RESPONSE));
+        let block_length = usize::from(block_length.max(1));
+        let padding = (block_length - unpadded_len % block_length) % block_length;
+
+        // The padding is shorter than the block length, it always fits the option
+        padded
+            .opt_or_default()
+            .set_option(&EdnsOption::Padding(padding as u16))?;
+        padded.build_bytes_vec_compressed()
+    }
+
     /// Write the contents of this package in wire format into the provided writer
     pub fn write_to<T: Write>(&self, out: &mut T) -> crate::Result<()> {
         self.write_header(out)?;
@@ -193,6 +298,10 @@ impl<'a> Packet<'a> {
             e.write_to(out)?;
         }
 
+        if let Some(rr) = &self.tsig {
+            rr.write_to(out)?;
+        }
+
         out.flush()?;
         Ok(())
     }
@@ -219,6 +328,11 @@ impl<'a> Packet<'a> {
         for e in &self.additional_records {
             e.write_compressed_to(out, &mut name_refs)?;
         }
+
+        // The TSIG record names must not be compressed
+        if let Some(rr) = &self.tsig {
+            rr.write_to(out)?;
+        }
         out.flush()?;
 
         Ok(())
@@ -230,17 +344,207 @@ impl<'a> Packet<'a> {
             self.questions.len() as u16,
             self.answers.len() as u16,
             self.name_servers.len() as u16,
-            self.additional_records.len() as u16 + u16::from(self.header.opt.is_some()),
+            self.additional_records.len() as u16
+                + u16::from(self.header.opt.is_some())
+                + u16::from(self.tsig.is_some()),
         )
     }
+
+    /// Signs this packet with the shared `secret` of the key `key_name`, as defined by
+    /// [RFC 8945](https://www.rfc-editor.org/rfc/rfc8945#section-5.1), and returns the signed
+    /// message in wire format, with compression enabled.
+    ///
+    /// `time_signed` is the current time, in seconds since Jan 1 1970, and `position` defines
+    /// the data covered by the MAC. Any previous TSIG record is replaced, the new record is
+    /// available through [Packet::tsig], its MAC is needed to sign or verify the next message
+    /// of the exchange.
+    ///
+    /// The packet must not be changed after it is signed, the returned bytes must be sent as
+    /// they are. Fails with [SimpleDnsError::SigningFailed] if the `hmac` provider does not
+    /// support the `algorithm`
+    pub fn sign_tsig<H: HmacProvider + ?Sized>(
+        &mut self,
+        key_name: Name<'a>,
+        algorithm: Name<'a>,
+        secret: &[u8],
+        time_signed: u64,
+        position: TsigMessage<'_>,
+        hmac: &H,
+    ) -> crate::Result<Vec<u8>> {
+        self.tsig = None;
+        let message = self.build_bytes_vec_compressed()?;
+
+        let mut tsig = TSIG {
+            algorithm,
+            time_signed,
+            fudge: tsig::DEFAULT_FUDGE,
+            mac: Cow::Borrowed(&[]),
+            original_id: self.id(),
+            error: 0,
+            other_data: Cow::Borrowed(&[]),
+        };
+        let data = tsig::mac_data(&key_name, &tsig, &message, position)?;
+        tsig.mac = hmac
+            .hmac(&tsig.algorithm, secret, &data)
+            .ok_or(SimpleDnsError::SigningFailed)?
+            .into();
+
+        self.tsig = Some(ResourceRecord::new(
+            key_name,
+            CLASS::IN,
+            0,
+            RData::TSIG(tsig),
+        ));
+        self.build_bytes_vec_compressed()
+    }
+
+    /// Parses a TSIG signed `message` and verifies its MAC with the shared `secret` of the key
+    /// `key_name`, as defined by [RFC 8945](https://www.rfc-editor.org/rfc/rfc8945#section-5.2).
+    ///
+    /// The MAC is calculated over the received bytes, without the TSIG record and with the
+    /// original ID restored. `now` is the current time, in seconds since Jan 1 1970, and
+    /// `position` defines the data covered by the MAC.
+    ///
+    /// Returns the parsed packet, or [SimpleDnsError::TsigFailed] with the reason of the failure.
+    /// A TSIG record holding an error is reported as [TsigError::Rejected] after its MAC is verified
+    pub fn verify_tsig<H: HmacProvider + ?Sized>(
+        message: &'a [u8],
+        key_name: &Name<'_>,
+        algorithm: &Name<'_>,
+        secret: &[u8],
+        now: u64,
+        position: TsigMessage<'_>,
+        hmac: &H,
+    ) -> crate::Result<Self> {
+        let packet = Self::parse(message)?;
+        let rr = packet
+            .tsig
+            .as_ref()
+            .ok_or(SimpleDnsError::TsigFailed(TsigError::Unsigned))?;
+        let RData::TSIG(tsig) = &rr.rdata else {
+            unreachable!()
+        };
+
+        if rr.name.to_canonical() != key_name.to_canonical()
+            || tsig.algorithm.to_canonical() != algorithm.to_canonical()
+        {
+            return Err(SimpleDnsError::TsigFailed(TsigError::BadKey));
+        }
+
+        // Errors like BADSIG and BADKEY are sent without a MAC
+        if tsig.error != 0 && tsig.mac.is_empty() {
+            return Err(SimpleDnsError::TsigFailed(TsigError::Rejected(tsig.error)));
+        }
+
+        let mut unsigned = Self::unsigned_message(message, rr)?;
+        unsigned[..2].copy_from_slice(&tsig.original_id.to_be_bytes());
+
+        let data = tsig::mac_data(&rr.name, tsig, &unsigned, position)?;
+        let expected = hmac
+            .hmac(&tsig.algorithm, secret, &data)
+            .ok_or(SimpleDnsError::TsigFailed(TsigError::BadKey))?;
+        tsig::check_mac(&tsig.mac, &expected).map_err(SimpleDnsError::TsigFailed)?;
+
+        if now.abs_diff(tsig.time_signed) > tsig.fudge as u64 {
+            return Err(SimpleDnsError::TsigFailed(TsigError::BadTime));
+        }
+
+        if tsig.error != 0 {
+            return Err(SimpleDnsError::TsigFailed(TsigError::Rejected(tsig.error)));
+        }
+
+        Ok(packet)
+    }
+
+    /// Returns the bytes of `message` without its last record, the `signature` record, and with
+    /// the additional records count adjusted, as covered by TSIG and SIG(0) signatures.
+    ///
+    /// Signature records are not compressed, the record is expected to take the last
+    /// `signature.len()` bytes of the message
+    pub(crate) fn unsigned_message(
+        message: &[u8],
+        signature: &ResourceRecord<'_>,
+    ) -> crate::Result<Vec<u8>> {
+        let mut unsigned = message
+            .len()
+            .checked_sub(signature.len())
+            .and_then(|len| message.get(..len))
+            .filter(|unsigned| unsigned.len() >= 12)
+            .ok_or(SimpleDnsError::InvalidDnsPacket)?
+            .to_vec();
+
+        let additional_records = u16::from_be_bytes([unsigned[10], unsigned[11]])
+            .checked_sub(1)
+            .ok_or(SimpleDnsError::InvalidDnsPacket)?;
+        unsigned[10..12].copy_from_slice(&additional_records.to_be_bytes());
+
+        Ok(unsigned)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{dns::CLASS, dns::TYPE, lib::ToString, SimpleDnsError};
+    use crate::{
+        dns::dnssec::sha, dns::CLASS, dns::TYPE, lib::ToString, rdata::A, tsig::HMAC_SHA256,
+        SimpleDnsError,
+    };
 
     use super::*;
 
+    const SECRET: &[u8] = b"a very secret shared key";
+    const NOW: u64 = 1_700_000_000;
+
+    struct Hmac;
+
+    impl HmacProvider for Hmac {
+        fn hmac(&self, algorithm: &Name<'_>, secret: &[u8], data: &[u8]) -> Option<Vec<u8>> {
+            (algorithm.to_canonical() == Name::new_unchecked(HMAC_SHA256))
+                .then(|| sha::hmac_sha256(secret, data).to_vec())
+        }
+    }
+
+    fn key_name() -> Name<'static> {
+        Name::new_unchecked("transfer.example.com")
+    }
+
+    fn algorithm() -> Name<'static> {
+        Name::new_unchecked(HMAC_SHA256)
+    }
+
+    fn query_packet() -> Packet<'static> {
+        let mut query = Packet::new_query(0x1234);
+        query.questions.push(Question::new(
+            Name::new_unchecked("example.com"),
+            TYPE::A.into(),
+            CLASS::IN.into(),
+            false,
+        ));
+        query
+    }
+
+    fn verify<'a>(
+        message: &'a [u8],
+        now: u64,
+        position: TsigMessage<'_>,
+    ) -> crate::Result<Packet<'a>> {
+        Packet::verify_tsig(
+            message,
+            &key_name(),
+            &algorithm(),
+            SECRET,
+            now,
+            position,
+            &Hmac,
+        )
+    }
+
+    fn mac_of(packet: &Packet<'_>) -> Vec<u8> {
+        match &packet.tsig().unwrap().rdata {
+            RData::TSIG(tsig) => tsig.mac.to_vec(),
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     fn parse_without_data_should_not_panic() {
         assert!(matches!(
@@ -275,4 +579,339 @@ mod tests {
         assert_eq!("_srv._udp.local", parsed.questions[0].qname.to_string());
         assert_eq!("_srv2._udp.local", parsed.questions[1].qname.to_string());
     }
+
+    #[test]
+    fn sign_and_verify_tsig() {
+        let mut query = query_packet();
+        let signed = query
+            .sign_tsig(
+                key_name(),
+                algorithm(),
+                SECRET,
+                NOW,
+                TsigMessage::Request,
+                &Hmac,
+            )
+            .unwrap();
+        assert_eq!(signed, query.build_bytes_vec_compressed().unwrap());
+
+        let tsig_rr = query.tsig().unwrap();
+        assert_eq!(tsig_rr.ttl, 0);
+        // Checked against an independent HMAC-SHA256 implementation
+        assert_eq!(
+            crate::encoding::hex::encode(&mac_of(&query)),
+            "5BDAEA96D913167F900C152F9098B3767D7455419D2FE32B9E9ABC8AD1F5FC5E"
+        );
+
+        let parsed = verify(&signed, NOW + 100, TsigMessage::Request).unwrap();
+        assert_eq!(parsed.questions[0].qname, query.questions[0].qname);
+        assert!(parsed.additional_records.is_empty());
+        assert_eq!(parsed.tsig(), query.tsig());
+
+        // The ID can be changed by forwarders
+        let mut forwarded = signed.clone();
+        forwarded[..2].copy_from_slice(&[0xab, 0xcd]);
+        assert!(verify(&forwarded, NOW, TsigMessage::Request).is_ok());
+
+        let mut tampered = signed.clone();
+        tampered[2] ^= 0x01;
+        assert_eq!(
+            verify(&tampered, NOW, TsigMessage::Request).unwrap_err(),
+            SimpleDnsError::TsigFailed(TsigError::BadSig)
+        );
+        assert_eq!(
+            verify(&signed, NOW + 301, TsigMessage::Request).unwrap_err(),
+            SimpleDnsError::TsigFailed(TsigError::BadTime)
+        );
+        assert_eq!(
+            Packet::verify_tsig(
+                &signed,
+                &Name::new_unchecked("other.example.com"),
+                &algorithm(),
+                SECRET,
+                NOW,
+                TsigMessage::Request,
+                &Hmac
+            )
+            .unwrap_err(),
+            SimpleDnsError::TsigFailed(TsigError::BadKey)
+        );
+        assert_eq!(
+            Packet::verify_tsig(
+                &signed,
+                &key_name(),
+                &algorithm(),
+                b"another secret",
+                NOW,
+                TsigMessage::Request,
+                &Hmac
+            )
+            .unwrap_err(),
+            SimpleDnsError::TsigFailed(TsigError::BadSig)
+        );
+
+        let unsigned = query_packet().build_bytes_vec().unwrap();
+        assert_eq!(
+            verify(&unsigned, NOW, TsigMessage::Request).unwrap_err(),
+            SimpleDnsError::TsigFailed(TsigError::Unsigned)
+        );
+
+        assert_eq!(
+            query_packet().sign_tsig(
+                key_name(),
+                Name::new_unchecked("hmac-sha1"),
+                SECRET,
+                NOW,
+                TsigMessage::Request,
+                &Hmac
+            ),
+            Err(SimpleDnsError::SigningFailed)
+        );
+    }
+
+    #[test]
+    fn sign_and_verify_tsig_response() {
+        let mut query = query_packet();
+        query
+            .sign_tsig(
+                key_name(),
+                algorithm(),
+                SECRET,
+                NOW,
+                TsigMessage::Request,
+                &Hmac,
+            )
+            .unwrap();
+        let request_mac = mac_of(&query);
+
+        let mut reply = query.into_reply();
+        assert!(reply.tsig().is_none());
+        reply.answers.push(ResourceRecord::new(
+            Name::new_unchecked("example.com"),
+            CLASS::IN,
+            300,
+            RData::A(A {
+                address: 0xc0000201,
+            }),
+        ));
+        let signed = reply
+            .sign_tsig(
+                key_name(),
+                algorithm(),
+                SECRET,
+                NOW,
+                TsigMessage::Response(&request_mac),
+                &Hmac,
+            )
+            .unwrap();
+
+        assert!(verify(&signed, NOW, TsigMessage::Response(&request_mac)).is_ok());
+        assert_eq!(
+            verify(&signed, NOW, TsigMessage::Request).unwrap_err(),
+            SimpleDnsError::TsigFailed(TsigError::BadSig)
+        );
+    }
+
+    #[test]
+    fn sign_and_verify_multi_message_response() {
+        let request_mac = [0xab; 32];
+        let mut previous_mac = request_mac.to_vec();
+        let mut messages = Vec::new();
+
+        for i in 0..3 {
+            let mut message = Packet::new_reply(0x1234);
+            message.answers.push(ResourceRecord::new(
+                Name::new_unchecked("example.com"),
+                CLASS::IN,
+                300,
+                RData::A(A { address: i }),
+            ));
+
+            let position = if i == 0 {
+                TsigMessage::Response(&previous_mac)
+            } else {
+                TsigMessage::Subsequent(&previous_mac)
+            };
+            let signed = message
+                .sign_tsig(key_name(), algorithm(), SECRET, NOW, position, &Hmac)
+                .unwrap();
+            previous_mac = mac_of(&message);
+            messages.push(signed);
+        }
+
+        let mut previous_mac = request_mac.to_vec();
+        for (i, message) in messages.iter().enumerate() {
+            let position = if i == 0 {
+                TsigMessage::Response(&previous_mac)
+            } else {
+                TsigMessage::Subsequent(&previous_mac)
+            };
+            let packet = verify(message, NOW, position).unwrap();
+            previous_mac = mac_of(&packet);
+        }
+
+        // Subsequent messages are chained to the previous MAC
+        let first_mac =
+            mac_of(&verify(&messages[0], NOW, TsigMessage::Response(&request_mac)).unwrap());
+        assert_eq!(
+            verify(&messages[2], NOW, TsigMessage::Subsequent(&first_mac)).unwrap_err(),
+            SimpleDnsError::TsigFailed(TsigError::BadSig)
+        );
+    }
+
+    #[test]
+    fn tsig_must_be_the_last_additional_record() {
+        let mut query = query_packet();
+        query
+            .sign_tsig(
+                key_name(),
+                algorithm(),
+                SECRET,
+                NOW,
+                TsigMessage::Request,
+                &Hmac,
+            )
+            .unwrap();
+
+        let mut reordered = query.clone();
+        reordered
+            .additional_records
+            .push(reordered.tsig.take().unwrap());
+        reordered.additional_records.push(ResourceRecord::new(
+            Name::new_unchecked("example.com"),
+            CLASS::IN,
+            300,
+            RData::A(A {
+                address: 0xc0000201,
+            }),
+        ));
+
+        let data = reordered.build_bytes_vec().unwrap();
+        assert_eq!(
+            Packet::parse(&data).unwrap_err(),
+            SimpleDnsError::InvalidDnsPacket
+        );
+    }
+
+    #[test]
+    fn add_and_read_extended_errors() {
+        use crate::edns::ExtendedErrorCode;
+
+        let mut reply = query_packet().into_reply();
+        *reply.rcode_mut() = RCODE::ServerFailure;
+        assert!(reply.opt().is_none());
+        assert_eq!(reply.extended_errors().count(), 0);
+
+        reply
+            .add_extended_error(ExtendedError::new(ExtendedErrorCode::DnssecBogus))
+            .unwrap();
+        reply
+            .add_extended_error(
+                ExtendedError::new(ExtendedErrorCode::SignatureExpired)
+                    .with_extra_text("example.com/DNSKEY"),
+            )
+            .unwrap();
+        assert_eq!(reply.opt().unwrap().udp_packet_size, 1232);
+
+        let data = reply.build_bytes_vec_compressed().unwrap();
+        let parsed = Packet::parse(&data).unwrap();
+        assert_eq!(parsed.rcode(), RCODE::ServerFailure);
+        let errors: Vec<_> = parsed.extended_errors().collect();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].info_code, ExtendedErrorCode::DnssecBogus);
+        assert_eq!(errors[1].extra_text.as_deref(), Some("example.com/DNSKEY"));
+
+        // Malformed options are skipped
+        let mut parsed = parsed.clone();
+        let opt = parsed.opt_mut().as_mut().unwrap();
+        opt.opt_codes[0].data = Cow::Borrowed(&[0]);
+        assert_eq!(parsed.extended_errors().count(), 1);
+    }
+
+    #[test]
+    fn build_padded_packets() {
+        use crate::edns::{QUERY_BLOCK_LENGTH, RESPONSE_BLOCK_LENGTH};
+
+        let query = query_packet();
+        let padded = query
+            .build_bytes_vec_padded(PaddingPolicy::Recommended)
+            .unwrap();
+        assert_eq!(padded.len(), QUERY_BLOCK_LENGTH as usize);
+        assert!(query.opt().is_none());
+
+        let parsed = Packet::parse(&padded).unwrap();
+        assert_eq!(parsed.questions.len(), 1);
+        let opt = parsed.opt().unwrap();
+        assert_eq!(opt.opt_codes.last().unwrap().code, edns::PADDING);
+        assert!(opt.padding().unwrap() > 0);
+
+        let mut reply = query.clone().into_reply();
+        for i in 0..20 {
+            reply.answers.push(ResourceRecord::new(
+                key_name(),
+                CLASS::IN,
+                300,
+                RData::A(A { address: i }),
+            ));
+        }
+        let padded = reply
+            .build_bytes_vec_padded(PaddingPolicy::Recommended)
+            .unwrap();
+        assert_eq!(padded.len() % RESPONSE_BLOCK_LENGTH as usize, 0);
+        assert_eq!(Packet::parse(&padded).unwrap().answers.len(), 20);
+
+        // An existing padding option is replaced, the length is already a multiple of the block
+        let parsed = Packet::parse(&padded).unwrap();
+        let repadded = parsed
+            .build_bytes_vec_padded(PaddingPolicy::BlockLength(4))
+            .unwrap();
+        assert_eq!(repadded.len() % 4, 0);
+        let opt = Packet::parse(&repadded).unwrap().opt().unwrap().clone();
+        assert_eq!(opt.opt_codes.len(), 1);
+
+        let unpadded = Packet::parse(&repadded).unwrap();
+        let exact = unpadded
+            .build_bytes_vec_padded(PaddingPolicy::BlockLength(1))
+            .unwrap();
+        assert_eq!(
+            Packet::parse(&exact).unwrap().opt().unwrap().padding(),
+            Some(0)
+        );
+
+        let mut signed = query_packet();
+        signed
+            .sign_tsig(
+                key_name(),
+                algorithm(),
+                SECRET,
+                NOW,
+                TsigMessage::Request,
+                &Hmac,
+            )
+            .unwrap();
+        assert_eq!(
+            signed
+                .build_bytes_vec_padded(PaddingPolicy::Recommended)
+                .unwrap_err(),
+            SimpleDnsError::InvalidDnsPacket
+        );
+
+        let mut signed = query_packet();
+        signed.additional_records.push(ResourceRecord::new(
+            Name::new_unchecked(""),
+            CLASS::IN,
+            0,
+            RData::from_str(
+                TYPE::SIG,
+                "TYPE0 15 0 0 20240101000500 20240101000000 3613 example.com. AQID",
+            )
+            .unwrap(),
+        ));
+        assert_eq!(
+            signed
+                .build_bytes_vec_padded(PaddingPolicy::Recommended)
+                .unwrap_err(),
+            SimpleDnsError::InvalidDnsPacket
+        );
+    }
 }
@@ -0,0 +1,247 @@
+use crate::lib::Vec;
+
+/// A single field of a presentation format entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Token<'a> {
+    /// The raw text of this token, escape sequences are not decoded.
+    /// Quoted tokens do not contain the surrounding quotes.
+    pub text: &'a str,
+    /// Indicates that this token was surrounded by quotes
+    pub quoted: bool,
+}
+
+/// A logical line of a master file, parentheses are already resolved
+#[derive(Debug)]
+pub(crate) struct Entry<'a> {
+    /// The line where this entry starts
+    pub line: usize,
+    /// Indicates that the entry starts with a blank, meaning the owner name is omitted
+    pub blank_owner: bool,
+    /// The tokens of this entry
+    pub tokens: Vec<Token<'a>>,
+}
+
+/// Splits text in master file format ([RFC 1035](https://datatracker.ietf.org/doc/html/rfc1035#section-5.1)) into entries.
+///
+/// Comments are removed and entries spanning multiple lines (by using parentheses) are merged
+/// into a single entry.
+pub(crate) struct Lexer<'a> {
+    input: &'a str,
+    position: usize,
+    line: usize,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            position: 0,
+            line: 1,
+        }
+    }
+
+    /// Returns the line of the current position
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.position).copied()
+    }
+
+    fn read_quoted(&mut self) -> crate::Result<Token<'a>> {
+        // skip the opening quote
+        self.position += 1;
+        let start = self.position;
+
+        loop {
+            match self.peek() {
+                Some(b'"') => break,
+                Some(b'\\') => self.position += 2,
+                Some(b'\n') => {
+                    self.line += 1;
+                    self.position += 1;
+                }
+                Some(_) => self.position += 1,
+                None => {
+                    self.position = self.input.len();
+                    return Err(crate::SimpleDnsError::InvalidPresentationFormat);
+                }
+            }
+        }
+
+        let text = &self.input[start..self.position];
+        self.position += 1;
+
+        Ok(Token { text, quoted: true })
+    }
+
+    fn read_word(&mut self) -> crate::Result<Token<'a>> {
+        let start = self.position;
+
+        loop {
+            match self.peek() {
+                None | Some(b' ' | b'\t' | b'\r' | b'\n' | b';' | b'(' | b')') => break,
+                Some(b'\\') => self.position = (self.position + 2).min(self.input.len()),
+                // quoted sections are allowed inside a word, ex: key="some value"
+                Some(b'"') => {
+                    self.read_quoted()?;
+                }
+                Some(_) => self.position += 1,
+            }
+        }
+
+        Ok(Token {
+            text: &self.input[start..self.position],
+            quoted: false,
+        })
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = crate::Result<Entry<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut entry = Entry {
+            line: self.line,
+            blank_owner: false,
+            tokens: Vec::new(),
+        };
+
+        let mut depth = 0usize;
+        let mut line_start = true;
+
+        loop {
+            let c = match self.peek() {
+                Some(c) => c,
+                None if depth > 0 => {
+                    return Some(Err(crate::SimpleDnsError::InvalidPresentationFormat))
+                }
+                None if entry.tokens.is_empty() => return None,
+                None => break,
+            };
+
+            if line_start && entry.tokens.is_empty() && depth == 0 {
+                entry.line = self.line;
+                entry.blank_owner = c == b' ' || c == b'\t';
+            }
+            line_start = false;
+
+            match c {
+                b'\n' => {
+                    self.position += 1;
+                    self.line += 1;
+                    line_start = true;
+
+                    if depth == 0 && !entry.tokens.is_empty() {
+                        break;
+                    }
+                }
+                b' ' | b'\t' | b'\r' => self.position += 1,
+                b';' => {
+                    while !matches!(self.peek(), None | Some(b'\n')) {
+                        self.position += 1;
+                    }
+                }
+                b'(' => {
+                    depth += 1;
+                    self.position += 1;
+                }
+                b')' => {
+                    self.position += 1;
+                    if depth == 0 {
+                        return Some(Err(crate::SimpleDnsError::InvalidPresentationFormat));
+                    }
+                    depth -= 1;
+                }
+                b'"' => match self.read_quoted() {
+                    Ok(token) => entry.tokens.push(token),
+                    Err(err) => return Some(Err(err)),
+                },
+                _ => match self.read_word() {
+                    Ok(token) => entry.tokens.push(token),
+                    Err(err) => return Some(Err(err)),
+                },
+            }
+        }
+
+        Some(Ok(entry))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts<'a>(entry: &Entry<'a>) -> Vec<&'a str> {
+        entry.tokens.iter().map(|t| t.text).collect()
+    }
+
+    #[test]
+    fn split_entries() {
+        let mut lexer = Lexer::new(
+            "; comment line\n\
+            example.com. 300 IN A 127.0.0.1 ; trailing comment\n\
+            \n\
+            \tIN TXT \"some ; text\" \"escaped \\\" quote\"\n\
+            @ SOA ns hostmaster ( 1 ; serial\n\
+                2 3\n\
+                4 5 )\n\
+            svc SVCB 1 . alpn=\"h2,h3\" key667=\"hello world\"",
+        );
+
+        let entry = lexer.next().unwrap().unwrap();
+        assert_eq!(entry.line, 2);
+        assert!(!entry.blank_owner);
+        assert_eq!(
+            texts(&entry),
+            ["example.com.", "300", "IN", "A", "127.0.0.1"]
+        );
+
+        let entry = lexer.next().unwrap().unwrap();
+        assert_eq!(entry.line, 4);
+        assert!(entry.blank_owner);
+        assert_eq!(
+            texts(&entry),
+            ["IN", "TXT", "some ; text", "escaped \\\" quote"]
+        );
+        assert!(entry.tokens[2].quoted);
+
+        let entry = lexer.next().unwrap().unwrap();
+        assert_eq!(entry.line, 5);
+        assert_eq!(
+            texts(&entry),
+            ["@", "SOA", "ns", "hostmaster", "1", "2", "3", "4", "5"]
+        );
+
+        let entry = lexer.next().unwrap().unwrap();
+        assert_eq!(entry.line, 8);
+        assert_eq!(
+            texts(&entry),
+            [
+                "svc",
+                "SVCB",
+                "1",
+                ".",
+                "alpn=\"h2,h3\"",
+                "key667=\"hello world\""
+            ]
+        );
+
+        assert!(lexer.next().is_none());
+    }
+
+    #[test]
+    fn escaped_delimiters() {
+        let mut lexer = Lexer::new("a\\ b\\;c\\(d TXT");
+        let entry = lexer.next().unwrap().unwrap();
+        assert_eq!(texts(&entry), ["a\\ b\\;c\\(d", "TXT"]);
+    }
+
+    #[test]
+    fn unbalanced_input() {
+        assert!(Lexer::new("a A ( 127.0.0.1").next().unwrap().is_err());
+        assert!(Lexer::new("a A 127.0.0.1 )").next().unwrap().is_err());
+        assert!(Lexer::new("a TXT \"unterminated").next().unwrap().is_err());
+    }
+}
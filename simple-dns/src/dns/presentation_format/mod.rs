@@ -0,0 +1,552 @@
+//! Presentation format (text representation) of DNS data, as described in
+//! [RFC 1035](https://datatracker.ietf.org/doc/html/rfc1035#section-5.1)
+
+mod lexer;
+pub(crate) use lexer::{Entry, Lexer, Token};
+
+use crate::{
+    encoding,
+    lib::{fmt, FromStr, Vec},
+    CharacterString, Label, Name, SimpleDnsError, TYPE,
+};
+
+/// Types that can be parsed from and written to their presentation format
+pub(crate) trait PresentationFormat: Sized {
+    /// Parses the value from the presentation format tokens
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self>;
+
+    /// Writes the value in presentation format
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+impl PresentationFormat for Name<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        tokens.next_name()
+    }
+
+    /// Names are always written fully qualified, with special characters escaped
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_labels(f, self.get_labels())?;
+        f.write_str(".")
+    }
+}
+
+/// Writes the labels separated by dots, without the trailing dot, escaping special characters
+pub(crate) fn write_labels(f: &mut fmt::Formatter<'_>, labels: &[Label<'_>]) -> fmt::Result {
+    for (i, label) in labels.iter().enumerate() {
+        if i > 0 {
+            f.write_str(".")?;
+        }
+
+        for byte in label.as_ref() {
+            match byte {
+                b'.' | b';' | b'\\' | b'(' | b')' | b'@' | b'$' | b'"' => {
+                    write!(f, "\\{}", *byte as char)?
+                }
+                0x21..=0x7e => write!(f, "{}", *byte as char)?,
+                _ => write!(f, "\\{byte:03}")?,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl PresentationFormat for CharacterString<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        tokens.next_character_string()
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_quoted(f, &self.data)
+    }
+}
+
+/// Adapter to use the presentation format of a value inside formatting macros
+pub(crate) struct Text<'a, T>(pub &'a T);
+
+impl<T: PresentationFormat> fmt::Display for Text<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.write_text(f)
+    }
+}
+
+/// Reads the fields of a presentation format entry, one token at time
+pub(crate) struct TokenReader<'a> {
+    tokens: &'a [Token<'a>],
+    position: usize,
+    origin: Option<&'a Name<'a>>,
+}
+
+impl<'a> TokenReader<'a> {
+    pub fn new(tokens: &'a [Token<'a>], origin: Option<&'a Name<'a>>) -> Self {
+        Self {
+            tokens,
+            position: 0,
+            origin,
+        }
+    }
+
+    /// Returns the next token without advancing the reader
+    pub fn peek(&self) -> Option<&'a Token<'a>> {
+        self.tokens.get(self.position)
+    }
+
+    /// Returns `true` if there are tokens left to be read
+    pub fn has_remaining(&self) -> bool {
+        self.position < self.tokens.len()
+    }
+
+    /// Returns the next token, fails if there are no tokens left
+    pub fn next_token(&mut self) -> crate::Result<&'a Token<'a>> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .ok_or(SimpleDnsError::InvalidPresentationFormat)?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    /// Returns the text of the next token, fails if there are no tokens left
+    pub fn next_text(&mut self) -> crate::Result<&'a str> {
+        self.next_token().map(|token| token.text)
+    }
+
+    /// Parses the next token using its [`FromStr`] implementation
+    pub fn next_value<T: FromStr>(&mut self) -> crate::Result<T> {
+        self.next_text()?
+            .parse()
+            .map_err(|_| SimpleDnsError::InvalidPresentationFormat)
+    }
+
+    /// Parses the next token as a domain name, relative names are completed with the origin
+    pub fn next_name<'b>(&mut self) -> crate::Result<Name<'b>> {
+        let origin = self.origin;
+        Name::from_presentation(self.next_text()?, origin)
+    }
+
+    /// Parses the next token as a [`CharacterString`]
+    pub fn next_character_string<'b>(&mut self) -> crate::Result<CharacterString<'b>> {
+        CharacterString::from_presentation(self.next_text()?)
+    }
+
+    /// Returns the unescaped bytes of the next token, without any length limit
+    pub fn next_bytes(&mut self) -> crate::Result<Vec<u8>> {
+        unescape(self.next_text()?)
+    }
+
+    /// Parses the next token as a [`TYPE`] mnemonic
+    pub fn next_type(&mut self) -> crate::Result<TYPE> {
+        self.next_text()?.parse()
+    }
+
+    /// Parses the next token as a DNSSEC algorithm, either its number or its mnemonic
+    pub fn next_algorithm(&mut self) -> crate::Result<u8> {
+        let text = self.next_text()?;
+        match text.parse() {
+            Ok(algorithm) => Ok(algorithm),
+            Err(_) => ALGORITHMS
+                .iter()
+                .find(|(mnemonic, _)| mnemonic.eq_ignore_ascii_case(text))
+                .map(|(_, algorithm)| *algorithm)
+                .ok_or(SimpleDnsError::InvalidPresentationFormat),
+        }
+    }
+
+    /// Parses the next token as a timestamp, in the `YYYYMMDDHHmmSS` format or as seconds
+    /// since Jan 1 1970
+    pub fn next_timestamp(&mut self) -> crate::Result<u32> {
+        parse_timestamp(self.next_text()?)
+    }
+
+    /// Parses the next token as a TTL value, units (w, d, h, m, s) are supported
+    pub fn next_ttl(&mut self) -> crate::Result<u32> {
+        parse_ttl(self.next_text()?)
+    }
+
+    /// Concatenates and decodes all the remaining tokens as base64
+    pub fn remaining_base64(&mut self) -> crate::Result<Vec<u8>> {
+        encoding::base64::decode(&self.remaining_concat())
+    }
+
+    /// Concatenates and decodes all the remaining tokens as hexadecimal
+    pub fn remaining_hex(&mut self) -> crate::Result<Vec<u8>> {
+        encoding::hex::decode(&self.remaining_concat())
+    }
+
+    fn remaining_concat(&mut self) -> Vec<u8> {
+        let mut data = Vec::new();
+        while let Ok(text) = self.next_text() {
+            data.extend_from_slice(text.as_bytes());
+        }
+        data
+    }
+
+    /// Ensures that all the tokens were consumed
+    pub fn finish(&self) -> crate::Result<()> {
+        if self.has_remaining() {
+            Err(SimpleDnsError::InvalidPresentationFormat)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Reads the only entry of a presentation format text, fails if there is more than one entry
+pub(crate) fn single_entry(text: &str) -> crate::Result<Entry<'_>> {
+    let mut lexer = Lexer::new(text);
+    let entry = lexer
+        .next()
+        .ok_or(SimpleDnsError::InvalidPresentationFormat)??;
+
+    match lexer.next() {
+        None => Ok(entry),
+        Some(_) => Err(SimpleDnsError::InvalidPresentationFormat),
+    }
+}
+
+/// Parses a value from a single line of presentation format text, used by the tests
+#[cfg(test)]
+pub(crate) fn parse_text<T: PresentationFormat>(text: &str) -> crate::Result<T> {
+    let entry = single_entry(text)?;
+    let mut tokens = TokenReader::new(&entry.tokens, None);
+    let value = T::parse_text(&mut tokens)?;
+    tokens.finish()?;
+    Ok(value)
+}
+
+/// Length of the chunks used when writing long base64 and hexadecimal values, same as BIND
+const CHUNK_LEN: usize = 56;
+
+/// Writes `data` as a quoted string, escaping `"`, `\` and non printable characters
+pub(crate) fn write_quoted(f: &mut fmt::Formatter<'_>, data: &[u8]) -> fmt::Result {
+    f.write_str("\"")?;
+    for byte in data {
+        match byte {
+            b'"' | b'\\' => write!(f, "\\{}", *byte as char)?,
+            0x20..=0x7e => write!(f, "{}", *byte as char)?,
+            _ => write!(f, "\\{byte:03}")?,
+        }
+    }
+    f.write_str("\"")
+}
+
+/// Writes `data` as base64, split in chunks separated by spaces
+pub(crate) fn write_base64(f: &mut fmt::Formatter<'_>, data: &[u8]) -> fmt::Result {
+    write_chunked(f, &encoding::base64::encode(data))
+}
+
+/// Writes `data` as uppercase hexadecimal, split in chunks separated by spaces
+pub(crate) fn write_hex(f: &mut fmt::Formatter<'_>, data: &[u8]) -> fmt::Result {
+    write_chunked(f, &encoding::hex::encode(data))
+}
+
+fn write_chunked(f: &mut fmt::Formatter<'_>, text: &str) -> fmt::Result {
+    // encoded values are always ascii
+    for (i, chunk) in text.as_bytes().chunks(CHUNK_LEN).enumerate() {
+        if i > 0 {
+            f.write_str(" ")?;
+        }
+        f.write_str(core::str::from_utf8(chunk).map_err(|_| fmt::Error)?)?;
+    }
+    Ok(())
+}
+
+/// Writes a type as its mnemonic, or in the generic `TYPEnnn` format when there is none
+pub(crate) fn write_type(f: &mut fmt::Formatter<'_>, rdatatype: TYPE) -> fmt::Result {
+    match rdatatype.mnemonic() {
+        Some(mnemonic) => f.write_str(mnemonic),
+        None => write!(f, "TYPE{}", u16::from(rdatatype)),
+    }
+}
+
+/// Writes data in the generic `\# length hex` format,
+/// [RFC 3597](https://datatracker.ietf.org/doc/html/rfc3597#section-5)
+pub(crate) fn write_generic(f: &mut fmt::Formatter<'_>, data: &[u8]) -> fmt::Result {
+    write!(f, "\\# {}", data.len())?;
+    if !data.is_empty() {
+        f.write_str(" ")?;
+        write_hex(f, data)?;
+    }
+    Ok(())
+}
+
+/// Writes a DNSSEC algorithm as its mnemonic, or as a number when there is none
+pub(crate) fn write_algorithm(f: &mut fmt::Formatter<'_>, algorithm: u8) -> fmt::Result {
+    match ALGORITHMS.iter().find(|(_, value)| *value == algorithm) {
+        Some((mnemonic, _)) => f.write_str(mnemonic),
+        None => write!(f, "{algorithm}"),
+    }
+}
+
+/// Writes a timestamp in the `YYYYMMDDHHmmSS` format
+pub(crate) fn write_timestamp(f: &mut fmt::Formatter<'_>, timestamp: u32) -> fmt::Result {
+    let timestamp = timestamp as i64;
+    let (year, month, day) = civil_from_days(timestamp.div_euclid(86400));
+    let seconds = timestamp.rem_euclid(86400);
+    write!(
+        f,
+        "{year:04}{month:02}{day:02}{:02}{:02}{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// DNSSEC algorithm mnemonics, [RFC 4034](https://datatracker.ietf.org/doc/html/rfc4034#appendix-A.1)
+pub(crate) const ALGORITHMS: &[(&str, u8)] = &[
+    ("RSAMD5", 1),
+    ("DH", 2),
+    ("DSA", 3),
+    ("ECC", 4),
+    ("RSASHA1", 5),
+    ("NSEC3DSA", 6),
+    ("NSEC3RSASHA1", 7),
+    ("RSASHA256", 8),
+    ("RSASHA512", 10),
+    ("ECCGOST", 12),
+    ("ECDSAP256SHA256", 13),
+    ("ECDSAP384SHA384", 14),
+    ("ED25519", 15),
+    ("ED448", 16),
+    ("INDIRECT", 252),
+    ("PRIVATEDNS", 253),
+    ("PRIVATEOID", 254),
+    // Aliases defined by the IANA registry
+    ("DSA-NSEC3-SHA1", 6),
+    ("RSASHA1-NSEC3-SHA1", 7),
+    ("ECC-GOST", 12),
+];
+
+/// Decodes the escape sequences (`\X` and `\DDD`) of a presentation format text
+pub(crate) fn unescape(text: &str) -> crate::Result<Vec<u8>> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'\\' {
+            out.push(bytes[i]);
+            i += 1;
+            continue;
+        }
+
+        match bytes.get(i + 1..i + 4) {
+            Some(digits) if digits.iter().all(u8::is_ascii_digit) => {
+                let value = digits
+                    .iter()
+                    .fold(0u16, |acc, d| acc * 10 + (d - b'0') as u16);
+                if value > u8::MAX as u16 {
+                    return Err(SimpleDnsError::InvalidPresentationFormat);
+                }
+                out.push(value as u8);
+                i += 4;
+            }
+            _ => match bytes.get(i + 1) {
+                Some(c) if !c.is_ascii_digit() => {
+                    out.push(*c);
+                    i += 2;
+                }
+                _ => return Err(SimpleDnsError::InvalidPresentationFormat),
+            },
+        }
+    }
+
+    Ok(out)
+}
+
+/// Parses a TTL value, with optional units, ex: `3600`, `1h`, `1w2d`
+pub(crate) fn parse_ttl(text: &str) -> crate::Result<u32> {
+    if text.is_empty() {
+        return Err(SimpleDnsError::InvalidPresentationFormat);
+    }
+
+    if let Ok(ttl) = text.parse() {
+        return Ok(ttl);
+    }
+
+    let mut total: u32 = 0;
+    let mut current: Option<u32> = None;
+    for c in text.bytes() {
+        let multiplier = match c.to_ascii_lowercase() {
+            c @ b'0'..=b'9' => {
+                current = current
+                    .unwrap_or_default()
+                    .checked_mul(10)
+                    .and_then(|v| v.checked_add((c - b'0') as u32));
+                if current.is_none() {
+                    return Err(SimpleDnsError::InvalidPresentationFormat);
+                }
+                continue;
+            }
+            b'w' => 604800,
+            b'd' => 86400,
+            b'h' => 3600,
+            b'm' => 60,
+            b's' => 1,
+            _ => return Err(SimpleDnsError::InvalidPresentationFormat),
+        };
+
+        total = current
+            .take()
+            .and_then(|value| value.checked_mul(multiplier))
+            .and_then(|value| total.checked_add(value))
+            .ok_or(SimpleDnsError::InvalidPresentationFormat)?;
+    }
+
+    if current.is_some() {
+        return Err(SimpleDnsError::InvalidPresentationFormat);
+    }
+
+    Ok(total)
+}
+
+/// Parses a timestamp in the `YYYYMMDDHHmmSS` format, or as seconds since Jan 1 1970,
+/// [RFC 4034](https://datatracker.ietf.org/doc/html/rfc4034#section-3.2)
+pub(crate) fn parse_timestamp(text: &str) -> crate::Result<u32> {
+    if text.len() != 14 {
+        return text
+            .parse()
+            .map_err(|_| SimpleDnsError::InvalidPresentationFormat);
+    }
+
+    if !text.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(SimpleDnsError::InvalidPresentationFormat);
+    }
+
+    let field = |range: core::ops::Range<usize>| -> crate::Result<i64> {
+        text[range]
+            .parse()
+            .map_err(|_| SimpleDnsError::InvalidPresentationFormat)
+    };
+
+    let (year, month, day) = (field(0..4)?, field(4..6)?, field(6..8)?);
+    let (hour, minute, second) = (field(8..10)?, field(10..12)?, field(12..14)?);
+
+    if !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return Err(SimpleDnsError::InvalidPresentationFormat);
+    }
+
+    let timestamp = days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second;
+
+    // Timestamps use serial number arithmetic, values are stored modulo 2^32
+    Ok(timestamp.rem_euclid(1 << 32) as u32)
+}
+
+/// Number of days since Jan 1 1970 for the given date in the proleptic Gregorian calendar
+pub(crate) fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146097 + day_of_era - 719468
+}
+
+/// Date in the proleptic Gregorian calendar for the given number of days since Jan 1 1970,
+/// inverse of [`days_from_civil`]
+pub(crate) fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400;
+
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+/// Formats a value with its presentation format, used by the tests
+#[cfg(test)]
+pub(crate) fn to_text<T: PresentationFormat>(value: &T) -> crate::lib::String {
+    use crate::lib::ToString;
+    Text(value).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unescape_text() {
+        assert_eq!(unescape("plain").unwrap(), b"plain");
+        assert_eq!(unescape("a\\.b\\\"c\\\\").unwrap(), b"a.b\"c\\");
+        assert_eq!(unescape("foo\\010bar\\255").unwrap(), b"foo\nbar\xff");
+
+        assert!(unescape("\\256").is_err());
+        assert!(unescape("trailing\\").is_err());
+        assert!(unescape("\\12").is_err());
+    }
+
+    #[test]
+    fn parse_ttl_units() {
+        assert_eq!(parse_ttl("3600").unwrap(), 3600);
+        assert_eq!(parse_ttl("1h").unwrap(), 3600);
+        assert_eq!(parse_ttl("1W2d3H4m5S").unwrap(), 788645);
+
+        assert!(parse_ttl("").is_err());
+        assert!(parse_ttl("h").is_err());
+        assert!(parse_ttl("1h30").is_err());
+        assert!(parse_ttl("1y").is_err());
+    }
+
+    #[test]
+    fn parse_timestamps() {
+        assert_eq!(parse_timestamp("19700101000000").unwrap(), 0);
+        assert_eq!(parse_timestamp("20030322173103").unwrap(), 1048354263);
+        assert_eq!(parse_timestamp("20030220173103").unwrap(), 1045762263);
+        assert_eq!(parse_timestamp("1045762263").unwrap(), 1045762263);
+
+        assert!(parse_timestamp("20031322173103").is_err());
+        assert!(parse_timestamp("yesterday").is_err());
+        assert!(parse_timestamp("200é032217310").is_err());
+        assert!(parse_timestamp("+0030322173103").is_err());
+    }
+
+    #[test]
+    fn format_timestamps() {
+        struct Timestamp(u32);
+        impl fmt::Display for Timestamp {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write_timestamp(f, self.0)
+            }
+        }
+
+        for text in [
+            "19700101000000",
+            "20000229235959",
+            "20030322173103",
+            "21060207062815",
+        ] {
+            let timestamp = parse_timestamp(text).unwrap();
+            assert_eq!(Timestamp(timestamp).to_string(), text);
+        }
+    }
+
+    #[test]
+    fn write_names_and_strings() {
+        assert_eq!(to_text(&Name::new_unchecked("")), ".");
+        assert_eq!(to_text(&Name::new_unchecked("example.com")), "example.com.");
+
+        let name = Name::from_presentation("a\\.b\\032c\\\"d\\@.com.", None).unwrap();
+        assert_eq!(to_text(&name), "a\\.b\\032c\\\"d\\@.com.");
+
+        let string = CharacterString::new(b"quote \" slash \\ newline \n").unwrap();
+        assert_eq!(to_text(&string), "\"quote \\\" slash \\\\ newline \\010\"");
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Name, WireFormat, QCLASS, QTYPE};
+use super::{presentation_format::Text, Name, WireFormat, QCLASS, QTYPE};
 use crate::{
     bytes_buffer::BytesBuffer,
     lib::{Seek, TryFrom, Write},
@@ -52,6 +52,13 @@ impl<'a> Question<'a> {
     }
 }
 
+impl crate::lib::fmt::Display for Question<'_> {
+    /// Formats the question in presentation format, ex: `example.com. IN A`
+    fn fmt(&self, f: &mut crate::lib::fmt::Formatter<'_>) -> crate::lib::fmt::Result {
+        write!(f, "{} {} {}", Text(&self.qname), self.qclass, self.qtype)
+    }
+}
+
 impl<'a> WireFormat<'a> for Question<'a> {
     const MINIMUM_LEN: usize = 4;
 
@@ -139,4 +146,18 @@ mod tests {
 
         assert!(parsed.unicast_response);
     }
+
+    #[test]
+    fn display() {
+        let question = Question::new(
+            Name::new_unchecked("_srv._udp.local"),
+            TYPE::SRV.into(),
+            CLASS::IN.into(),
+            true,
+        );
+        assert_eq!(question.to_string(), "_srv._udp.local. IN SRV");
+
+        let question = Question::new(Name::new_unchecked(""), QTYPE::AXFR, QCLASS::ANY, false);
+        assert_eq!(question.to_string(), ". ANY AXFR");
+    }
 }
@@ -1,7 +1,10 @@
 use crate::{
     bytes_buffer::BytesBuffer,
-    dns::WireFormat,
-    lib::{Ipv4Addr, Write},
+    dns::{
+        presentation_format::{PresentationFormat, TokenReader},
+        WireFormat,
+    },
+    lib::{fmt, Ipv4Addr, Write},
 };
 
 use super::RR;
@@ -47,6 +50,16 @@ impl From<Ipv4Addr> for A {
     }
 }
 
+impl PresentationFormat for A {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        tokens.next_value::<Ipv4Addr>().map(Into::into)
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Ipv4Addr::from(self.address))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::lib::Vec;
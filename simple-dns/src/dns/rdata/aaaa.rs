@@ -1,7 +1,10 @@
 use crate::{
     bytes_buffer::BytesBuffer,
-    dns::WireFormat,
-    lib::{Ipv6Addr, Write},
+    dns::{
+        presentation_format::{PresentationFormat, TokenReader},
+        WireFormat,
+    },
+    lib::{fmt, Ipv6Addr, Write},
 };
 
 use super::RR;
@@ -45,6 +48,16 @@ impl From<Ipv6Addr> for AAAA {
     }
 }
 
+impl PresentationFormat for AAAA {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        tokens.next_value::<Ipv6Addr>().map(Into::into)
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Ipv6Addr::from(self.address))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::lib::FromStr;
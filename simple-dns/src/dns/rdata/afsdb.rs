@@ -1,7 +1,10 @@
 use crate::{
     bytes_buffer::BytesBuffer,
-    dns::{Name, WireFormat},
-    lib::{Seek, Write},
+    dns::{
+        presentation_format::{PresentationFormat, Text, TokenReader},
+        Name, WireFormat,
+    },
+    lib::{fmt, Seek, Write},
 };
 
 use super::RR;
@@ -61,6 +64,19 @@ impl<'a> WireFormat<'a> for AFSDB<'a> {
     }
 }
 
+impl PresentationFormat for AFSDB<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            subtype: tokens.next_value()?,
+            hostname: tokens.next_name()?,
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.subtype, Text(&self.hostname))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::lib::{ToString, Vec};
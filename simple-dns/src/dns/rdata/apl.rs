@@ -0,0 +1,370 @@
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{PresentationFormat, TokenReader},
+        WireFormat,
+    },
+    encoding::hex,
+    lib::{fmt, Cow, Ipv4Addr, Ipv6Addr, Vec, Write},
+    SimpleDnsError,
+};
+
+use super::RR;
+
+/// An APL record lists address prefixes, see [rfc3123](https://www.rfc-editor.org/rfc/rfc3123#section-4)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct APL<'a> {
+    /// The address prefix items of this record, the list may be empty
+    pub items: Vec<AplItem<'a>>,
+}
+
+/// An address prefix item of an [`APL`] record
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct AplItem<'a> {
+    /// Negation flag, indicates that the prefix is excluded from the list
+    pub negation: bool,
+    /// The prefix length, in bits
+    pub prefix: u8,
+    /// The address of the prefix
+    pub address: AplAddress<'a>,
+}
+
+/// Address of an [`AplItem`], identified by its address family,
+/// see [IANA Address Family Numbers](https://www.iana.org/assignments/address-family-numbers)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum AplAddress<'a> {
+    /// IPv4 address, address family 1
+    Ipv4(Ipv4Addr),
+    /// IPv6 address, address family 2
+    Ipv6(Ipv6Addr),
+    /// Address of any other family, with the address octets as they are sent
+    Unknown {
+        /// The address family number
+        family: u16,
+        /// The address octets, without trailing zero octets
+        data: Cow<'a, [u8]>,
+    },
+}
+
+impl AplAddress<'_> {
+    /// Returns the address family number
+    pub fn family(&self) -> u16 {
+        match self {
+            AplAddress::Ipv4(_) => 1,
+            AplAddress::Ipv6(_) => 2,
+            AplAddress::Unknown { family, .. } => *family,
+        }
+    }
+
+    /// Calls `f` with the address octets, with the trailing zero octets removed,
+    /// see [RFC 3123](https://www.rfc-editor.org/rfc/rfc3123#section-4.1)
+    fn with_afd_part<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+        fn trim(octets: &[u8]) -> &[u8] {
+            let len = octets.iter().rposition(|o| *o != 0).map_or(0, |i| i + 1);
+            &octets[..len]
+        }
+
+        match self {
+            AplAddress::Ipv4(address) => f(trim(&address.octets())),
+            AplAddress::Ipv6(address) => f(trim(&address.octets())),
+            AplAddress::Unknown { data, .. } => f(trim(data)),
+        }
+    }
+}
+
+impl AplItem<'_> {
+    /// Transforms the inner data into its owned type
+    pub fn into_owned<'b>(self) -> AplItem<'b> {
+        AplItem {
+            negation: self.negation,
+            prefix: self.prefix,
+            address: match self.address {
+                AplAddress::Ipv4(address) => AplAddress::Ipv4(address),
+                AplAddress::Ipv6(address) => AplAddress::Ipv6(address),
+                AplAddress::Unknown { family, data } => AplAddress::Unknown {
+                    family,
+                    data: data.into_owned().into(),
+                },
+            },
+        }
+    }
+
+    fn parse_text(text: &str) -> crate::Result<Self> {
+        let (negation, text) = match text.strip_prefix('!') {
+            Some(text) => (true, text),
+            None => (false, text),
+        };
+        let (family, text) = text
+            .split_once(':')
+            .ok_or(SimpleDnsError::InvalidPresentationFormat)?;
+        let (address, prefix) = text
+            .rsplit_once('/')
+            .ok_or(SimpleDnsError::InvalidPresentationFormat)?;
+
+        let family: u16 = family
+            .parse()
+            .map_err(|_| SimpleDnsError::InvalidPresentationFormat)?;
+        let prefix: u8 = prefix
+            .parse()
+            .map_err(|_| SimpleDnsError::InvalidPresentationFormat)?;
+        let address = match family {
+            1 => AplAddress::Ipv4(
+                address
+                    .parse()
+                    .map_err(|_| SimpleDnsError::InvalidPresentationFormat)?,
+            ),
+            2 => AplAddress::Ipv6(
+                address
+                    .parse()
+                    .map_err(|_| SimpleDnsError::InvalidPresentationFormat)?,
+            ),
+            family => AplAddress::Unknown {
+                family,
+                data: Cow::Owned(hex::decode(address.as_bytes())?),
+            },
+        };
+
+        let item = AplItem {
+            negation,
+            prefix,
+            address,
+        };
+        item.check_prefix()
+            .ok_or(SimpleDnsError::InvalidPresentationFormat)?;
+        Ok(item)
+    }
+
+    /// Returns None if the prefix is longer than the address
+    fn check_prefix(&self) -> Option<()> {
+        let max_prefix = match &self.address {
+            AplAddress::Ipv4(_) => 32,
+            AplAddress::Ipv6(_) => 128,
+            AplAddress::Unknown { .. } => u8::MAX,
+        };
+        (self.prefix <= max_prefix).then_some(())
+    }
+}
+
+impl RR for APL<'_> {
+    const TYPE_CODE: u16 = 42;
+}
+
+impl<'a> WireFormat<'a> for APL<'a> {
+    const MINIMUM_LEN: usize = 0;
+
+    fn parse(data: &mut BytesBuffer<'a>) -> crate::Result<Self>
+    where
+        Self: Sized,
+    {
+        let mut items = Vec::new();
+        while data.has_remaining() {
+            let family = data.get_u16()?;
+            let prefix = data.get_u8()?;
+            let length = data.get_u8()?;
+            let afd_part = data.get_slice((length & 0x7f) as usize)?;
+
+            let address = match family {
+                1 => AplAddress::Ipv4(pad_address::<4>(afd_part)?.into()),
+                2 => AplAddress::Ipv6(pad_address::<16>(afd_part)?.into()),
+                family => AplAddress::Unknown {
+                    family,
+                    data: Cow::Borrowed(afd_part),
+                },
+            };
+
+            let item = AplItem {
+                negation: length & 0x80 != 0,
+                prefix,
+                address,
+            };
+            item.check_prefix()
+                .ok_or(SimpleDnsError::InvalidDnsPacket)?;
+            items.push(item);
+        }
+
+        Ok(Self { items })
+    }
+
+    fn write_to<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        for item in &self.items {
+            item.address.with_afd_part(|afd_part| {
+                // The length shares its octet with the negation flag
+                if afd_part.len() > 0x7f {
+                    return Err(SimpleDnsError::InvalidDnsPacket);
+                }
+                let length = afd_part.len() as u8 | if item.negation { 0x80 } else { 0 };
+
+                out.write_all(&item.address.family().to_be_bytes())?;
+                out.write_all(&[item.prefix, length])?;
+                out.write_all(afd_part)
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.items
+            .iter()
+            .map(|item| item.address.with_afd_part(|afd_part| afd_part.len()) + 4)
+            .sum()
+    }
+}
+
+/// Restores the trailing zero octets removed from an address
+fn pad_address<const N: usize>(afd_part: &[u8]) -> crate::Result<[u8; N]> {
+    let mut address = [0u8; N];
+    address
+        .get_mut(..afd_part.len())
+        .ok_or(SimpleDnsError::InvalidDnsPacket)?
+        .copy_from_slice(afd_part);
+    Ok(address)
+}
+
+impl APL<'_> {
+    /// Transforms the inner data into its owned type
+    pub fn into_owned<'b>(self) -> APL<'b> {
+        APL {
+            items: self
+                .items
+                .into_iter()
+                .map(|item| item.into_owned())
+                .collect(),
+        }
+    }
+}
+
+impl PresentationFormat for APL<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        let mut items = Vec::new();
+        while tokens.has_remaining() {
+            items.push(AplItem::parse_text(tokens.next_text()?)?);
+        }
+
+        Ok(Self { items })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, item) in self.items.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            if item.negation {
+                f.write_str("!")?;
+            }
+
+            write!(f, "{}:", item.address.family())?;
+            match &item.address {
+                AplAddress::Ipv4(address) => write!(f, "{address}")?,
+                AplAddress::Ipv6(address) => write!(f, "{address}")?,
+                AplAddress::Unknown { data, .. } => f.write_str(&hex::encode(data))?,
+            }
+            write!(f, "/{}", item.prefix)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::presentation_format::{parse_text, to_text};
+    use crate::lib::vec;
+
+    #[test]
+    fn parse_and_write_apl() {
+        // RFC 3123 section 5
+        let text = "1:192.168.32.0/21 !1:192.168.38.0/28";
+        let apl: APL = parse_text(text).unwrap();
+        assert_eq!(
+            apl.items,
+            [
+                AplItem {
+                    negation: false,
+                    prefix: 21,
+                    address: AplAddress::Ipv4(Ipv4Addr::new(192, 168, 32, 0)),
+                },
+                AplItem {
+                    negation: true,
+                    prefix: 28,
+                    address: AplAddress::Ipv4(Ipv4Addr::new(192, 168, 38, 0)),
+                },
+            ]
+        );
+        assert_eq!(to_text(&apl), text);
+
+        // Trailing zero octets are not sent
+        let mut data = Vec::new();
+        apl.write_to(&mut data).unwrap();
+        assert_eq!(
+            data,
+            [0, 1, 21, 0x03, 192, 168, 32, 0, 1, 28, 0x83, 192, 168, 38]
+        );
+        assert_eq!(data.len(), apl.len());
+
+        let parsed = APL::parse(&mut data[..].into()).unwrap();
+        assert_eq!(parsed, apl);
+    }
+
+    #[test]
+    fn ipv6_and_unknown_families() {
+        let text = "1:0.0.0.0/0 2:ff00::/8 !2:2001:db8::1/128 3:0A0B/16";
+        let apl: APL = parse_text(text).unwrap();
+        assert_eq!(to_text(&apl), text);
+
+        let mut data = Vec::new();
+        apl.write_to(&mut data).unwrap();
+        assert_eq!(&data[..9], [0, 1, 0, 0, 0, 2, 8, 1, 0xff]);
+        assert_eq!(data.len(), apl.len());
+        assert_eq!(APL::parse(&mut data[..].into()).unwrap(), apl);
+
+        // The address length is limited to 127 octets
+        let mut apl = APL {
+            items: vec![AplItem {
+                negation: true,
+                prefix: 8,
+                address: AplAddress::Unknown {
+                    family: 3,
+                    data: Cow::Owned(vec![1; 0x7f]),
+                },
+            }],
+        };
+        let mut data = Vec::new();
+        apl.write_to(&mut data).unwrap();
+        assert_eq!(data[..4], [0, 3, 8, 0xff]);
+        assert_eq!(APL::parse(&mut data[..].into()).unwrap(), apl);
+
+        apl.items[0].address = AplAddress::Unknown {
+            family: 3,
+            data: Cow::Owned(vec![1; 0x80]),
+        };
+        assert_eq!(
+            apl.write_to(&mut Vec::new()),
+            Err(SimpleDnsError::InvalidDnsPacket)
+        );
+
+        let empty = APL { items: Vec::new() };
+        assert_eq!(empty.len(), 0);
+        assert_eq!(to_text(&empty), "");
+        assert_eq!(APL::parse(&mut [][..].into()).unwrap(), empty);
+    }
+
+    #[test]
+    fn reject_invalid_items() {
+        for text in [
+            "1:192.168.0.0/33",
+            "2:::/129",
+            "1:192.168.0.0",
+            "192.168.0.0/8",
+        ] {
+            assert!(parse_text::<APL>(text).is_err());
+        }
+
+        // Address longer than the address family
+        let data = [0, 1, 8, 5, 10, 0, 0, 0, 1];
+        assert!(APL::parse(&mut data[..].into()).is_err());
+        // Prefix longer than the address family
+        let data = [0, 1, 40, 1, 10];
+        assert!(APL::parse(&mut data[..].into()).is_err());
+    }
+}
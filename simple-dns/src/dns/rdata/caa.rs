@@ -1,7 +1,10 @@
 use crate::{
     bytes_buffer::BytesBuffer,
-    dns::{CharacterString, WireFormat},
-    lib::{Cow, Write},
+    dns::{
+        presentation_format::{write_quoted, PresentationFormat, TokenReader},
+        CharacterString, WireFormat,
+    },
+    lib::{fmt, Cow, Write},
 };
 
 use super::RR;
@@ -62,6 +65,25 @@ impl<'a> WireFormat<'a> for CAA<'a> {
     }
 }
 
+impl PresentationFormat for CAA<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            flag: tokens.next_value()?,
+            tag: tokens.next_character_string()?,
+            value: Cow::Owned(tokens.next_bytes()?),
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ", self.flag)?;
+        for byte in self.tag.data.iter() {
+            write!(f, "{}", *byte as char)?;
+        }
+        f.write_str(" ")?;
+        write_quoted(f, &self.value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,199 @@
+//! Canonical form and ordering of RData, [RFC 4034](https://www.rfc-editor.org/rfc/rfc4034#section-6)
+
+use core::cmp::Ordering;
+
+use super::{
+    RData, RouteThrough, AFSDB, CNAME, DNAME, KX, MB, MD, MF, MG, MINFO, MR, MX, NAPTR, NS, PTR,
+    RP, RRSIG, SIG, SOA, SRV,
+};
+use crate::{
+    dns::WireFormat,
+    lib::{Vec, Write},
+};
+
+impl<'a> RData<'a> {
+    /// Returns a copy of this rdata with the names embedded in the types listed by
+    /// [RFC 4034](https://www.rfc-editor.org/rfc/rfc4034#section-6.2) in canonical form.
+    ///
+    /// NSEC is left out of the list, as updated by [RFC 6840](https://www.rfc-editor.org/rfc/rfc6840#section-5.1)
+    pub(crate) fn to_canonical(&self) -> RData<'a> {
+        match self {
+            RData::NS(NS(ns)) => RData::NS(NS(ns.to_canonical())),
+            RData::MD(MD(md)) => RData::MD(MD(md.to_canonical())),
+            RData::MF(MF(mf)) => RData::MF(MF(mf.to_canonical())),
+            RData::CNAME(CNAME(cname)) => RData::CNAME(CNAME(cname.to_canonical())),
+            RData::MB(MB(mb)) => RData::MB(MB(mb.to_canonical())),
+            RData::MG(MG(mg)) => RData::MG(MG(mg.to_canonical())),
+            RData::MR(MR(mr)) => RData::MR(MR(mr.to_canonical())),
+            RData::PTR(PTR(ptr)) => RData::PTR(PTR(ptr.to_canonical())),
+            RData::SOA(soa) => RData::SOA(SOA {
+                mname: soa.mname.to_canonical(),
+                rname: soa.rname.to_canonical(),
+                ..soa.clone()
+            }),
+            RData::MINFO(minfo) => RData::MINFO(MINFO {
+                rmailbox: minfo.rmailbox.to_canonical(),
+                emailbox: minfo.emailbox.to_canonical(),
+            }),
+            RData::MX(mx) => RData::MX(MX {
+                preference: mx.preference,
+                exchange: mx.exchange.to_canonical(),
+            }),
+            RData::RP(rp) => RData::RP(RP {
+                mbox: rp.mbox.to_canonical(),
+                txt: rp.txt.to_canonical(),
+            }),
+            RData::AFSDB(afsdb) => RData::AFSDB(AFSDB {
+                subtype: afsdb.subtype,
+                hostname: afsdb.hostname.to_canonical(),
+            }),
+            RData::RouteThrough(rt) => RData::RouteThrough(RouteThrough {
+                preference: rt.preference,
+                intermediate_host: rt.intermediate_host.to_canonical(),
+            }),
+            RData::NAPTR(naptr) => RData::NAPTR(NAPTR {
+                replacement: naptr.replacement.to_canonical(),
+                ..naptr.clone()
+            }),
+            RData::KX(kx) => RData::KX(KX {
+                preference: kx.preference,
+                exchanger: kx.exchanger.to_canonical(),
+            }),
+            RData::SRV(srv) => RData::SRV(SRV {
+                target: srv.target.to_canonical(),
+                ..srv.clone()
+            }),
+            RData::DNAME(dname) => RData::DNAME(DNAME {
+                target: dname.target.to_canonical(),
+            }),
+            RData::RRSIG(rrsig) => RData::RRSIG(RRSIG {
+                signer_name: rrsig.signer_name.to_canonical(),
+                ..rrsig.clone()
+            }),
+            RData::SIG(SIG(sig)) => RData::SIG(SIG(RRSIG {
+                signer_name: sig.signer_name.to_canonical(),
+                ..sig.clone()
+            })),
+            rdata => rdata.clone(),
+        }
+    }
+
+    /// Writes this rdata in canonical form, without name compression
+    pub(crate) fn write_canonical_to<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        self.to_canonical().write_to(out)
+    }
+
+    fn canonical_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.len());
+        // Writing to a Vec only fails for invalid rdata, which is still ordered by what was written
+        let _ = self.write_canonical_to(&mut bytes);
+        bytes
+    }
+
+    fn wire_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.len());
+        let _ = self.write_to(&mut bytes);
+        bytes
+    }
+}
+
+/// RData is sorted in canonical order, as defined by [RFC 4034](https://www.rfc-editor.org/rfc/rfc4034#section-6.3):
+/// rdata of the same type is compared by its canonical form, as left-justified unsigned octet
+/// sequences, where the absence of an octet sorts before a zero octet.
+///
+/// Rdata of different types is sorted by type code, and rdata that differ only by the case of the
+/// embedded names is sorted by its original bytes, to remain consistent with [`PartialEq`]
+impl Ord for RData<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        u16::from(self.type_code())
+            .cmp(&u16::from(other.type_code()))
+            .then_with(|| self.canonical_bytes().cmp(&other.canonical_bytes()))
+            .then_with(|| self.wire_bytes().cmp(&other.wire_bytes()))
+    }
+}
+
+impl PartialOrd for RData<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lib::vec, TYPE};
+
+    #[test]
+    fn lowercase_names() {
+        let rdata = RData::from_str(TYPE::MX, "10 Mail.EXAMPLE.com.").unwrap();
+        assert_eq!(
+            rdata.to_canonical(),
+            RData::from_str(TYPE::MX, "10 mail.example.com.").unwrap()
+        );
+
+        let rdata = RData::from_str(TYPE::SOA, "NS.example. Host.Example. 1 2 3 4 5").unwrap();
+        let mut canonical = Vec::new();
+        rdata.write_canonical_to(&mut canonical).unwrap();
+        assert!(canonical.starts_with(b"\x02ns\x07example\x00\x04host\x07example\x00"));
+
+        let rdata = RData::from_str(TYPE::DNAME, "Example.NET.").unwrap();
+        assert_eq!(
+            rdata.to_canonical(),
+            RData::from_str(TYPE::DNAME, "example.net.").unwrap()
+        );
+
+        let rdata = RData::from_str(
+            TYPE::SIG,
+            "A 5 3 86400 20300101000000 20200101000000 2642 Example.COM. AQID",
+        )
+        .unwrap();
+        assert_eq!(
+            rdata.to_canonical(),
+            RData::from_str(
+                TYPE::SIG,
+                "A 5 3 86400 20300101000000 20200101000000 2642 example.com. AQID"
+            )
+            .unwrap()
+        );
+
+        let rdata = RData::from_str(TYPE::NSEC, "HOST.example.com. A").unwrap();
+        assert_eq!(rdata.to_canonical(), rdata);
+
+        let rdata = RData::from_str(TYPE::TXT, "\"Mixed Case\"").unwrap();
+        assert_eq!(rdata.to_canonical(), rdata);
+    }
+
+    #[test]
+    fn canonical_order() {
+        let parse = |rdatatype, text| RData::from_str(rdatatype, text).unwrap();
+
+        let mut rdatas = vec![
+            parse(TYPE::TXT, "\"b\""),
+            parse(TYPE::TXT, "\"ab\""),
+            parse(TYPE::A, "192.0.2.10"),
+            parse(TYPE::TXT, "\"a\""),
+            parse(TYPE::A, "192.0.2.9"),
+            parse(TYPE::TXT, "\"a\" \"\""),
+        ];
+        rdatas.sort();
+        assert_eq!(
+            rdatas,
+            [
+                parse(TYPE::A, "192.0.2.9"),
+                parse(TYPE::A, "192.0.2.10"),
+                // Length octet first, then "a", then the empty string
+                parse(TYPE::TXT, "\"a\""),
+                parse(TYPE::TXT, "\"a\" \"\""),
+                parse(TYPE::TXT, "\"b\""),
+                parse(TYPE::TXT, "\"ab\""),
+            ]
+        );
+
+        // Embedded names are compared in lowercase
+        let upper = parse(TYPE::NS, "B.example.");
+        let lower = parse(TYPE::NS, "a.example.");
+        assert!(lower < upper);
+        assert!(parse(TYPE::NS, "A.example.") < lower);
+        assert_ne!(parse(TYPE::NS, "A.example."), lower);
+    }
+}
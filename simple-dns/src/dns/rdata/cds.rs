@@ -0,0 +1,71 @@
+use crate::lib::Cow;
+
+use super::{CDNSKEY, CDS, DNSKEY, DS};
+
+impl CDS<'_> {
+    /// Creates the CDS record that requests the removal of the DS RRset from the parent zone,
+    /// `0 0 0 00`, see [RFC 8078](https://www.rfc-editor.org/rfc/rfc8078#section-4)
+    pub fn delete() -> CDS<'static> {
+        CDS(DS {
+            key_tag: 0,
+            algorithm: 0,
+            digest_type: 0,
+            digest: Cow::Borrowed(&[0]),
+        })
+    }
+
+    /// Returns true if this record requests the removal of the DS RRset from the parent zone,
+    /// identified by the algorithm 0, see [RFC 8078](https://www.rfc-editor.org/rfc/rfc8078#section-4)
+    pub fn is_delete(&self) -> bool {
+        self.algorithm == 0
+    }
+}
+
+impl CDNSKEY<'_> {
+    /// Creates the CDNSKEY record that requests the removal of the DS RRset from the parent zone,
+    /// `0 3 0 AA==`, see [RFC 8078](https://www.rfc-editor.org/rfc/rfc8078#section-4)
+    pub fn delete() -> CDNSKEY<'static> {
+        CDNSKEY(DNSKEY {
+            flags: 0,
+            protocol: 3,
+            algorithm: 0,
+            public_key: Cow::Borrowed(&[0]),
+        })
+    }
+
+    /// Returns true if this record requests the removal of the DS RRset from the parent zone,
+    /// identified by the algorithm 0, see [RFC 8078](https://www.rfc-editor.org/rfc/rfc8078#section-4)
+    pub fn is_delete(&self) -> bool {
+        self.algorithm == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{lib::ToString, rdata::RData, TYPE};
+
+    #[test]
+    fn delete_records() {
+        let cds = CDS::delete();
+        assert!(cds.is_delete());
+        assert_eq!(cds.to_string(), "0 0 0 00");
+        assert_eq!(
+            RData::from_str(TYPE::CDS, "0 0 0 00").unwrap(),
+            RData::CDS(cds)
+        );
+
+        let cdnskey = CDNSKEY::delete();
+        assert!(cdnskey.is_delete());
+        assert_eq!(cdnskey.to_string(), "0 3 0 AA==");
+        assert_eq!(
+            RData::from_str(TYPE::CDNSKEY, "0 3 0 AA==").unwrap(),
+            RData::CDNSKEY(cdnskey)
+        );
+
+        let RData::CDS(cds) = RData::from_str(TYPE::CDS, "60485 5 1 2BB183AF").unwrap() else {
+            unreachable!()
+        };
+        assert!(!cds.is_delete());
+    }
+}
@@ -1,4 +1,11 @@
-use crate::{bytes_buffer::BytesBuffer, dns::WireFormat, lib::Cow, lib::Write};
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{write_algorithm, write_base64, PresentationFormat, TokenReader},
+        WireFormat,
+    },
+    lib::{fmt, Cow, Write},
+};
 
 use super::RR;
 
@@ -65,6 +72,52 @@ impl CERT<'_> {
     }
 }
 
+/// Certificate type mnemonics, [RFC 4398](https://datatracker.ietf.org/doc/html/rfc4398#section-2.1)
+pub(crate) const CERT_TYPES: &[(&str, u16)] = &[
+    ("PKIX", 1),
+    ("SPKI", 2),
+    ("PGP", 3),
+    ("IPKIX", 4),
+    ("ISPKI", 5),
+    ("IPGP", 6),
+    ("ACPKIX", 7),
+    ("IACPKIX", 8),
+    ("URI", 253),
+    ("OID", 254),
+];
+
+impl PresentationFormat for CERT<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        let type_code = tokens.next_text()?;
+        let type_code = match type_code.parse() {
+            Ok(type_code) => type_code,
+            Err(_) => CERT_TYPES
+                .iter()
+                .find(|(mnemonic, _)| mnemonic.eq_ignore_ascii_case(type_code))
+                .map(|(_, type_code)| *type_code)
+                .ok_or(crate::SimpleDnsError::InvalidPresentationFormat)?,
+        };
+
+        Ok(Self {
+            type_code,
+            key_tag: tokens.next_value()?,
+            algorithm: tokens.next_algorithm()?,
+            certificate: Cow::Owned(tokens.remaining_base64()?),
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match CERT_TYPES.iter().find(|(_, code)| *code == self.type_code) {
+            Some((mnemonic, _)) => write!(f, "{mnemonic} ")?,
+            None => write!(f, "{} ", self.type_code)?,
+        }
+        write!(f, "{} ", self.key_tag)?;
+        write_algorithm(f, self.algorithm)?;
+        f.write_str(" ")?;
+        write_base64(f, &self.certificate)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::lib::{vec, Vec};
@@ -0,0 +1,143 @@
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{PresentationFormat, TokenReader},
+        WireFormat,
+    },
+    lib::{fmt, Vec, Write},
+    TYPE,
+};
+
+use super::{NsecTypeBitMap, RR};
+
+/// A CSYNC record see [rfc7477](https://www.rfc-editor.org/rfc/rfc7477#section-2)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct CSYNC<'a> {
+    /// The SOA serial of the child zone the records to synchronize were taken from
+    pub serial: u32,
+    /// Flags field, see [`CSYNC::IMMEDIATE`] and [`CSYNC::SOA_MINIMUM`]
+    pub flags: u16,
+    /// The type bit maps representing the RR types the parent should synchronize from the child
+    pub type_bit_maps: Vec<NsecTypeBitMap<'a>>,
+}
+
+impl RR for CSYNC<'_> {
+    const TYPE_CODE: u16 = 62;
+}
+
+impl<'a> WireFormat<'a> for CSYNC<'a> {
+    const MINIMUM_LEN: usize = 6;
+
+    fn parse(data: &mut BytesBuffer<'a>) -> crate::Result<Self>
+    where
+        Self: Sized,
+    {
+        let serial = data.get_u32()?;
+        let flags = data.get_u16()?;
+        let type_bit_maps = NsecTypeBitMap::parse_all(data)?;
+
+        Ok(Self {
+            serial,
+            flags,
+            type_bit_maps,
+        })
+    }
+
+    fn write_to<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        out.write_all(&self.serial.to_be_bytes())?;
+        out.write_all(&self.flags.to_be_bytes())?;
+        NsecTypeBitMap::write_all(&self.type_bit_maps, out)
+    }
+
+    fn len(&self) -> usize {
+        Self::MINIMUM_LEN + NsecTypeBitMap::len_all(&self.type_bit_maps)
+    }
+}
+
+impl CSYNC<'_> {
+    /// Immediate flag, the parent may synchronize without waiting for the SOA serial to be
+    /// reached
+    pub const IMMEDIATE: u16 = 0x0001;
+    /// SOA minimum flag, the parent must only synchronize when the SOA serial of the child is
+    /// greater than or equal to the serial field
+    pub const SOA_MINIMUM: u16 = 0x0002;
+
+    /// Transforms the inner data into its owned type
+    pub fn into_owned<'b>(self) -> CSYNC<'b> {
+        CSYNC {
+            serial: self.serial,
+            flags: self.flags,
+            type_bit_maps: NsecTypeBitMap::into_owned_all(self.type_bit_maps),
+        }
+    }
+
+    /// Returns true if the Immediate flag is set
+    pub fn is_immediate(&self) -> bool {
+        self.flags & Self::IMMEDIATE != 0
+    }
+
+    /// Returns true if the SOA minimum flag is set
+    pub fn is_soa_minimum(&self) -> bool {
+        self.flags & Self::SOA_MINIMUM != 0
+    }
+
+    /// Returns true if the type bit maps have the given type
+    pub fn has_type(&self, rdatatype: TYPE) -> bool {
+        NsecTypeBitMap::contains(&self.type_bit_maps, rdatatype.into())
+    }
+}
+
+impl PresentationFormat for CSYNC<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        let serial = tokens.next_value()?;
+        let flags = tokens.next_value()?;
+        let mut types = Vec::new();
+        while tokens.has_remaining() {
+            types.push(tokens.next_type()?.into());
+        }
+
+        Ok(Self {
+            serial,
+            flags,
+            type_bit_maps: NsecTypeBitMap::from_types(types),
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.serial, self.flags)?;
+        for bit_map in &self.type_bit_maps {
+            write!(f, " {bit_map}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::presentation_format::{parse_text, to_text};
+
+    #[test]
+    fn parse_and_write_csync() {
+        // RFC 7477 section 2.2
+        let csync: CSYNC = parse_text("66 3 A NS AAAA").unwrap();
+        assert_eq!(csync.serial, 66);
+        assert!(csync.is_immediate());
+        assert!(csync.is_soa_minimum());
+        assert!(csync.has_type(TYPE::NS));
+        assert!(!csync.has_type(TYPE::MX));
+        assert_eq!(to_text(&csync), "66 3 A NS AAAA");
+
+        let mut data = Vec::new();
+        csync.write_to(&mut data).unwrap();
+        assert_eq!(data, [0, 0, 0, 66, 0, 3, 0, 4, 0x60, 0, 0, 0x08]);
+        assert_eq!(data.len(), csync.len());
+
+        let parsed = CSYNC::parse(&mut data[..].into()).unwrap();
+        assert_eq!(parsed, csync);
+
+        let empty: CSYNC = parse_text("1 0").unwrap();
+        assert!(empty.type_bit_maps.is_empty());
+        assert_eq!(empty.len(), 6);
+    }
+}
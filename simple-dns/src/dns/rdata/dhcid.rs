@@ -1,4 +1,11 @@
-use crate::{bytes_buffer::BytesBuffer, dns::WireFormat, lib::Cow, lib::Write};
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{write_base64, PresentationFormat, TokenReader},
+        WireFormat,
+    },
+    lib::{fmt, Cow, Vec, Write},
+};
 
 use super::RR;
 
@@ -59,6 +66,19 @@ impl DHCID<'_> {
     }
 }
 
+impl PresentationFormat for DHCID<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        let data = tokens.remaining_base64()?;
+        DHCID::parse(&mut BytesBuffer::new(&data)).map(|dhcid| dhcid.into_owned())
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut data = Vec::with_capacity(self.len());
+        self.write_to(&mut data).map_err(|_| fmt::Error)?;
+        write_base64(f, &data)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::lib::Vec;
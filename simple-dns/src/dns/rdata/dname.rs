@@ -0,0 +1,119 @@
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{PresentationFormat, Text, TokenReader},
+        WireFormat,
+    },
+    lib::{fmt, Write},
+    Name,
+};
+
+use super::RR;
+
+/// DNAME provides redirection for a subtree of the domain name tree,
+/// [RFC 6672](https://www.rfc-editor.org/rfc/rfc6672)
+///
+/// The target name is never compressed, as required by
+/// [RFC 6672](https://www.rfc-editor.org/rfc/rfc6672#section-2.5)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct DNAME<'a> {
+    /// The name that replaces the owner of the DNAME record in descendant names
+    pub target: Name<'a>,
+}
+
+impl RR for DNAME<'_> {
+    const TYPE_CODE: u16 = 39;
+}
+
+impl DNAME<'_> {
+    /// Transforms the inner data into its owned type
+    pub fn into_owned<'b>(self) -> DNAME<'b> {
+        DNAME {
+            target: self.target.into_owned(),
+        }
+    }
+
+    /// Returns the name `name` is redirected to by this record, owned by `owner`.
+    /// See [`Name::substitute_dname`]
+    pub fn substitute(&self, owner: &Name<'_>, name: &Name<'_>) -> crate::Result<Name<'static>> {
+        name.substitute_dname(owner, &self.target)
+    }
+}
+
+impl<'a> WireFormat<'a> for DNAME<'a> {
+    const MINIMUM_LEN: usize = 0;
+
+    fn parse(data: &mut BytesBuffer<'a>) -> crate::Result<Self>
+    where
+        Self: Sized,
+    {
+        let target = Name::parse(data)?;
+        Ok(Self { target })
+    }
+
+    fn write_to<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        self.target.write_to(out)
+    }
+
+    fn len(&self) -> usize {
+        self.target.len()
+    }
+}
+
+impl PresentationFormat for DNAME<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            target: tokens.next_name()?,
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Text(&self.target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::presentation_format::{parse_text, to_text};
+    use crate::lib::{Cursor, Vec};
+
+    #[test]
+    fn parse_and_write_dname() {
+        let dname = DNAME {
+            target: Name::new_unchecked("example.net"),
+        };
+
+        let mut data = Vec::new();
+        dname.write_to(&mut data).unwrap();
+        assert_eq!(data, b"\x07example\x03net\x00");
+        assert_eq!(data.len(), dname.len());
+
+        let parsed = DNAME::parse(&mut data[..].into()).unwrap();
+        assert_eq!(parsed, dname);
+
+        assert_eq!(to_text(&dname), "example.net.");
+        assert_eq!(parse_text::<DNAME>("example.net.").unwrap(), dname);
+    }
+
+    #[test]
+    fn dname_should_not_be_compressed() {
+        let dname = DNAME {
+            target: Name::new_unchecked("example.net"),
+        };
+
+        let name = Name::new_unchecked("example.net");
+        let mut names = Default::default();
+        let mut compressed = Cursor::new(Vec::new());
+        name.write_compressed_to(&mut compressed, &mut names)
+            .unwrap();
+        dname
+            .write_compressed_to(&mut compressed, &mut names)
+            .unwrap();
+
+        assert_eq!(
+            compressed.into_inner(),
+            b"\x07example\x03net\x00\x07example\x03net\x00"
+        );
+    }
+}
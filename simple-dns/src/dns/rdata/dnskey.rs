@@ -1,4 +1,11 @@
-use crate::{bytes_buffer::BytesBuffer, dns::WireFormat, lib::Cow, lib::Write};
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{write_base64, PresentationFormat, TokenReader},
+        WireFormat,
+    },
+    lib::{fmt, Cow, Write},
+};
 
 use super::RR;
 
@@ -63,6 +70,50 @@ impl DNSKEY<'_> {
             public_key: Cow::Owned(self.public_key.into_owned()),
         }
     }
+
+    /// Calculates the key tag of this key, used by RRSIG and DS records to identify the key,
+    /// see [RFC 4034](https://www.rfc-editor.org/rfc/rfc4034#appendix-B)
+    pub fn key_tag(&self) -> u16 {
+        // RSA/MD5 keys use the most significant 16 bits of the least significant 24 bits of the modulus
+        if self.algorithm == 1 {
+            let key = &self.public_key;
+            return match key.len() {
+                len if len >= 3 => u16::from_be_bytes([key[len - 3], key[len - 2]]),
+                _ => 0,
+            };
+        }
+
+        let [flags_high, flags_low] = self.flags.to_be_bytes();
+        let header = [flags_high, flags_low, self.protocol, self.algorithm];
+
+        let mut accumulator = header
+            .iter()
+            .chain(self.public_key.iter())
+            .enumerate()
+            .fold(0u32, |accumulator, (i, byte)| match i & 1 {
+                0 => accumulator + ((*byte as u32) << 8),
+                _ => accumulator + *byte as u32,
+            });
+        accumulator += (accumulator >> 16) & 0xffff;
+
+        (accumulator & 0xffff) as u16
+    }
+}
+
+impl PresentationFormat for DNSKEY<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            flags: tokens.next_value()?,
+            protocol: tokens.next_value()?,
+            algorithm: tokens.next_algorithm()?,
+            public_key: Cow::Owned(tokens.remaining_base64()?),
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {} ", self.flags, self.protocol, self.algorithm)?;
+        write_base64(f, &self.public_key)
+    }
 }
 
 #[cfg(test)]
@@ -112,4 +163,40 @@ mod tests {
 
         Ok(())
     }
+
+    fn dnskey(text: &str) -> DNSKEY<'static> {
+        match crate::rdata::RData::from_str(crate::TYPE::DNSKEY, text).unwrap() {
+            crate::rdata::RData::DNSKEY(dnskey) => dnskey,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn calculate_key_tag() {
+        // RFC 4034 section 5.4
+        let key = dnskey(
+            "256 3 5 ( AQOeiiR0GOMYkDshWoSKz9Xz fwJr1AYtsmx3TGkJaNXVbfi/
+            2pHm822aJ5iI9BMzNXxeYCmZDRD99WYwYqUSdjMmmAphXdvxegXd/ M5+X7OrzKBaMbCVdFLU
+            Uh6DhweJBjEVv5f2wwjM9XzcnOf+EPbtG9DMBmADjFDc2w/rljwvFw== )",
+        );
+        assert_eq!(key.key_tag(), 60485);
+
+        // RFC 8080 section 6
+        let key = dnskey("257 3 15 l02Woi0iS8Aa25FQkUd9RMzZHJpBoRQwAQEX1SxZJA4=");
+        assert_eq!(key.key_tag(), 3613);
+
+        let key = dnskey("257 3 15 zPnZ/QwEe7S8C5SPz2OfS5RR40ATk2/rYnE9xHIEijs=");
+        assert_eq!(key.key_tag(), 35217);
+    }
+
+    #[test]
+    fn calculate_rsa_md5_key_tag() {
+        let key = DNSKEY {
+            flags: 256,
+            protocol: 3,
+            algorithm: 1,
+            public_key: b"\x01\x03\xaa\xbb\xcc\x12\x34\x56"[..].into(),
+        };
+        assert_eq!(key.key_tag(), 0x1234);
+    }
 }
@@ -1,6 +1,14 @@
-use crate::{bytes_buffer::BytesBuffer, dns::WireFormat, lib::Cow, lib::Write};
-
-use super::RR;
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{write_hex, PresentationFormat, TokenReader},
+        Name, WireFormat,
+    },
+    lib::{fmt, Cow, Vec, Write},
+    DigestAlgorithm, DigestProvider,
+};
+
+use super::{DNSKEY, RR};
 
 /// A DS record see [rfc4034](https://www.rfc-editor.org/rfc/rfc4034#section-5)
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
@@ -62,6 +70,71 @@ impl DS<'_> {
             digest: Cow::Owned(self.digest.into_owned()),
         }
     }
+
+    /// Creates the DS record that refers to the `dnskey` owned by `owner`, with the digest
+    /// calculated by the `digest_provider`, see [RFC 4034](https://www.rfc-editor.org/rfc/rfc4034#section-5.1.4)
+    ///
+    /// Returns None if the `digest_type` is unknown or not supported by the provider
+    pub fn from_dnskey<D: DigestProvider + ?Sized>(
+        owner: &Name<'_>,
+        dnskey: &DNSKEY<'_>,
+        digest_type: u8,
+        digest_provider: &D,
+    ) -> Option<DS<'static>> {
+        let algorithm = match digest_type {
+            1 => DigestAlgorithm::Sha1,
+            2 => DigestAlgorithm::Sha256,
+            4 => DigestAlgorithm::Sha384,
+            _ => return None,
+        };
+
+        let mut data = Vec::with_capacity(owner.len() + dnskey.len());
+        owner.to_canonical().write_to(&mut data).ok()?;
+        dnskey.write_to(&mut data).ok()?;
+
+        Some(DS {
+            key_tag: dnskey.key_tag(),
+            algorithm: dnskey.algorithm,
+            digest_type,
+            digest: Cow::Owned(digest_provider.digest(algorithm, &data)?),
+        })
+    }
+
+    /// Returns true if this record refers to the `dnskey` owned by `owner`, comparing the key tag,
+    /// the algorithm and the digest calculated by the `digest_provider`.
+    ///
+    /// Returns false if the digest type is unknown or not supported by the provider
+    pub fn matches<D: DigestProvider + ?Sized>(
+        &self,
+        owner: &Name<'_>,
+        dnskey: &DNSKEY<'_>,
+        digest_provider: &D,
+    ) -> bool {
+        self.key_tag == dnskey.key_tag()
+            && self.algorithm == dnskey.algorithm
+            && DS::from_dnskey(owner, dnskey, self.digest_type, digest_provider)
+                .is_some_and(|ds| ds.digest == self.digest)
+    }
+}
+
+impl PresentationFormat for DS<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            key_tag: tokens.next_value()?,
+            algorithm: tokens.next_algorithm()?,
+            digest_type: tokens.next_value()?,
+            digest: Cow::Owned(tokens.remaining_hex()?),
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} ",
+            self.key_tag, self.algorithm, self.digest_type
+        )?;
+        write_hex(f, &self.digest)
+    }
 }
 
 #[cfg(test)]
@@ -111,4 +184,51 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn create_from_dnskey() {
+        use crate::dns::dnssec::sha::Sha;
+        use crate::rdata::RData;
+
+        // RFC 4034 section 5.4 and RFC 4509 section 2.3
+        let owner = Name::new_unchecked("dskey.example.com");
+        let RData::DNSKEY(dnskey) = RData::from_str(
+            crate::TYPE::DNSKEY,
+            "256 3 5 ( AQOeiiR0GOMYkDshWoSKz9Xz fwJr1AYtsmx3TGkJaNXVbfi/
+            2pHm822aJ5iI9BMzNXxeYCmZDRD99WYwYqUSdjMmmAphXdvxegXd/ M5+X7OrzKBaMbCVdFLU
+            Uh6DhweJBjEVv5f2wwjM9XzcnOf+EPbtG9DMBmADjFDc2w/rljwvFw== )",
+        )
+        .unwrap() else {
+            unreachable!()
+        };
+
+        let sha1 = DS::from_dnskey(&owner, &dnskey, 1, &Sha).unwrap();
+        assert_eq!(
+            sha1.to_string(),
+            "60485 5 1 2BB183AF5F22588179A53B0A98631FAD1A292118"
+        );
+        assert!(sha1.matches(&owner, &dnskey, &Sha));
+
+        let sha256 =
+            DS::from_dnskey(&Name::new_unchecked("DSKEY.example.com"), &dnskey, 2, &Sha).unwrap();
+        assert_eq!(
+            sha256.to_string(),
+            "60485 5 2 D4B7D520E7BB5F0F67674A0CCEB1E3E0614B93C4F9E99B8383F6A1E4 469DA50A"
+        );
+        assert!(sha256.matches(&owner, &dnskey, &Sha));
+
+        assert!(DS::from_dnskey(&owner, &dnskey, 4, &Sha).is_none());
+        assert!(!sha1.matches(&Name::new_unchecked("example.com"), &dnskey, &Sha));
+
+        let other_key = DNSKEY {
+            flags: 257,
+            ..dnskey.clone()
+        };
+        assert!(!sha1.matches(&owner, &other_key, &Sha));
+        let unsupported = DS {
+            digest_type: 4,
+            ..sha1
+        };
+        assert!(!unsupported.matches(&owner, &dnskey, &Sha));
+    }
 }
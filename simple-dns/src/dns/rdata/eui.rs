@@ -1,4 +1,11 @@
-use crate::{bytes_buffer::BytesBuffer, dns::WireFormat, lib::Write};
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{PresentationFormat, TokenReader},
+        WireFormat,
+    },
+    lib::{fmt, Write},
+};
 
 use super::RR;
 
@@ -82,6 +89,58 @@ impl From<EUI64> for [u8; 8] {
     }
 }
 
+/// Parses a EUI address in the `xx-xx-xx-xx-xx-xx` format
+fn parse_eui<const N: usize>(text: &str) -> crate::Result<[u8; N]> {
+    let mut address = [0u8; N];
+    let mut groups = text.split('-');
+
+    for octet in address.iter_mut() {
+        let group = groups
+            .next()
+            .filter(|group| group.len() == 2)
+            .ok_or(crate::SimpleDnsError::InvalidPresentationFormat)?;
+        *octet = u8::from_str_radix(group, 16)
+            .map_err(|_| crate::SimpleDnsError::InvalidPresentationFormat)?;
+    }
+
+    if groups.next().is_some() {
+        return Err(crate::SimpleDnsError::InvalidPresentationFormat);
+    }
+
+    Ok(address)
+}
+
+impl PresentationFormat for EUI48 {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        parse_eui(tokens.next_text()?).map(|address| Self { address })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_eui(f, &self.address)
+    }
+}
+
+impl PresentationFormat for EUI64 {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        parse_eui(tokens.next_text()?).map(|address| Self { address })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_eui(f, &self.address)
+    }
+}
+
+/// Writes a EUI address in the `xx-xx-xx-xx-xx-xx` format
+fn write_eui(f: &mut fmt::Formatter<'_>, address: &[u8]) -> fmt::Result {
+    for (i, byte) in address.iter().enumerate() {
+        if i > 0 {
+            f.write_str("-")?;
+        }
+        write!(f, "{byte:02x}")?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use crate::lib::Vec;
@@ -1,7 +1,10 @@
 use crate::{
     bytes_buffer::BytesBuffer,
-    dns::{CharacterString, WireFormat},
-    lib::{Seek, Write},
+    dns::{
+        presentation_format::{PresentationFormat, Text, TokenReader},
+        CharacterString, WireFormat,
+    },
+    lib::{fmt, Seek, Write},
 };
 
 use super::RR;
@@ -63,6 +66,19 @@ impl<'a> WireFormat<'a> for HINFO<'a> {
     }
 }
 
+impl PresentationFormat for HINFO<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            cpu: tokens.next_character_string()?,
+            os: tokens.next_character_string()?,
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", Text(&self.cpu), Text(&self.os))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,191 @@
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{PresentationFormat, TokenReader},
+        WireFormat,
+    },
+    encoding::{base64, hex},
+    lib::{fmt, Cow, Vec, Write},
+    Name, SimpleDnsError,
+};
+
+use super::RR;
+
+/// A HIP record see [rfc8005](https://www.rfc-editor.org/rfc/rfc8005#section-5)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct HIP<'a> {
+    /// The algorithm of the public key
+    pub public_key_algorithm: u8,
+    /// The Host Identity Tag
+    pub hit: Cow<'a, [u8]>,
+    /// The Host Identity public key
+    pub public_key: Cow<'a, [u8]>,
+    /// The rendezvous servers of the host, in order of preference
+    pub rendezvous_servers: Vec<Name<'a>>,
+}
+
+impl RR for HIP<'_> {
+    const TYPE_CODE: u16 = 55;
+}
+
+impl<'a> WireFormat<'a> for HIP<'a> {
+    const MINIMUM_LEN: usize = 4;
+
+    fn parse(data: &mut BytesBuffer<'a>) -> crate::Result<Self>
+    where
+        Self: Sized,
+    {
+        let hit_length = data.get_u8()? as usize;
+        let public_key_algorithm = data.get_u8()?;
+        let public_key_length = data.get_u16()? as usize;
+        let hit = Cow::Borrowed(data.get_slice(hit_length)?);
+        let public_key = Cow::Borrowed(data.get_slice(public_key_length)?);
+
+        let mut rendezvous_servers = Vec::new();
+        while data.has_remaining() {
+            rendezvous_servers.push(Name::parse(data)?);
+        }
+
+        Ok(Self {
+            public_key_algorithm,
+            hit,
+            public_key,
+            rendezvous_servers,
+        })
+    }
+
+    fn write_to<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        let hit_length =
+            u8::try_from(self.hit.len()).map_err(|_| SimpleDnsError::InvalidDnsPacket)?;
+        let public_key_length =
+            u16::try_from(self.public_key.len()).map_err(|_| SimpleDnsError::InvalidDnsPacket)?;
+
+        out.write_all(&[hit_length, self.public_key_algorithm])?;
+        out.write_all(&public_key_length.to_be_bytes())?;
+        out.write_all(&self.hit)?;
+        out.write_all(&self.public_key)?;
+        for server in &self.rendezvous_servers {
+            server.write_to(out)?;
+        }
+
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        Self::MINIMUM_LEN
+            + self.hit.len()
+            + self.public_key.len()
+            + self
+                .rendezvous_servers
+                .iter()
+                .map(|server| server.len())
+                .sum::<usize>()
+    }
+}
+
+impl HIP<'_> {
+    /// Transforms the inner data into its owned type
+    pub fn into_owned<'b>(self) -> HIP<'b> {
+        HIP {
+            public_key_algorithm: self.public_key_algorithm,
+            hit: self.hit.into_owned().into(),
+            public_key: self.public_key.into_owned().into(),
+            rendezvous_servers: self
+                .rendezvous_servers
+                .into_iter()
+                .map(|server| server.into_owned())
+                .collect(),
+        }
+    }
+}
+
+impl PresentationFormat for HIP<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        let public_key_algorithm = tokens.next_value()?;
+        let hit = hex::decode(tokens.next_text()?.as_bytes())?;
+        let public_key = base64::decode(tokens.next_text()?.as_bytes())?;
+
+        let mut rendezvous_servers = Vec::new();
+        while tokens.has_remaining() {
+            rendezvous_servers.push(tokens.next_name()?);
+        }
+
+        Ok(Self {
+            public_key_algorithm,
+            hit: Cow::Owned(hit),
+            public_key: Cow::Owned(public_key),
+            rendezvous_servers,
+        })
+    }
+
+    // The HIT and the public key are not split in chunks, same as BIND
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.public_key_algorithm,
+            hex::encode(&self.hit),
+            base64::encode(&self.public_key)
+        )?;
+        for server in &self.rendezvous_servers {
+            f.write_str(" ")?;
+            server.write_text(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::presentation_format::{parse_text, to_text};
+    use crate::lib::Cursor;
+
+    // RFC 8005 section 6
+    const TEXT: &str = "2 200100107B1A74DF365639CC39F1D578 AwEAAbdxyhNuSutc5EMzxTs9LBPCIkOFH8cIvM4p9+LrV4e19WzK00+CI6zBCQTdtWsuxKbWIy87UOoJTwkUs7lBu+Upr1gsNrut79ryra+bSRGQb1slImA8YVJyuIDsj7kwzG7jnERNqnWxZ48AWkskmdHaVDP4BcelrTI3rMXdXF5D rvs1.example.com. rvs2.example.com.";
+
+    #[test]
+    fn parse_and_write_hip() {
+        let hip: HIP = parse_text(TEXT).unwrap();
+        assert_eq!(hip.public_key_algorithm, 2);
+        assert_eq!(hip.hit.len(), 16);
+        assert_eq!(hip.public_key.len(), 132);
+        assert_eq!(
+            hip.rendezvous_servers,
+            [
+                Name::new_unchecked("rvs1.example.com"),
+                Name::new_unchecked("rvs2.example.com")
+            ]
+        );
+        assert_eq!(to_text(&hip), TEXT);
+
+        let mut data = Vec::new();
+        hip.write_to(&mut data).unwrap();
+        assert_eq!(data[..4], [16, 2, 0, 132]);
+        assert_eq!(data.len(), hip.len());
+
+        let parsed = HIP::parse(&mut data[..].into()).unwrap();
+        assert_eq!(parsed, hip);
+
+        let without_servers = HIP {
+            rendezvous_servers: Vec::new(),
+            ..hip
+        };
+        let text = to_text(&without_servers);
+        assert_eq!(parse_text::<HIP>(&text).unwrap(), without_servers);
+    }
+
+    #[test]
+    fn rendezvous_servers_should_not_be_compressed() {
+        let hip: HIP = parse_text(TEXT).unwrap();
+
+        let mut plain = Vec::new();
+        let mut compressed = Cursor::new(Vec::new());
+        let mut names = Default::default();
+        hip.write_to(&mut plain).unwrap();
+        hip.write_compressed_to(&mut compressed, &mut names)
+            .unwrap();
+
+        assert_eq!(plain, compressed.into_inner());
+    }
+}
@@ -1,9 +1,11 @@
 use super::RR;
 use crate::{
     bytes_buffer::BytesBuffer,
-    dns::WireFormat,
-    lib::{Cow, Ipv4Addr, Ipv6Addr},
-    lib::Write,
+    dns::{
+        presentation_format::{write_base64, PresentationFormat, TokenReader},
+        WireFormat,
+    },
+    lib::{fmt, Cow, Ipv4Addr, Ipv6Addr, Write},
     Name,
 };
 
@@ -45,6 +47,17 @@ impl Gateway<'_> {
     }
 }
 
+impl fmt::Display for Gateway<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Gateway::None => f.write_str("."),
+            Gateway::IPv4(address) => write!(f, "{address}"),
+            Gateway::IPv6(address) => write!(f, "{address}"),
+            Gateway::Domain(name) => name.write_text(f),
+        }
+    }
+}
+
 impl RR for IPSECKEY<'_> {
     const TYPE_CODE: u16 = 45;
 }
@@ -120,6 +133,48 @@ impl IPSECKEY<'_> {
     }
 }
 
+impl PresentationFormat for IPSECKEY<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        let precedence = tokens.next_value()?;
+        let gateway_type: u8 = tokens.next_value()?;
+        let algorithm = tokens.next_value()?;
+        let gateway = match gateway_type {
+            0 if tokens.next_text()? == "." => Gateway::None,
+            1 => Gateway::IPv4(tokens.next_value()?),
+            2 => Gateway::IPv6(tokens.next_value()?),
+            3 => Gateway::Domain(tokens.next_name()?),
+            _ => return Err(crate::SimpleDnsError::InvalidPresentationFormat),
+        };
+
+        Ok(Self {
+            precedence,
+            algorithm,
+            gateway,
+            public_key: Cow::Owned(tokens.remaining_base64()?),
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gateway_type = match self.gateway {
+            Gateway::None => 0,
+            Gateway::IPv4(_) => 1,
+            Gateway::IPv6(_) => 2,
+            Gateway::Domain(_) => 3,
+        };
+        write!(
+            f,
+            "{} {} {} {}",
+            self.precedence, gateway_type, self.algorithm, self.gateway
+        )?;
+
+        if !self.public_key.is_empty() {
+            f.write_str(" ")?;
+            write_base64(f, &self.public_key)?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,7 +1,10 @@
 use crate::{
     bytes_buffer::BytesBuffer,
-    dns::{CharacterString, WireFormat},
-    lib::{Seek, Write},
+    dns::{
+        presentation_format::{PresentationFormat, Text, TokenReader},
+        CharacterString, WireFormat,
+    },
+    lib::{fmt, Seek, Write},
 };
 
 use super::RR;
@@ -60,6 +63,23 @@ impl<'a> WireFormat<'a> for ISDN<'a> {
     }
 }
 
+impl PresentationFormat for ISDN<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        let address = tokens.next_character_string()?;
+        // The subaddress is optional in the presentation format
+        let sa = match tokens.has_remaining() {
+            true => tokens.next_character_string()?,
+            false => CharacterString::new(b"")?,
+        };
+
+        Ok(Self { address, sa })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", Text(&self.address), Text(&self.sa))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,12 @@
-use crate::{bytes_buffer::BytesBuffer, dns::WireFormat, lib::Write, Name};
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{PresentationFormat, Text, TokenReader},
+        WireFormat,
+    },
+    lib::{fmt, Write},
+    Name,
+};
 
 use super::RR;
 
@@ -51,6 +59,19 @@ impl KX<'_> {
     }
 }
 
+impl PresentationFormat for KX<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            preference: tokens.next_value()?,
+            exchanger: tokens.next_name()?,
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.preference, Text(&self.exchanger))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::lib::Vec;
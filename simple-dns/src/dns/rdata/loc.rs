@@ -1,4 +1,12 @@
-use crate::{bytes_buffer::BytesBuffer, dns::WireFormat, lib::Write, SimpleDnsError};
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{PresentationFormat, TokenReader},
+        WireFormat,
+    },
+    lib::{fmt, Write},
+    SimpleDnsError,
+};
 
 use super::RR;
 
@@ -81,11 +89,254 @@ impl<'a> WireFormat<'a> for LOC {
     }
 }
 
+impl PresentationFormat for LOC {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        let latitude = parse_coordinate(tokens, ("N", "S"), 90)?;
+        let longitude = parse_coordinate(tokens, ("E", "W"), 180)?;
+        let altitude = parse_meters(tokens.next_text()?)? + ALTITUDE_REFERENCE;
+
+        let mut precision = |default: u8| -> crate::Result<u8> {
+            match tokens.has_remaining() {
+                true => encode_precision(parse_meters(tokens.next_text()?)?),
+                false => Ok(default),
+            }
+        };
+
+        Ok(Self {
+            version: 0,
+            size: precision(DEFAULT_SIZE)?,
+            horizontal_precision: precision(DEFAULT_HORIZONTAL_PRECISION)?,
+            vertical_precision: precision(DEFAULT_VERTICAL_PRECISION)?,
+            latitude,
+            longitude,
+            altitude: altitude
+                .try_into()
+                .map_err(|_| SimpleDnsError::InvalidPresentationFormat)?,
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_coordinate(f, self.latitude, ('N', 'S'))?;
+        f.write_str(" ")?;
+        write_coordinate(f, self.longitude, ('E', 'W'))?;
+
+        let altitude = self.altitude as u32 as i64 - ALTITUDE_REFERENCE;
+        let sign = if altitude < 0 { "-" } else { "" };
+        write!(
+            f,
+            " {sign}{}.{:02}m",
+            altitude.abs() / 100,
+            altitude.abs() % 100
+        )?;
+
+        for precision in [
+            self.size,
+            self.horizontal_precision,
+            self.vertical_precision,
+        ] {
+            f.write_str(" ")?;
+            write_precision(f, precision)?;
+        }
+        Ok(())
+    }
+}
+
+/// Latitude and longitude values are relative to the equator and prime meridian, 2^31
+const COORDINATE_REFERENCE: i64 = 1 << 31;
+/// Altitude values are relative to 100,000m below the WGS 84 reference spheroid, in centimeters
+const ALTITUDE_REFERENCE: i64 = 10_000_000;
+
+/// Default values for omitted fields, 1m, 10,000m and 10m
+const DEFAULT_SIZE: u8 = 0x12;
+const DEFAULT_HORIZONTAL_PRECISION: u8 = 0x16;
+const DEFAULT_VERTICAL_PRECISION: u8 = 0x13;
+
+/// Parses a coordinate in the `d [m [s]] {hemispheres}` format, no further than `max_degrees`
+/// from the origin, returns the value in thousandths of a second of arc, offset by 2^31
+fn parse_coordinate(
+    tokens: &mut TokenReader<'_>,
+    hemispheres: (&str, &str),
+    max_degrees: i64,
+) -> crate::Result<i32> {
+    let is_hemisphere = |text: &str| {
+        text.eq_ignore_ascii_case(hemispheres.0) || text.eq_ignore_ascii_case(hemispheres.1)
+    };
+
+    let mut parts = [0i64; 3];
+    let mut hemisphere = tokens.next_text()?;
+    for (i, part) in parts.iter_mut().enumerate() {
+        if is_hemisphere(hemisphere) {
+            if i == 0 {
+                return Err(SimpleDnsError::InvalidPresentationFormat);
+            }
+            break;
+        }
+
+        *part = match i {
+            2 => parse_decimal(hemisphere, 3)?,
+            _ => hemisphere
+                .parse::<u32>()
+                .map_err(|_| SimpleDnsError::InvalidPresentationFormat)?
+                .into(),
+        };
+        hemisphere = tokens.next_text()?;
+    }
+
+    if !is_hemisphere(hemisphere) || parts[1] >= 60 || !(0..60_000).contains(&parts[2]) {
+        return Err(SimpleDnsError::InvalidPresentationFormat);
+    }
+
+    let value = parts[0]
+        .checked_mul(3600)
+        .and_then(|value| value.checked_add(parts[1] * 60))
+        .and_then(|value| value.checked_mul(1000))
+        .and_then(|value| value.checked_add(parts[2]))
+        .filter(|value| *value <= max_degrees * 3_600_000)
+        .ok_or(SimpleDnsError::InvalidPresentationFormat)?;
+
+    let value = if hemisphere.eq_ignore_ascii_case(hemispheres.0) {
+        COORDINATE_REFERENCE + value
+    } else {
+        COORDINATE_REFERENCE - value
+    };
+
+    Ok(value as u32 as i32)
+}
+
+/// Parses a distance in meters, with an optional `m` suffix, returns the value in centimeters
+fn parse_meters(text: &str) -> crate::Result<i64> {
+    parse_decimal(text.strip_suffix(['m', 'M']).unwrap_or(text), 2)
+}
+
+/// Parses a decimal number into an integer scaled by 10^`scale`
+fn parse_decimal(text: &str, scale: u32) -> crate::Result<i64> {
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(text) => (true, text),
+        None => (false, text),
+    };
+
+    let (integer, fraction) = text.split_once('.').unwrap_or((text, ""));
+    if integer.is_empty()
+        || fraction.len() > scale as usize
+        || !integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|c| c.is_ascii_digit())
+    {
+        return Err(SimpleDnsError::InvalidPresentationFormat);
+    }
+
+    let integer: i64 = integer
+        .parse()
+        .map_err(|_| SimpleDnsError::InvalidPresentationFormat)?;
+    let fraction = fraction
+        .bytes()
+        .chain(core::iter::repeat(b'0'))
+        .take(scale as usize)
+        .fold(0i64, |acc, c| acc * 10 + (c - b'0') as i64);
+
+    let value = integer
+        .checked_mul(10i64.pow(scale))
+        .and_then(|value| value.checked_add(fraction))
+        .ok_or(SimpleDnsError::InvalidPresentationFormat)?;
+
+    Ok(if negative { -value } else { value })
+}
+
+/// Encodes a value in centimeters as a pair of four-bit unsigned integers (mantissa, exponent)
+fn encode_precision(mut value: i64) -> crate::Result<u8> {
+    if value < 0 {
+        return Err(SimpleDnsError::InvalidPresentationFormat);
+    }
+
+    let mut exponent = 0;
+    while value > 9 {
+        value /= 10;
+        exponent += 1;
+    }
+
+    if exponent > 9 {
+        return Err(SimpleDnsError::InvalidPresentationFormat);
+    }
+
+    Ok(((value as u8) << 4) | exponent)
+}
+
+/// Writes a coordinate in the `d m s.fff {hemisphere}` format
+fn write_coordinate(
+    f: &mut fmt::Formatter<'_>,
+    value: i32,
+    hemispheres: (char, char),
+) -> fmt::Result {
+    let value = value as u32 as i64 - COORDINATE_REFERENCE;
+    let hemisphere = if value > 0 {
+        hemispheres.0
+    } else {
+        hemispheres.1
+    };
+
+    let value = value.abs();
+    write!(
+        f,
+        "{} {} {}.{:03} {hemisphere}",
+        value / 3_600_000,
+        value / 60_000 % 60,
+        value / 1000 % 60,
+        value % 1000
+    )
+}
+
+/// Writes a precision value, encoded as a pair of four-bit unsigned integers, in meters
+fn write_precision(f: &mut fmt::Formatter<'_>, precision: u8) -> fmt::Result {
+    let value = (precision >> 4) as u64 * 10u64.pow((precision & 0xf) as u32);
+    if value >= 100 {
+        write!(f, "{}m", value / 100)
+    } else {
+        write!(f, "0.{value:02}m")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::lib::Vec;
 
+    #[test]
+    fn parse_presentation_format() {
+        use crate::dns::presentation_format::parse_text;
+
+        let loc: LOC = parse_text("42 21 54 N 71 06 18.5 W -24m").unwrap();
+        assert_eq!(loc.latitude as u32, (1 << 31) + 152514000);
+        assert_eq!(loc.longitude as u32, (1 << 31) - 255978500);
+        assert_eq!(loc.altitude, 10_000_000 - 2400);
+        assert_eq!(loc.size, 0x12);
+        assert_eq!(loc.horizontal_precision, 0x16);
+        assert_eq!(loc.vertical_precision, 0x13);
+
+        let loc: LOC = parse_text("52 S 0 30 E 0.01 1m 30m 4.5m").unwrap();
+        assert_eq!(loc.latitude as u32, (1 << 31) - 187200000);
+        assert_eq!(loc.longitude as u32, (1 << 31) + 1800000);
+        assert_eq!(loc.altitude, 10_000_001);
+        assert_eq!(loc.size, 0x12);
+        assert_eq!(loc.horizontal_precision, 0x33);
+        assert_eq!(loc.vertical_precision, 0x42);
+
+        assert!(parse_text::<LOC>("N 71 W 0m").is_err());
+        assert!(parse_text::<LOC>("42 60 N 71 W 0m").is_err());
+        assert!(parse_text::<LOC>("42 N 71 N 0m").is_err());
+        assert!(parse_text::<LOC>("42 N 71 W 0.001m").is_err());
+        assert!(parse_text::<LOC>("91 0 0 N 0 0 0 E 0m").is_err());
+        assert!(parse_text::<LOC>("90 0 0.001 S 0 0 0 E 0m").is_err());
+        assert!(parse_text::<LOC>("0 0 0 N 181 0 0 W 0m").is_err());
+        assert!(parse_text::<LOC>("-5 0 0 N 0 0 0 E 0m").is_err());
+        assert!(parse_text::<LOC>("5 0 -1 N 0 0 0 E 0m").is_err());
+        assert!(parse_text::<LOC>("9223372036854775807 0 0 N 0 0 0 E 0m").is_err());
+
+        let loc: LOC = parse_text("90 S 180 E 0m").unwrap();
+        assert_eq!(loc.latitude as u32, (1 << 31) - 324000000);
+        assert_eq!(loc.longitude as u32, (1 << 31) + 648000000);
+    }
+
     #[test]
     fn parse_and_write_loc() {
         let loc = LOC {
@@ -135,4 +386,18 @@ mod tests {
         assert_eq!(-1930943648, sample_rdata.latitude);
         Ok(())
     }
+
+    #[test]
+    fn write_presentation_format() {
+        use crate::dns::presentation_format::{parse_text, to_text};
+
+        let loc: LOC = parse_text("52 S 0 30 E 0.01 1m 30m 4.5m").unwrap();
+        assert_eq!(to_text(&loc), "52 0 0.000 S 0 30 0.000 E 0.01m 1m 30m 4m");
+
+        let loc: LOC = parse_text("42 21 54 N 71 06 18.5 W -24m 0.5m").unwrap();
+        assert_eq!(
+            to_text(&loc),
+            "42 21 54.000 N 71 6 18.500 W -24.00m 0.50m 10000m 10m"
+        );
+    }
 }
@@ -47,6 +47,19 @@ macro_rules! rr_wrapper {
             }
         }
 
+        impl<'a> PresentationFormat for $t<'a> {
+            fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+                $w::parse_text(tokens).map(|n| $t(n))
+            }
+
+            fn write_text(
+                &self,
+                f: &mut crate::lib::fmt::Formatter<'_>,
+            ) -> crate::lib::fmt::Result {
+                self.0.write_text(f)
+            }
+        }
+
         impl<'a> crate::lib::Deref for $t<'a> {
             type Target = $w<'a>;
 
@@ -172,6 +185,73 @@ macro_rules! rdata_enum {
             }
         }
 
+        impl RData<'_> {
+            /// Parses the RData of the given type from its presentation format
+            ///
+            /// Every type can also be represented with the generic `\# length hex` format,
+            /// [RFC 3597](https://datatracker.ietf.org/doc/html/rfc3597#section-5)
+            pub(crate) fn parse_text(rdatatype: TYPE, tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+                let is_generic = tokens
+                    .peek()
+                    .is_some_and(|token| !token.quoted && token.text == "\\#");
+
+                let rdata = match rdatatype {
+                    // OPT is a pseudo record, it has no presentation format
+                    _ if is_generic && rdatatype != TYPE::OPT => {
+                        let generic = NULL::parse_text(tokens)?;
+                        if generic.get_data().is_empty() {
+                            RData::Empty(rdatatype)
+                        } else {
+                            let mut data = BytesBuffer::new(generic.get_data());
+                            let rdata = parse_rdata(&mut data, rdatatype)?.into_owned();
+                            if data.has_remaining() {
+                                return Err(crate::SimpleDnsError::InvalidPresentationFormat);
+                            }
+                            rdata
+                        }
+                    }
+                    $(
+                        TYPE::$i => RData::$i($i::parse_text(tokens)?),
+                    )+
+
+                    TYPE::NULL => RData::NULL(rdatatype.into(), NULL::parse_text(tokens)?),
+                    TYPE::Unknown(rdatatype) => RData::NULL(rdatatype, NULL::parse_text(tokens)?),
+                };
+
+                tokens.finish()?;
+                Ok(rdata)
+            }
+        }
+
+        impl crate::lib::fmt::Display for RData<'_> {
+            /// Formats the RData in presentation format
+            fn fmt(&self, f: &mut crate::lib::fmt::Formatter<'_>) -> crate::lib::fmt::Result {
+                match self {
+                    $(
+                        RData::$i(data) => data.write_text(f),
+                    )+
+
+                    RData::NULL(_, data) => data.write_text(f),
+                    RData::Empty(_) => crate::dns::presentation_format::write_generic(f, &[]),
+                }
+            }
+        }
+
+        $(
+            impl<$($x)?> crate::lib::fmt::Display for $i$(<$x>)? {
+                /// Formats the RData in presentation format
+                fn fmt(&self, f: &mut crate::lib::fmt::Formatter<'_>) -> crate::lib::fmt::Result {
+                    self.write_text(f)
+                }
+            }
+        )+
+
+        impl crate::lib::fmt::Display for NULL<'_> {
+            fn fmt(&self, f: &mut crate::lib::fmt::Formatter<'_>) -> crate::lib::fmt::Result {
+                self.write_text(f)
+            }
+        }
+
         fn parse_rdata<'a>(data: &mut BytesBuffer<'a>, rdatatype: TYPE) -> crate::Result<RData<'a>> {
             let rdata = match rdatatype {
                 $(
@@ -199,6 +279,55 @@ macro_rules! rdata_enum {
         }
 
 
+        impl TYPE {
+            const ALL: &'static [TYPE] = &[$(TYPE::$i,)+ TYPE::NULL];
+
+            /// Returns the mnemonic used to represent this type in presentation format
+            pub(crate) fn mnemonic(&self) -> Option<&'static str> {
+                #[allow(unreachable_patterns)]
+                match self {
+                    TYPE::RouteThrough => Some("RT"),
+                    TYPE::NSAP_PTR => Some("NSAP-PTR"),
+                    $(
+                        TYPE::$i => Some(stringify!($i)),
+                    )+
+
+                    TYPE::NULL => Some("NULL"),
+                    TYPE::Unknown(_) => None,
+                }
+            }
+        }
+
+        impl crate::lib::fmt::Display for TYPE {
+            /// Formats the type as its mnemonic, or in the generic `TYPEnnn` format
+            fn fmt(&self, f: &mut crate::lib::fmt::Formatter<'_>) -> crate::lib::fmt::Result {
+                crate::dns::presentation_format::write_type(f, *self)
+            }
+        }
+
+        impl crate::lib::FromStr for TYPE {
+            type Err = crate::SimpleDnsError;
+
+            /// Parses a type from its mnemonic or from the generic `TYPEnnn` format,
+            /// [RFC 3597](https://datatracker.ietf.org/doc/html/rfc3597#section-5)
+            fn from_str(s: &str) -> crate::Result<Self> {
+                if let Some(ty) = TYPE::ALL
+                    .iter()
+                    .find(|ty| ty.mnemonic().is_some_and(|mnemonic| mnemonic.eq_ignore_ascii_case(s)))
+                {
+                    return Ok(*ty);
+                }
+
+                match s.get(..4) {
+                    Some(prefix) if prefix.eq_ignore_ascii_case("TYPE") => s[4..]
+                        .parse::<u16>()
+                        .map(TYPE::from)
+                        .map_err(|_| crate::SimpleDnsError::InvalidPresentationFormat),
+                    _ => Err(crate::SimpleDnsError::InvalidPresentationFormat),
+                }
+            }
+        }
+
         impl From<TYPE> for u16 {
             fn from(value: TYPE) -> Self {
                 match value {
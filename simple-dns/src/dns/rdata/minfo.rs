@@ -1,7 +1,10 @@
 use crate::{
     bytes_buffer::BytesBuffer,
-    dns::{Name, WireFormat},
-    lib::{Seek, Write},
+    dns::{
+        presentation_format::{PresentationFormat, Text, TokenReader},
+        Name, WireFormat,
+    },
+    lib::{fmt, Seek, Write},
 };
 
 use super::RR;
@@ -61,6 +64,19 @@ impl<'a> WireFormat<'a> for MINFO<'a> {
     }
 }
 
+impl PresentationFormat for MINFO<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            rmailbox: tokens.next_name()?,
+            emailbox: tokens.next_name()?,
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", Text(&self.rmailbox), Text(&self.emailbox))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,6 +3,7 @@
 
 use crate::{
     bytes_buffer::BytesBuffer,
+    dns::presentation_format::{single_entry, PresentationFormat, TokenReader},
     lib::{Seek, Write},
     CharacterString, Name, WireFormat,
 };
@@ -11,6 +12,8 @@ use core::fmt::Debug;
 
 mod macros;
 
+mod canonical;
+
 mod a;
 pub use a::A;
 
@@ -99,9 +102,70 @@ pub use ds::DS;
 mod nsec;
 pub use nsec::{NsecTypeBitMap, NSEC};
 
+mod nsec3;
+pub use nsec3::NSEC3;
+
+mod nsec3param;
+pub use nsec3param::NSEC3PARAM;
+
+mod tlsa;
+pub use tlsa::{CertificateUsage, MatchingType, Selector, TLSA};
+
+mod sshfp;
+pub use sshfp::{FingerprintType, SshfpAlgorithm, SSHFP};
+
+mod dname;
+pub use dname::DNAME;
+
+mod uri;
+pub use uri::URI;
+
+mod cds;
+
+mod openpgpkey;
+pub use openpgpkey::OPENPGPKEY;
+
+mod hip;
+pub use hip::HIP;
+
+mod csync;
+pub use csync::CSYNC;
+
+mod apl;
+pub use apl::{AplAddress, AplItem, APL};
+
+mod tsig;
+pub use tsig::TSIG;
+
+mod tkey;
+pub use tkey::{TkeyMode, TKEY};
+
+mod sig;
+
 mod dhcid;
 pub use dhcid::DHCID;
 
+impl RData<'_> {
+    /// Parses the RData of the given type from its presentation format, as found in zone files.
+    ///
+    /// Names must be fully qualified, names without a trailing dot are considered relative to the
+    /// root. Any type can also be written using the generic `\# length hex` format,
+    /// [RFC 3597](https://datatracker.ietf.org/doc/html/rfc3597#section-5)
+    ///
+    /// ```
+    /// # use simple_dns::{rdata::RData, TYPE};
+    /// let rdata = RData::from_str(TYPE::MX, "10 mail.example.com.").unwrap();
+    /// assert_eq!(rdata.to_string(), "10 mail.example.com.");
+    ///
+    /// let rdata = RData::from_str(TYPE::A, r"\# 4 7F000001").unwrap();
+    /// assert_eq!(rdata.to_string(), "127.0.0.1");
+    /// ```
+    pub fn from_str(rdatatype: TYPE, text: &str) -> crate::Result<RData<'static>> {
+        let entry = single_entry(text)?;
+        RData::parse_text(rdatatype, &mut TokenReader::new(&entry.tokens, None))
+    }
+}
+
 pub(crate) trait RR {
     const TYPE_CODE: u16;
 }
@@ -162,6 +226,31 @@ macros::rr_wrapper! {
     HTTPS: SVCB = 65
 }
 
+macros::rr_wrapper! {
+    #[doc = "S/MIME certificate association, with the same format as [TLSA], [RFC 8162](https://www.rfc-editor.org/rfc/rfc8162)"]
+    SMIMEA: TLSA = 53
+}
+
+macros::rr_wrapper! {
+    #[doc = "Child copy of a DS record, with the same format as [DS], [RFC 7344](https://www.rfc-editor.org/rfc/rfc7344#section-3.1)"]
+    CDS: DS = 59
+}
+
+macros::rr_wrapper! {
+    #[doc = "Child copy of a DNSKEY record, with the same format as [DNSKEY], [RFC 7344](https://www.rfc-editor.org/rfc/rfc7344#section-3.2)"]
+    CDNSKEY: DNSKEY = 60
+}
+
+macros::rr_wrapper! {
+    #[doc = "Public key used by SIG(0) transaction signatures, with the same format as [DNSKEY], [RFC 2535](https://www.rfc-editor.org/rfc/rfc2535#section-3) and [RFC 2931](https://www.rfc-editor.org/rfc/rfc2931)"]
+    KEY: DNSKEY = 25
+}
+
+macros::rr_wrapper! {
+    #[doc = "Signature, used by SIG(0) transaction signatures, with the same format as [RRSIG], [RFC 2535](https://www.rfc-editor.org/rfc/rfc2535#section-4) and [RFC 2931](https://www.rfc-editor.org/rfc/rfc2931)"]
+    SIG: RRSIG = 24
+}
+
 macros::rdata_enum! {
     A,
     AAAA,
@@ -203,41 +292,27 @@ macros::rdata_enum! {
     DS<'a>,
     NSEC<'a>,
     DHCID<'a>,
+    NSEC3<'a>,
+    NSEC3PARAM<'a>,
+    TLSA<'a>,
+    SMIMEA<'a>,
+    SSHFP<'a>,
+    DNAME<'a>,
+    URI<'a>,
+    CDS<'a>,
+    CDNSKEY<'a>,
+    OPENPGPKEY<'a>,
+    HIP<'a>,
+    CSYNC<'a>,
+    APL<'a>,
+    TSIG<'a>,
+    TKEY<'a>,
+    KEY<'a>,
+    SIG<'a>,
 }
 
 /*
 Not implemented
 
-SIG        - RFC 2535
-KEY        - RFC 2535 & RFC 2930
-
-TKEY       - RFC 2930
-
-APL        - RFC 3123
-
-SSHFP      - RFC 4255
-
 DLV        - RFC 4431
-
-NSEC3PARAM - RFC 5155
-NSEC3      - RFC 5155
-
-TLSA       - RFC 6698
-
-DNAME      - RFC 6672
-
-OPENPGPKEY - RFC 7929
-
-URI        - RFC 7553
-
-CSYNC      - RFC 7477
-
-CDS        - RFC 7344
-CDNSKEY    - RFC 7344
-
-SMIMEA     - RFC 8162
-
-HIP        - RFC 8005
-
-TSIG       - RFC 8945
 */
@@ -1,7 +1,10 @@
 use crate::{
     bytes_buffer::BytesBuffer,
-    dns::{Name, WireFormat},
-    lib::{Seek, Write},
+    dns::{
+        presentation_format::{PresentationFormat, Text, TokenReader},
+        Name, WireFormat,
+    },
+    lib::{fmt, Seek, Write},
 };
 
 use super::RR;
@@ -66,6 +69,19 @@ impl<'a> WireFormat<'a> for MX<'a> {
     }
 }
 
+impl PresentationFormat for MX<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            preference: tokens.next_value()?,
+            exchange: tokens.next_name()?,
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.preference, Text(&self.exchange))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
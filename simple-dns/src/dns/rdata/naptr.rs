@@ -1,7 +1,10 @@
 use crate::{
     bytes_buffer::BytesBuffer,
-    dns::{CharacterString, Name, WireFormat},
-    lib::Write,
+    dns::{
+        presentation_format::{PresentationFormat, Text, TokenReader},
+        CharacterString, Name, WireFormat,
+    },
+    lib::{fmt, Write},
 };
 
 use super::RR;
@@ -86,6 +89,32 @@ impl<'a> WireFormat<'a> for NAPTR<'a> {
     }
 }
 
+impl PresentationFormat for NAPTR<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            order: tokens.next_value()?,
+            preference: tokens.next_value()?,
+            flags: tokens.next_character_string()?,
+            services: tokens.next_character_string()?,
+            regexp: tokens.next_character_string()?,
+            replacement: tokens.next_name()?,
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {}",
+            self.order,
+            self.preference,
+            Text(&self.flags),
+            Text(&self.services),
+            Text(&self.regexp),
+            Text(&self.replacement)
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,11 @@
-use crate::{bytes_buffer::BytesBuffer, dns::WireFormat, lib::Write};
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{PresentationFormat, TokenReader},
+        WireFormat,
+    },
+    lib::{fmt, Vec, Write},
+};
 
 use super::RR;
 
@@ -87,6 +94,36 @@ impl<'a> WireFormat<'a> for NSAP {
     }
 }
 
+impl PresentationFormat for NSAP {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        let text = tokens.next_text()?;
+        let hex: Vec<u8> = text
+            .strip_prefix("0x")
+            .or_else(|| text.strip_prefix("0X"))
+            .ok_or(crate::SimpleDnsError::InvalidPresentationFormat)?
+            .bytes()
+            .filter(|c| *c != b'.')
+            .collect();
+
+        let data = crate::encoding::hex::decode(&hex)?;
+        if data.len() != Self::MINIMUM_LEN {
+            return Err(crate::SimpleDnsError::InvalidPresentationFormat);
+        }
+
+        NSAP::parse(&mut BytesBuffer::new(&data))
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut data = Vec::with_capacity(Self::MINIMUM_LEN);
+        self.write_to(&mut data).map_err(|_| fmt::Error)?;
+        f.write_str("0x")?;
+        for byte in data {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::lib::Vec;
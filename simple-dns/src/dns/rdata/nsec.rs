@@ -1,9 +1,11 @@
 use crate::{
     bytes_buffer::BytesBuffer,
-    dns::WireFormat,
-    lib::{Cow, Vec},
-    lib::Write,
-    Name,
+    dns::{
+        presentation_format::{write_type, PresentationFormat, TokenReader},
+        WireFormat,
+    },
+    lib::{fmt, BTreeMap, Cow, Vec, Write},
+    Name, TYPE,
 };
 
 use super::RR;
@@ -37,6 +39,80 @@ impl<'a> WireFormat<'a> for NSEC<'a> {
         Self: Sized,
     {
         let next_name = Name::parse(data)?;
+        let type_bit_maps = NsecTypeBitMap::parse_all(data)?;
+
+        Ok(Self {
+            next_name,
+            type_bit_maps,
+        })
+    }
+
+    fn write_to<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        self.next_name.write_to(out)?;
+        NsecTypeBitMap::write_all(&self.type_bit_maps, out)
+    }
+
+    fn len(&self) -> usize {
+        self.next_name.len() + NsecTypeBitMap::len_all(&self.type_bit_maps)
+    }
+}
+
+impl NSEC<'_> {
+    /// Transforms the inner data into its owned type
+    pub fn into_owned<'b>(self) -> NSEC<'b> {
+        NSEC {
+            next_name: self.next_name.into_owned(),
+            type_bit_maps: NsecTypeBitMap::into_owned_all(self.type_bit_maps),
+        }
+    }
+
+    /// Returns true if the type bit maps have the given type
+    pub fn has_type(&self, rdatatype: TYPE) -> bool {
+        NsecTypeBitMap::contains(&self.type_bit_maps, rdatatype.into())
+    }
+
+    /// Returns true if this record, owned by `owner`, covers `name`,
+    /// that is, `name` sorts after the owner name and before the next owner name in the
+    /// canonical order, wrapping around at the last record of the zone
+    pub fn covers(&self, owner: &Name<'_>, name: &Name<'_>) -> bool {
+        let owner = owner.to_canonical();
+        let next = self.next_name.to_canonical();
+        let name = name.to_canonical();
+        if owner < next {
+            owner < name && name < next
+        } else {
+            owner < name || name < next
+        }
+    }
+}
+
+impl PresentationFormat for NSEC<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        let next_name = tokens.next_name()?;
+        let mut types = Vec::new();
+        while tokens.has_remaining() {
+            types.push(tokens.next_type()?.into());
+        }
+
+        Ok(Self {
+            next_name,
+            type_bit_maps: NsecTypeBitMap::from_types(types),
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.next_name.write_text(f)?;
+        for bit_map in &self.type_bit_maps {
+            write!(f, " {bit_map}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> NsecTypeBitMap<'a> {
+    /// Parses the type bit maps that take the remaining of the rdata, window blocks must be in
+    /// ascending order and appear only once
+    pub(crate) fn parse_all(data: &mut BytesBuffer<'a>) -> crate::Result<Vec<Self>> {
         let mut type_bit_maps = Vec::new();
         let mut prev_window_block = None;
 
@@ -63,17 +139,13 @@ impl<'a> WireFormat<'a> for NSEC<'a> {
             });
         }
 
-        Ok(Self {
-            next_name,
-            type_bit_maps,
-        })
+        Ok(type_bit_maps)
     }
 
-    fn write_to<T: Write>(&self, out: &mut T) -> crate::Result<()> {
-        self.next_name.write_to(out)?;
-
-        let mut sorted = self.type_bit_maps.clone();
-        sorted.sort_by(|a, b| a.window_block.cmp(&b.window_block));
+    /// Writes the type bit maps sorted by window block
+    pub(crate) fn write_all<T: Write>(type_bit_maps: &[Self], out: &mut T) -> crate::Result<()> {
+        let mut sorted = type_bit_maps.to_vec();
+        sorted.sort_by_key(|a| a.window_block);
 
         for record in sorted.iter() {
             out.write_all(&[record.window_block])?;
@@ -84,26 +156,79 @@ impl<'a> WireFormat<'a> for NSEC<'a> {
         Ok(())
     }
 
-    fn len(&self) -> usize {
-        self.next_name.len()
+    /// Length of the type bit maps in the wire format
+    pub(crate) fn len_all(type_bit_maps: &[Self]) -> usize {
+        type_bit_maps.iter().map(|x| x.bitmap.len() + 2).sum()
     }
-}
 
-impl NSEC<'_> {
-    /// Transforms the inner data into its owned type
-    pub fn into_owned<'b>(self) -> NSEC<'b> {
-        let type_bit_maps = self
-            .type_bit_maps
+    /// Transforms the inner data of the type bit maps into its owned type
+    pub(crate) fn into_owned_all<'b>(type_bit_maps: Vec<Self>) -> Vec<NsecTypeBitMap<'b>> {
+        type_bit_maps
             .into_iter()
             .map(|x| NsecTypeBitMap {
                 window_block: x.window_block,
                 bitmap: x.bitmap.into_owned().into(),
             })
-            .collect();
-        NSEC {
-            next_name: self.next_name.into_owned(),
-            type_bit_maps,
+            .collect()
+    }
+
+    /// Returns true if the type bit maps have the type `ty`
+    pub(crate) fn contains(type_bit_maps: &[Self], ty: u16) -> bool {
+        let position = (ty & 0xff) as usize / 8;
+        type_bit_maps
+            .iter()
+            .filter(|x| x.window_block == (ty >> 8) as u8)
+            .any(|x| {
+                x.bitmap
+                    .get(position)
+                    .is_some_and(|byte| byte & (0x80 >> (ty % 8)) != 0)
+            })
+    }
+
+    /// Builds the type bit maps representing the given types
+    pub(crate) fn from_types<'b>(types: impl IntoIterator<Item = u16>) -> Vec<NsecTypeBitMap<'b>> {
+        let mut windows: BTreeMap<u8, Vec<u8>> = BTreeMap::new();
+        for ty in types {
+            let bitmap = windows.entry((ty >> 8) as u8).or_default();
+            let position = (ty & 0xff) as usize / 8;
+            if bitmap.len() <= position {
+                bitmap.resize(position + 1, 0);
+            }
+            bitmap[position] |= 0x80 >> (ty % 8);
+        }
+
+        windows
+            .into_iter()
+            .map(|(window_block, bitmap)| NsecTypeBitMap {
+                window_block,
+                bitmap: Cow::Owned(bitmap),
+            })
+            .collect()
+    }
+
+    /// Returns the types present in this bit map, in ascending order
+    pub(crate) fn types(&self) -> impl Iterator<Item = u16> + '_ {
+        let window = (self.window_block as u16) << 8;
+        self.bitmap
+            .iter()
+            .enumerate()
+            .flat_map(move |(position, byte)| {
+                (0..8u16)
+                    .filter(move |bit| byte & (0x80 >> bit) != 0)
+                    .map(move |bit| window | (position as u16 * 8 + bit))
+            })
+    }
+}
+
+impl fmt::Display for NsecTypeBitMap<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, ty) in self.types().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write_type(f, ty.into())?;
         }
+        Ok(())
     }
 }
 
@@ -132,6 +257,36 @@ mod tests {
         assert_eq!(nsec.type_bit_maps[0].bitmap, vec![64, 1, 0, 0, 0, 1]);
     }
 
+    #[test]
+    fn has_type_and_covers() {
+        let owner = Name::new_unchecked("ai.example");
+        let nsec = NSEC {
+            next_name: Name::new_unchecked("*.w.Example"),
+            type_bit_maps: NsecTypeBitMap::from_types([1, 13, 28, 46, 47, 1234]),
+        };
+
+        assert!(nsec.has_type(TYPE::AAAA));
+        assert!(nsec.has_type(TYPE::Unknown(1234)));
+        assert!(!nsec.has_type(TYPE::MX));
+        assert!(!nsec.has_type(TYPE::Unknown(1235)));
+
+        assert!(nsec.covers(&owner, &Name::new_unchecked("b.example")));
+        assert!(nsec.covers(&owner, &Name::new_unchecked("a.AI.example")));
+        assert!(!nsec.covers(&owner, &Name::new_unchecked("ai.example")));
+        assert!(!nsec.covers(&owner, &Name::new_unchecked("*.w.example")));
+        assert!(!nsec.covers(&owner, &Name::new_unchecked("x.w.example")));
+
+        // The last record of the zone wraps around to the apex
+        let owner = Name::new_unchecked("xx.example");
+        let nsec = NSEC {
+            next_name: Name::new_unchecked("example"),
+            type_bit_maps: Vec::new(),
+        };
+        assert!(nsec.covers(&owner, &Name::new_unchecked("a.xx.example")));
+        assert!(nsec.covers(&owner, &Name::new_unchecked("z.example")));
+        assert!(!nsec.covers(&owner, &Name::new_unchecked("a.example")));
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn parse_sample() -> Result<(), Box<dyn std::error::Error>> {
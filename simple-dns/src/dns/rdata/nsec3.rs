@@ -0,0 +1,391 @@
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        dnssec::{nsec3_hash, NSEC3_SHA1},
+        presentation_format::{PresentationFormat, TokenReader},
+        WireFormat,
+    },
+    encoding::{base32hex, hex},
+    lib::{fmt, Cow, Vec, Write},
+    DigestProvider, Name, SimpleDnsError, TYPE,
+};
+
+use super::{NsecTypeBitMap, RR};
+
+/// A NSEC3 record see [rfc5155](https://www.rfc-editor.org/rfc/rfc5155#section-3)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct NSEC3<'a> {
+    /// The cryptographic hash algorithm used to construct the hash value
+    pub hash_algorithm: u8,
+    /// Flags field, the least significant bit is the Opt-Out flag
+    pub flags: u8,
+    /// The number of additional times the hash function has been performed
+    pub iterations: u16,
+    /// The salt appended to the original owner name before hashing
+    pub salt: Cow<'a, [u8]>,
+    /// The next hashed owner name in hash order, in binary format
+    pub next_hashed_owner: Cow<'a, [u8]>,
+    /// The type bit maps representing the RR types present at the original owner name
+    pub type_bit_maps: Vec<NsecTypeBitMap<'a>>,
+}
+
+impl RR for NSEC3<'_> {
+    const TYPE_CODE: u16 = 50;
+}
+
+impl<'a> WireFormat<'a> for NSEC3<'a> {
+    const MINIMUM_LEN: usize = 6;
+
+    fn parse(data: &mut BytesBuffer<'a>) -> crate::Result<Self>
+    where
+        Self: Sized,
+    {
+        let hash_algorithm = data.get_u8()?;
+        let flags = data.get_u8()?;
+        let iterations = data.get_u16()?;
+        let salt_length = data.get_u8()? as usize;
+        let salt = Cow::Borrowed(data.get_slice(salt_length)?);
+        let hash_length = data.get_u8()? as usize;
+        let next_hashed_owner = Cow::Borrowed(data.get_slice(hash_length)?);
+        let type_bit_maps = NsecTypeBitMap::parse_all(data)?;
+
+        Ok(Self {
+            hash_algorithm,
+            flags,
+            iterations,
+            salt,
+            next_hashed_owner,
+            type_bit_maps,
+        })
+    }
+
+    fn write_to<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        let salt_length =
+            u8::try_from(self.salt.len()).map_err(|_| SimpleDnsError::InvalidDnsPacket)?;
+        let hash_length = u8::try_from(self.next_hashed_owner.len())
+            .map_err(|_| SimpleDnsError::InvalidDnsPacket)?;
+
+        out.write_all(&[self.hash_algorithm, self.flags])?;
+        out.write_all(&self.iterations.to_be_bytes())?;
+        out.write_all(&[salt_length])?;
+        out.write_all(&self.salt)?;
+        out.write_all(&[hash_length])?;
+        out.write_all(&self.next_hashed_owner)?;
+        NsecTypeBitMap::write_all(&self.type_bit_maps, out)
+    }
+
+    fn len(&self) -> usize {
+        Self::MINIMUM_LEN
+            + self.salt.len()
+            + self.next_hashed_owner.len()
+            + NsecTypeBitMap::len_all(&self.type_bit_maps)
+    }
+}
+
+impl NSEC3<'_> {
+    /// Opt-Out flag, indicates that the NSEC3 record may cover unsigned delegations
+    pub const OPT_OUT: u8 = 0x01;
+
+    /// Transforms the inner data into its owned type
+    pub fn into_owned<'b>(self) -> NSEC3<'b> {
+        NSEC3 {
+            hash_algorithm: self.hash_algorithm,
+            flags: self.flags,
+            iterations: self.iterations,
+            salt: self.salt.into_owned().into(),
+            next_hashed_owner: self.next_hashed_owner.into_owned().into(),
+            type_bit_maps: NsecTypeBitMap::into_owned_all(self.type_bit_maps),
+        }
+    }
+
+    /// Returns true if the Opt-Out flag is set
+    pub fn is_opt_out(&self) -> bool {
+        self.flags & Self::OPT_OUT != 0
+    }
+
+    /// Returns true if the type bit maps have the given type
+    pub fn has_type(&self, rdatatype: TYPE) -> bool {
+        NsecTypeBitMap::contains(&self.type_bit_maps, rdatatype.into())
+    }
+
+    /// Returns the hash of `name` calculated with the algorithm, salt and iterations of this record,
+    /// or None if the hash algorithm is not supported by this crate or by the `digest_provider`
+    pub fn hash_name<D: DigestProvider + ?Sized>(
+        &self,
+        name: &Name<'_>,
+        digest_provider: &D,
+    ) -> Option<Vec<u8>> {
+        if self.hash_algorithm != NSEC3_SHA1 {
+            return None;
+        }
+        nsec3_hash(name, &self.salt, self.iterations, digest_provider)
+    }
+
+    /// Returns true if this record, owned by `owner`, matches `name`,
+    /// that is, the hashed owner name is the hash of `name`
+    ///
+    /// Names outside of the zone of this record never match
+    pub fn matches<D: DigestProvider + ?Sized>(
+        &self,
+        owner: &Name<'_>,
+        name: &Name<'_>,
+        digest_provider: &D,
+    ) -> bool {
+        match self.hashes(owner, name, digest_provider) {
+            Some((owner_hash, hash)) => owner_hash == hash,
+            None => false,
+        }
+    }
+
+    /// Returns true if this record, owned by `owner`, covers `name`,
+    /// that is, the hash of `name` sorts after the hashed owner name and before the next hashed
+    /// owner name, wrapping around at the last record of the chain
+    ///
+    /// Names outside of the zone of this record are never covered
+    pub fn covers<D: DigestProvider + ?Sized>(
+        &self,
+        owner: &Name<'_>,
+        name: &Name<'_>,
+        digest_provider: &D,
+    ) -> bool {
+        let (owner_hash, hash) = match self.hashes(owner, name, digest_provider) {
+            Some(hashes) => hashes,
+            None => return false,
+        };
+
+        let next = &self.next_hashed_owner[..];
+        if owner_hash.as_slice() < next {
+            owner_hash < hash && hash.as_slice() < next
+        } else {
+            owner_hash < hash || hash.as_slice() < next
+        }
+    }
+
+    /// Returns the hashed owner name decoded from the first label of `owner`, and the hash of
+    /// `name`, when `name` belongs to the zone of the record
+    fn hashes<D: DigestProvider + ?Sized>(
+        &self,
+        owner: &Name<'_>,
+        name: &Name<'_>,
+        digest_provider: &D,
+    ) -> Option<(Vec<u8>, Vec<u8>)> {
+        let (hashed_label, zone) = owner.get_labels().split_first()?;
+        let owner_hash = base32hex::decode(hashed_label.as_ref()).ok()?;
+
+        let zone = Name::new_with_labels(zone).to_canonical();
+        let name_canonical = name.to_canonical();
+        if name_canonical != zone && !name_canonical.is_subdomain_of(&zone) {
+            return None;
+        }
+
+        Some((owner_hash, self.hash_name(name, digest_provider)?))
+    }
+}
+
+impl PresentationFormat for NSEC3<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        let hash_algorithm = tokens.next_value()?;
+        let flags = tokens.next_value()?;
+        let iterations = tokens.next_value()?;
+        let salt = parse_salt(tokens.next_text()?)?;
+        let next_hashed_owner = base32hex::decode(tokens.next_text()?.as_bytes())?;
+        if next_hashed_owner.len() > u8::MAX as usize {
+            return Err(crate::SimpleDnsError::InvalidPresentationFormat);
+        }
+
+        let mut types = Vec::new();
+        while tokens.has_remaining() {
+            types.push(tokens.next_type()?.into());
+        }
+
+        Ok(Self {
+            hash_algorithm,
+            flags,
+            iterations,
+            salt: Cow::Owned(salt),
+            next_hashed_owner: Cow::Owned(next_hashed_owner),
+            type_bit_maps: NsecTypeBitMap::from_types(types),
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} ",
+            self.hash_algorithm, self.flags, self.iterations
+        )?;
+        write_salt(f, &self.salt)?;
+        write!(f, " {}", base32hex::encode(&self.next_hashed_owner))?;
+        for bit_map in &self.type_bit_maps {
+            write!(f, " {bit_map}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses the salt field of NSEC3 and NSEC3PARAM records, in hex or `-` when empty
+pub(super) fn parse_salt(text: &str) -> crate::Result<Vec<u8>> {
+    if text == "-" {
+        return Ok(Vec::new());
+    }
+
+    let salt = hex::decode(text.as_bytes())?;
+    if salt.is_empty() || salt.len() > u8::MAX as usize {
+        return Err(crate::SimpleDnsError::InvalidPresentationFormat);
+    }
+
+    Ok(salt)
+}
+
+/// Writes the salt field of NSEC3 and NSEC3PARAM records, in hex or `-` when empty
+pub(super) fn write_salt(f: &mut fmt::Formatter<'_>, salt: &[u8]) -> fmt::Result {
+    if salt.is_empty() {
+        f.write_str("-")
+    } else {
+        f.write_str(&hex::encode(salt))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::dnssec::sha::Sha;
+    use crate::dns::presentation_format::{parse_text, to_text};
+    use crate::lib::vec;
+
+    #[test]
+    fn parse_and_write_nsec3() {
+        let nsec3 = NSEC3 {
+            hash_algorithm: 1,
+            flags: 1,
+            iterations: 12,
+            salt: Cow::Borrowed(&[0xaa, 0xbb, 0xcc, 0xdd]),
+            next_hashed_owner: Cow::Borrowed(&[1, 2, 3, 4, 5]),
+            type_bit_maps: NsecTypeBitMap::from_types([1, 46]),
+        };
+
+        let mut data = Vec::new();
+        nsec3.write_to(&mut data).unwrap();
+        assert_eq!(data.len(), nsec3.len());
+
+        let parsed = NSEC3::parse(&mut data[..].into()).unwrap();
+        assert_eq!(parsed, nsec3);
+        assert!(parsed.is_opt_out());
+        assert!(parsed.has_type(TYPE::A));
+        assert!(parsed.has_type(TYPE::RRSIG));
+        assert!(!parsed.has_type(TYPE::NS));
+
+        let mut data = Vec::new();
+        NSEC3 {
+            salt: Cow::Borrowed(&[]),
+            ..nsec3.clone()
+        }
+        .write_to(&mut data)
+        .unwrap();
+        assert_eq!(&data[..8], &[1, 1, 0, 12, 0, 5, 1, 2]);
+
+        // The lengths of the salt and of the hash are written in a single octet
+        for nsec3 in [
+            NSEC3 {
+                salt: Cow::Owned(vec![0; 256]),
+                ..nsec3.clone()
+            },
+            NSEC3 {
+                next_hashed_owner: Cow::Owned(vec![0; 256]),
+                ..nsec3
+            },
+        ] {
+            assert_eq!(
+                nsec3.write_to(&mut Vec::new()),
+                Err(SimpleDnsError::InvalidDnsPacket)
+            );
+        }
+    }
+
+    #[test]
+    fn presentation_format() {
+        let text = "1 1 12 AABBCCDD 2T7B4G4VSA5SMI47K61MV5BV1A22BOJR A RRSIG";
+        let nsec3: NSEC3 = parse_text(text).unwrap();
+        assert_eq!(*nsec3.salt, [0xaa, 0xbb, 0xcc, 0xdd]);
+        assert_eq!(nsec3.next_hashed_owner.len(), 20);
+        assert_eq!(
+            nsec3.type_bit_maps,
+            vec![NsecTypeBitMap {
+                window_block: 0,
+                bitmap: vec![0x40, 0, 0, 0, 0, 2].into(),
+            }]
+        );
+        assert_eq!(to_text(&nsec3), text);
+
+        let nsec3: NSEC3 = parse_text("1 0 0 - 2t7b4g4vsa5smi47k61mv5bv1a22bojr").unwrap();
+        assert!(nsec3.salt.is_empty());
+        assert!(nsec3.type_bit_maps.is_empty());
+        assert_eq!(to_text(&nsec3), "1 0 0 - 2T7B4G4VSA5SMI47K61MV5BV1A22BOJR");
+
+        assert!(parse_text::<NSEC3>("1 0 0 aabbccd 2t7b4g4vsa5smi47k61mv5bv1a22bojr").is_err());
+        assert!(parse_text::<NSEC3>("1 0 0 - 2t7b4g4vsa5smi47k61mv5bv1a22boj!").is_err());
+    }
+
+    #[test]
+    fn matches_and_covers() {
+        // RFC 5155 appendix A
+        let nsec3 = |next: &str| NSEC3 {
+            hash_algorithm: 1,
+            flags: 1,
+            iterations: 12,
+            salt: Cow::Borrowed(&[0xaa, 0xbb, 0xcc, 0xdd]),
+            next_hashed_owner: Cow::Owned(base32hex::decode(next.as_bytes()).unwrap()),
+            type_bit_maps: Vec::new(),
+        };
+
+        let owner = Name::new_unchecked("0p9mhaveqvm6t7vbl5lop2u3t2rp3tom.example");
+        let apex = nsec3("2t7b4g4vsa5smi47k61mv5bv1a22bojr");
+        assert!(apex.matches(&owner, &Name::new_unchecked("example"), &Sha));
+        assert!(apex.matches(&owner, &Name::new_unchecked("EXAMPLE"), &Sha));
+        assert!(!apex.covers(&owner, &Name::new_unchecked("example"), &Sha));
+
+        // a.example hashes to 35mthgpgcu1qg68fab165klnsnk3dpvl
+        assert!(!apex.covers(&owner, &Name::new_unchecked("a.example"), &Sha));
+        // v.example hashes to 1p6rrvuu176flde9g31cqdtfs7hht95b
+        assert!(apex.covers(&owner, &Name::new_unchecked("v.example"), &Sha));
+        assert!(!apex.matches(&owner, &Name::new_unchecked("v.example"), &Sha));
+
+        // Names outside of the zone
+        assert!(!apex.matches(&owner, &Name::new_unchecked("example.com"), &Sha));
+        assert!(!apex.covers(&owner, &Name::new_unchecked("v.example.com"), &Sha));
+
+        // Providers without SHA-1 never match
+        struct NoSha1;
+        impl DigestProvider for NoSha1 {
+            fn digest(&self, _: crate::DigestAlgorithm, _: &[u8]) -> Option<Vec<u8>> {
+                None
+            }
+        }
+        assert!(apex
+            .hash_name(&Name::new_unchecked("example"), &NoSha1)
+            .is_none());
+        assert!(!apex.matches(&owner, &Name::new_unchecked("example"), &NoSha1));
+        assert!(!apex.covers(&owner, &Name::new_unchecked("v.example"), &NoSha1));
+
+        // The last record of the chain wraps around to the first one
+        let owner = Name::new_unchecked("t644ebqk9bibcna874givr6joj62mlhv.example");
+        let last = nsec3("0p9mhaveqvm6t7vbl5lop2u3t2rp3tom");
+        assert!(last.matches(&owner, &Name::new_unchecked("xx.example"), &Sha));
+        // x.w.example hashes to b4um86eghhds6nea196smvmlo4ors995
+        assert!(!last.covers(&owner, &Name::new_unchecked("x.w.example"), &Sha));
+        // f.example hashes to vh6oa7l8bqliime9rf8o887u6uebasok
+        assert!(last.covers(&owner, &Name::new_unchecked("f.example"), &Sha));
+        // ac.example hashes to 0m1amssj5ipsuv1vf6fllsuqtg1mke08
+        assert!(last.covers(&owner, &Name::new_unchecked("ac.example"), &Sha));
+
+        let unsupported = NSEC3 {
+            hash_algorithm: 2,
+            ..apex
+        };
+        assert!(unsupported
+            .hash_name(&Name::new_unchecked("example"), &Sha)
+            .is_none());
+        let owner = Name::new_unchecked("0p9mhaveqvm6t7vbl5lop2u3t2rp3tom.example");
+        assert!(!unsupported.matches(&owner, &Name::new_unchecked("example"), &Sha));
+    }
+}
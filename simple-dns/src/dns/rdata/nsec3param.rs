@@ -0,0 +1,157 @@
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        dnssec::{nsec3_hash, NSEC3_SHA1},
+        presentation_format::{PresentationFormat, TokenReader},
+        WireFormat,
+    },
+    lib::{fmt, Cow, Vec, Write},
+    DigestProvider, Name, SimpleDnsError,
+};
+
+use super::nsec3::{parse_salt, write_salt};
+use super::RR;
+
+/// A NSEC3PARAM record see [rfc5155](https://www.rfc-editor.org/rfc/rfc5155#section-4)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct NSEC3PARAM<'a> {
+    /// The cryptographic hash algorithm used to construct the hash value
+    pub hash_algorithm: u8,
+    /// Flags field, must be zero
+    pub flags: u8,
+    /// The number of additional times the hash function has been performed
+    pub iterations: u16,
+    /// The salt appended to the original owner name before hashing
+    pub salt: Cow<'a, [u8]>,
+}
+
+impl RR for NSEC3PARAM<'_> {
+    const TYPE_CODE: u16 = 51;
+}
+
+impl<'a> WireFormat<'a> for NSEC3PARAM<'a> {
+    const MINIMUM_LEN: usize = 5;
+
+    fn parse(data: &mut BytesBuffer<'a>) -> crate::Result<Self>
+    where
+        Self: Sized,
+    {
+        let hash_algorithm = data.get_u8()?;
+        let flags = data.get_u8()?;
+        let iterations = data.get_u16()?;
+        let salt_length = data.get_u8()? as usize;
+        let salt = Cow::Borrowed(data.get_slice(salt_length)?);
+
+        Ok(Self {
+            hash_algorithm,
+            flags,
+            iterations,
+            salt,
+        })
+    }
+
+    fn write_to<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        let salt_length =
+            u8::try_from(self.salt.len()).map_err(|_| SimpleDnsError::InvalidDnsPacket)?;
+
+        out.write_all(&[self.hash_algorithm, self.flags])?;
+        out.write_all(&self.iterations.to_be_bytes())?;
+        out.write_all(&[salt_length])?;
+        out.write_all(&self.salt)?;
+
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        Self::MINIMUM_LEN + self.salt.len()
+    }
+}
+
+impl NSEC3PARAM<'_> {
+    /// Transforms the inner data into its owned type
+    pub fn into_owned<'b>(self) -> NSEC3PARAM<'b> {
+        NSEC3PARAM {
+            hash_algorithm: self.hash_algorithm,
+            flags: self.flags,
+            iterations: self.iterations,
+            salt: self.salt.into_owned().into(),
+        }
+    }
+
+    /// Returns the hashed owner name of `name` for the NSEC3 chain described by this record,
+    /// or None if the hash algorithm is not supported by this crate or by the `digest_provider`
+    pub fn hash_name<D: DigestProvider + ?Sized>(
+        &self,
+        name: &Name<'_>,
+        digest_provider: &D,
+    ) -> Option<Vec<u8>> {
+        if self.hash_algorithm != NSEC3_SHA1 {
+            return None;
+        }
+        nsec3_hash(name, &self.salt, self.iterations, digest_provider)
+    }
+}
+
+impl PresentationFormat for NSEC3PARAM<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            hash_algorithm: tokens.next_value()?,
+            flags: tokens.next_value()?,
+            iterations: tokens.next_value()?,
+            salt: Cow::Owned(parse_salt(tokens.next_text()?)?),
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} ",
+            self.hash_algorithm, self.flags, self.iterations
+        )?;
+        write_salt(f, &self.salt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::presentation_format::{parse_text, to_text};
+    use crate::lib::Vec;
+
+    #[test]
+    fn parse_and_write_nsec3param() {
+        let nsec3param = NSEC3PARAM {
+            hash_algorithm: 1,
+            flags: 0,
+            iterations: 12,
+            salt: Cow::Borrowed(&[0xaa, 0xbb, 0xcc, 0xdd]),
+        };
+
+        let mut data = Vec::new();
+        nsec3param.write_to(&mut data).unwrap();
+        assert_eq!(data, [1, 0, 0, 12, 4, 0xaa, 0xbb, 0xcc, 0xdd]);
+        assert_eq!(data.len(), nsec3param.len());
+
+        let parsed = NSEC3PARAM::parse(&mut data[..].into()).unwrap();
+        assert_eq!(parsed, nsec3param);
+
+        assert_eq!(to_text(&nsec3param), "1 0 12 AABBCCDD");
+        assert_eq!(
+            parse_text::<NSEC3PARAM>("1 0 12 aabbccdd").unwrap(),
+            nsec3param
+        );
+
+        let empty_salt = parse_text::<NSEC3PARAM>("1 0 0 -").unwrap();
+        assert!(empty_salt.salt.is_empty());
+        assert_eq!(to_text(&empty_salt), "1 0 0 -");
+
+        let long_salt = NSEC3PARAM {
+            salt: Cow::Owned([0; 256].to_vec()),
+            ..nsec3param
+        };
+        assert_eq!(
+            long_salt.write_to(&mut Vec::new()),
+            Err(SimpleDnsError::InvalidDnsPacket)
+        );
+    }
+}
@@ -1,8 +1,10 @@
 use crate::{
     bytes_buffer::BytesBuffer,
-    dns::{WireFormat, MAX_NULL_LENGTH},
-    lib::Cow,
-    lib::Write,
+    dns::{
+        presentation_format::{write_generic, PresentationFormat, TokenReader},
+        WireFormat, MAX_NULL_LENGTH,
+    },
+    lib::{fmt, Cow, Write},
 };
 
 use super::RR;
@@ -62,3 +64,28 @@ impl<'a> WireFormat<'a> for NULL<'a> {
         self.length as usize
     }
 }
+
+impl PresentationFormat for NULL<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        // NULL and unknown types can only be represented using the generic format,
+        // [RFC 3597](https://datatracker.ietf.org/doc/html/rfc3597#section-5)
+        if tokens.next_text()? != "\\#" {
+            return Err(crate::SimpleDnsError::InvalidPresentationFormat);
+        }
+
+        let length: usize = tokens.next_value()?;
+        let data = tokens.remaining_hex()?;
+        if data.len() != length || length > MAX_NULL_LENGTH {
+            return Err(crate::SimpleDnsError::InvalidPresentationFormat);
+        }
+
+        Ok(Self {
+            length: length as u16,
+            data: Cow::Owned(data),
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_generic(f, &self.data)
+    }
+}
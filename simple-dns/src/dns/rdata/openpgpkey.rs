@@ -0,0 +1,184 @@
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{write_base64, PresentationFormat, TokenReader},
+        WireFormat, MAX_NAME_LENGTH,
+    },
+    encoding::hex,
+    lib::{fmt, Cow, Vec, Write},
+    DigestAlgorithm, DigestProvider, Label, Name, SimpleDnsError,
+};
+
+use super::RR;
+
+/// An OPENPGPKEY record holds an OpenPGP transferable public key, [RFC 7929](https://www.rfc-editor.org/rfc/rfc7929)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct OPENPGPKEY<'a> {
+    /// The OpenPGP transferable public key, without ASCII armor or base64 encoding
+    pub public_key: Cow<'a, [u8]>,
+}
+
+impl RR for OPENPGPKEY<'_> {
+    const TYPE_CODE: u16 = 61;
+}
+
+impl<'a> WireFormat<'a> for OPENPGPKEY<'a> {
+    const MINIMUM_LEN: usize = 0;
+
+    fn parse(data: &mut BytesBuffer<'a>) -> crate::Result<Self>
+    where
+        Self: Sized,
+    {
+        let public_key = Cow::Borrowed(data.get_remaining());
+        Ok(Self { public_key })
+    }
+
+    fn write_to<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        out.write_all(&self.public_key)?;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.public_key.len()
+    }
+}
+
+impl OPENPGPKEY<'_> {
+    /// Transforms the inner data into its owned type
+    pub fn into_owned<'b>(self) -> OPENPGPKEY<'b> {
+        OPENPGPKEY {
+            public_key: self.public_key.into_owned().into(),
+        }
+    }
+
+    /// Returns the owner name of the OPENPGPKEY record of an email address, in the
+    /// `<hash>._openpgpkey.<domain>` format, where the hash is the first 28 octets of the SHA-256
+    /// digest of the local-part, [RFC 7929](https://www.rfc-editor.org/rfc/rfc7929#section-3)
+    ///
+    /// The digest is calculated by the `digest_provider`.
+    /// Returns [`SimpleDnsError::AttemptedInvalidOperation`] if the provider does not support it
+    ///
+    /// ```
+    /// # use simple_dns::{rdata::OPENPGPKEY, DigestAlgorithm, DigestProvider};
+    /// # struct Sha256;
+    /// # impl DigestProvider for Sha256 {
+    /// #     fn digest(&self, _: DigestAlgorithm, _: &[u8]) -> Option<Vec<u8>> { Some(vec![0xc9; 32]) }
+    /// # }
+    /// let owner = OPENPGPKEY::owner_name("hugh@example.com", &Sha256)?;
+    /// assert!(owner.to_string().ends_with("._openpgpkey.example.com"));
+    /// # Ok::<(), simple_dns::SimpleDnsError>(())
+    /// ```
+    pub fn owner_name<D: DigestProvider + ?Sized>(
+        email: &str,
+        digest_provider: &D,
+    ) -> crate::Result<Name<'static>> {
+        email_owner_name(email, "_openpgpkey", digest_provider)
+    }
+}
+
+/// Returns the `<hash>.<prefix>.<domain>` owner name of an email address, shared by OPENPGPKEY
+/// and SMIMEA records.
+///
+/// The local-part is hashed as it is, without any canonicalization
+pub(super) fn email_owner_name<D: DigestProvider + ?Sized>(
+    email: &str,
+    prefix: &'static str,
+    digest_provider: &D,
+) -> crate::Result<Name<'static>> {
+    let (local_part, domain) = email
+        .rsplit_once('@')
+        .filter(|(local_part, domain)| !local_part.is_empty() && !domain.is_empty())
+        .ok_or(SimpleDnsError::InvalidServiceName)?;
+    let domain = Name::new(domain)?;
+
+    let digest = digest_provider
+        .digest(DigestAlgorithm::Sha256, local_part.as_bytes())
+        .ok_or(SimpleDnsError::AttemptedInvalidOperation)?;
+    let digest = digest
+        .get(..28)
+        .ok_or(SimpleDnsError::AttemptedInvalidOperation)?;
+
+    let mut labels = Vec::with_capacity(domain.get_labels().len() + 2);
+    labels.push(Label::new_unchecked(
+        hex::encode(digest).to_ascii_lowercase().into_bytes(),
+    ));
+    labels.push(Label::new_unchecked(prefix.as_bytes()));
+    labels.extend(domain.get_labels().iter().map(|l| l.clone().into_owned()));
+
+    let name = Name::new_with_labels(&labels);
+    if name.len() > MAX_NAME_LENGTH {
+        return Err(SimpleDnsError::NameTooLong);
+    }
+
+    Ok(name)
+}
+
+impl PresentationFormat for OPENPGPKEY<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            public_key: Cow::Owned(tokens.remaining_base64()?),
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_base64(f, &self.public_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::dnssec::sha::{self, Sha};
+    use crate::dns::presentation_format::{parse_text, to_text};
+    use crate::lib::{format, ToString};
+
+    #[test]
+    fn parse_and_write_openpgpkey() {
+        let openpgpkey = OPENPGPKEY {
+            public_key: Cow::Borrowed(&[0x99, 0x01, 0x0d, 0x04, 0x52]),
+        };
+
+        let mut data = Vec::new();
+        openpgpkey.write_to(&mut data).unwrap();
+        assert_eq!(data, [0x99, 0x01, 0x0d, 0x04, 0x52]);
+        assert_eq!(data.len(), openpgpkey.len());
+
+        let parsed = OPENPGPKEY::parse(&mut data[..].into()).unwrap();
+        assert_eq!(parsed, openpgpkey);
+
+        assert_eq!(to_text(&openpgpkey), "mQENBFI=");
+        assert_eq!(parse_text::<OPENPGPKEY>("mQEN BFI=").unwrap(), openpgpkey);
+    }
+
+    #[test]
+    fn owner_name() {
+        // RFC 7929 section 3
+        assert_eq!(
+            OPENPGPKEY::owner_name("hugh@example.com", &Sha)
+                .unwrap()
+                .to_string(),
+            "c93f1e400f26708f98cb19d936620da35eec8f72e57f9eec01c1afd6._openpgpkey.example.com"
+        );
+
+        for email in ["hugh", "@example.com", "hugh@", "hugh@exa mple.com"] {
+            assert!(OPENPGPKEY::owner_name(email, &Sha).is_err());
+        }
+
+        let long_domain = ["a".repeat(63), "a".repeat(63), "a".repeat(63)].join(".");
+        assert_eq!(
+            OPENPGPKEY::owner_name(&format!("hugh@{long_domain}"), &Sha),
+            Err(SimpleDnsError::NameTooLong)
+        );
+
+        struct Sha1;
+        impl DigestProvider for Sha1 {
+            fn digest(&self, algorithm: DigestAlgorithm, data: &[u8]) -> Option<Vec<u8>> {
+                (algorithm == DigestAlgorithm::Sha1).then(|| sha::sha1(data).to_vec())
+            }
+        }
+        assert_eq!(
+            OPENPGPKEY::owner_name("hugh@example.com", &Sha1),
+            Err(SimpleDnsError::AttemptedInvalidOperation)
+        );
+    }
+}
@@ -1,9 +1,12 @@
 use crate::{
     bytes_buffer::BytesBuffer,
-    dns::{header::Header, WireFormat},
-    lib::Cow,
-    lib::Vec,
-    lib::Write,
+    dns::{
+        header::Header,
+        presentation_format::{write_generic, PresentationFormat, TokenReader},
+        WireFormat,
+    },
+    edns::{self, ClientSubnet, Cookie, EdnsOption, ExtendedError, ZoneVersion},
+    lib::{fmt, Cow, Vec, Write},
     RCODE,
 };
 
@@ -20,6 +23,9 @@ pub mod masks {
 ///
 /// There must be only one OPT record in the message.
 /// If a query message with more than one OPT RR is received, a FORMERR (RCODE=1) MUST be returned.
+///
+/// The options are kept as raw [`OPTCode`] values, use [`OPT::options`] and the typed accessors,
+/// like [`OPT::client_subnet`], to read them as [`EdnsOption`] values
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct OPT<'a> {
     /// The variable part of this OPT RR
@@ -109,10 +115,137 @@ impl OPT<'_> {
     }
 }
 
-/// Represents the variable part of an OPT rr
+impl<'a> OPT<'a> {
+    /// Returns the options of this record as [`EdnsOption`] values, options with invalid data
+    /// are returned as errors
+    pub fn options(&self) -> impl Iterator<Item = crate::Result<EdnsOption<'_>>> {
+        self.opt_codes
+            .iter()
+            .map(|option| EdnsOption::parse_data(option.code, &option.data))
+    }
+
+    /// Returns true if this record holds an option with the given code
+    pub fn has_option(&self, code: u16) -> bool {
+        self.opt_codes.iter().any(|option| option.code == code)
+    }
+
+    /// Returns the first option with the given code, or an error if its data is invalid
+    pub fn option(&self, code: u16) -> crate::Result<Option<EdnsOption<'_>>> {
+        self.opt_codes
+            .iter()
+            .find(|option| option.code == code)
+            .map(|option| EdnsOption::parse_data(option.code, &option.data))
+            .transpose()
+    }
+
+    /// Adds an option, after the options already present
+    pub fn add_option(&mut self, option: &EdnsOption<'_>) -> crate::Result<()> {
+        self.opt_codes.push(option.to_opt_code()?);
+        Ok(())
+    }
+
+    /// Replaces the options with the same code as `option`, or adds it if there is none
+    pub fn set_option(&mut self, option: &EdnsOption<'_>) -> crate::Result<()> {
+        let opt_code = option.to_opt_code()?;
+        let position = self
+            .opt_codes
+            .iter()
+            .position(|o| o.code == opt_code.code)
+            .unwrap_or(self.opt_codes.len());
+        self.remove_option(opt_code.code);
+        self.opt_codes.insert(position, opt_code);
+        Ok(())
+    }
+
+    /// Removes every option with the given code
+    pub fn remove_option(&mut self, code: u16) {
+        self.opt_codes.retain(|option| option.code != code);
+    }
+
+    /// Returns the name server identifier option, empty in queries
+    pub fn nsid(&self) -> Option<&[u8]> {
+        self.opt_codes
+            .iter()
+            .find(|option| option.code == edns::NSID)
+            .map(|option| &option.data[..])
+    }
+
+    /// Returns the client subnet option
+    pub fn client_subnet(&self) -> crate::Result<Option<ClientSubnet>> {
+        match self.option(edns::CLIENT_SUBNET)? {
+            Some(EdnsOption::ClientSubnet(subnet)) => Ok(Some(subnet)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the zone expire timer, in seconds. The empty option sent in queries returns None,
+    /// use [`OPT::has_option`] to check for it
+    pub fn expire(&self) -> crate::Result<Option<u32>> {
+        match self.option(edns::EXPIRE)? {
+            Some(EdnsOption::Expire(expire)) => Ok(expire),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the cookie option
+    pub fn cookie(&self) -> crate::Result<Option<Cookie<'_>>> {
+        match self.option(edns::COOKIE)? {
+            Some(EdnsOption::Cookie(cookie)) => Ok(Some(cookie)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the TCP keepalive timeout, in units of 100 milliseconds. The empty option sent in
+    /// queries returns None, use [`OPT::has_option`] to check for it
+    pub fn tcp_keepalive(&self) -> crate::Result<Option<u16>> {
+        match self.option(edns::TCP_KEEPALIVE)? {
+            Some(EdnsOption::TcpKeepalive(timeout)) => Ok(timeout),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the number of padding octets
+    pub fn padding(&self) -> Option<usize> {
+        self.opt_codes
+            .iter()
+            .find(|option| option.code == edns::PADDING)
+            .map(|option| option.data.len())
+    }
+
+    /// Returns the closest trust point of the chain query option
+    pub fn chain(&self) -> crate::Result<Option<crate::Name<'_>>> {
+        match self.option(edns::CHAIN)? {
+            Some(EdnsOption::Chain(name)) => Ok(Some(name)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the extended DNS error options, a message may hold more than one
+    pub fn extended_errors(&self) -> impl Iterator<Item = crate::Result<ExtendedError<'_>>> {
+        self.opt_codes
+            .iter()
+            .filter(|option| option.code == edns::EXTENDED_ERROR)
+            .map(
+                |option| match EdnsOption::parse_data(option.code, &option.data)? {
+                    EdnsOption::ExtendedError(error) => Ok(error),
+                    _ => unreachable!(),
+                },
+            )
+    }
+
+    /// Returns the zone version. The empty option sent in queries returns None, use
+    /// [`OPT::has_option`] to check for it
+    pub fn zone_version(&self) -> crate::Result<Option<ZoneVersion<'_>>> {
+        match self.option(edns::ZONE_VERSION)? {
+            Some(EdnsOption::ZoneVersion(version)) => Ok(version),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Represents the variable part of an OPT rr, see [`EdnsOption`] for the typed options
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct OPTCode<'a> {
-    // TODO: include an OPT_CODE enum???
     /// Assigned by the Expert Review process as defined by the DNSEXT working group and the IESG.
     pub code: u16,
     /// Varies per OPTION-CODE.  MUST be treated as a bit field.
@@ -129,10 +262,28 @@ impl OPTCode<'_> {
     }
 }
 
+impl PresentationFormat for OPT<'_> {
+    fn parse_text(_tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        // OPT is a pseudo record, it does not have a presentation format
+        Err(crate::SimpleDnsError::InvalidPresentationFormat)
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Written in the generic format, for debugging purposes
+        let mut data = Vec::with_capacity(self.len());
+        for code in &self.opt_codes {
+            data.extend_from_slice(&code.code.to_be_bytes());
+            data.extend_from_slice(&(code.data.len() as u16).to_be_bytes());
+            data.extend_from_slice(&code.data);
+        }
+        write_generic(f, &data)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::lib::vec;
-    use crate::{rdata::RData, Name, ResourceRecord};
+    use crate::{edns::ExtendedErrorCode, rdata::RData, Name, ResourceRecord};
 
     use super::*;
 
@@ -221,4 +372,59 @@ mod tests {
         assert_eq!(1, opt_code.code);
         assert_eq!(vec![255, 255], *opt_code.data);
     }
+
+    #[test]
+    fn typed_options() {
+        let mut opt = OPT {
+            udp_packet_size: 1232,
+            version: 0,
+            opt_codes: Vec::new(),
+        };
+        let subnet = ClientSubnet {
+            source_prefix_length: 24,
+            scope_prefix_length: 0,
+            address: crate::lib::Ipv4Addr::new(192, 0, 2, 0).into(),
+        };
+        opt.add_option(&EdnsOption::Nsid(Cow::Borrowed(&[])))
+            .unwrap();
+        opt.add_option(&EdnsOption::ClientSubnet(subnet)).unwrap();
+        opt.add_option(&EdnsOption::Expire(None)).unwrap();
+        opt.add_option(&EdnsOption::Padding(8)).unwrap();
+        for info_code in [
+            ExtendedErrorCode::DnssecBogus,
+            ExtendedErrorCode::Prohibited,
+        ] {
+            let error = ExtendedError::new(info_code);
+            opt.add_option(&EdnsOption::ExtendedError(error)).unwrap();
+        }
+        opt.opt_codes.push(OPTCode {
+            code: 65001,
+            data: Cow::Borrowed(&[1]),
+        });
+
+        assert_eq!(opt.nsid(), Some(&[][..]));
+        assert_eq!(opt.client_subnet().unwrap(), Some(subnet));
+        assert!(opt.has_option(edns::EXPIRE));
+        assert_eq!(opt.expire().unwrap(), None);
+        assert_eq!(opt.padding(), Some(8));
+        assert_eq!(opt.cookie().unwrap(), None);
+        let codes: Vec<u16> = opt
+            .extended_errors()
+            .map(|error| error.unwrap().info_code.into())
+            .collect();
+        assert_eq!(codes, [6, 18]);
+        assert!(matches!(
+            opt.options().last(),
+            Some(Ok(EdnsOption::Unknown(OPTCode { code: 65001, .. })))
+        ));
+
+        opt.set_option(&EdnsOption::Expire(Some(60))).unwrap();
+        assert_eq!(opt.expire().unwrap(), Some(60));
+        assert_eq!(opt.opt_codes[2].code, edns::EXPIRE);
+        opt.remove_option(edns::PADDING);
+        assert_eq!(opt.padding(), None);
+
+        opt.opt_codes[1].data = Cow::Borrowed(&[0, 1, 33, 0]);
+        assert!(opt.client_subnet().is_err());
+    }
 }
@@ -1,7 +1,10 @@
 use crate::{
     bytes_buffer::BytesBuffer,
-    dns::{Name, WireFormat},
-    lib::{Seek, Write},
+    dns::{
+        presentation_format::{PresentationFormat, Text, TokenReader},
+        Name, WireFormat,
+    },
+    lib::{fmt, Seek, Write},
 };
 
 use super::RR;
@@ -65,6 +68,19 @@ impl<'a> WireFormat<'a> for RouteThrough<'a> {
     }
 }
 
+impl PresentationFormat for RouteThrough<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            preference: tokens.next_value()?,
+            intermediate_host: tokens.next_name()?,
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.preference, Text(&self.intermediate_host))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -1,7 +1,10 @@
 use crate::{
     bytes_buffer::BytesBuffer,
-    dns::{Name, WireFormat},
-    lib::{Seek, Write},
+    dns::{
+        presentation_format::{PresentationFormat, Text, TokenReader},
+        Name, WireFormat,
+    },
+    lib::{fmt, Seek, Write},
 };
 
 use super::RR;
@@ -60,6 +63,19 @@ impl<'a> WireFormat<'a> for RP<'a> {
     }
 }
 
+impl PresentationFormat for RP<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            mbox: tokens.next_name()?,
+            txt: tokens.next_name()?,
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", Text(&self.mbox), Text(&self.txt))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -1,8 +1,12 @@
 use crate::{
     bytes_buffer::BytesBuffer,
-    dns::{Name, WireFormat},
-    lib::Cow,
-    lib::Write,
+    dns::{
+        presentation_format::{
+            write_base64, write_timestamp, write_type, PresentationFormat, Text, TokenReader,
+        },
+        Name, WireFormat,
+    },
+    lib::{fmt, Cow, Write},
 };
 
 use super::RR;
@@ -101,6 +105,36 @@ impl RRSIG<'_> {
     }
 }
 
+impl PresentationFormat for RRSIG<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            type_covered: tokens.next_type()?.into(),
+            algorithm: tokens.next_algorithm()?,
+            labels: tokens.next_value()?,
+            original_ttl: tokens.next_ttl()?,
+            signature_expiration: tokens.next_timestamp()?,
+            signature_inception: tokens.next_timestamp()?,
+            key_tag: tokens.next_value()?,
+            signer_name: tokens.next_name()?,
+            signature: Cow::Owned(tokens.remaining_base64()?),
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_type(f, self.type_covered.into())?;
+        write!(
+            f,
+            " {} {} {} ",
+            self.algorithm, self.labels, self.original_ttl
+        )?;
+        write_timestamp(f, self.signature_expiration)?;
+        f.write_str(" ")?;
+        write_timestamp(f, self.signature_inception)?;
+        write!(f, " {} {} ", self.key_tag, Text(&self.signer_name))?;
+        write_base64(f, &self.signature)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
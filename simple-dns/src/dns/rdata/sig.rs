@@ -0,0 +1,57 @@
+use super::SIG;
+
+impl SIG<'_> {
+    /// Returns true if this is a SIG(0) transaction signature, which covers a whole message
+    /// instead of an RRset, identified by the type covered 0,
+    /// see [RFC 2931](https://www.rfc-editor.org/rfc/rfc2931#section-3)
+    pub fn is_sig0(&self) -> bool {
+        self.type_covered == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dns::WireFormat,
+        lib::{ToString, Vec},
+        rdata::{RData, KEY},
+        TYPE,
+    };
+
+    #[test]
+    fn parse_and_write_key_and_sig() {
+        let text = "512 3 15 l02Woi0iS8Aa25FQkUd9RMzZHJpBoRQwAQEX1SxZJA4=";
+        let RData::KEY(key) = RData::from_str(TYPE::KEY, text).unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(key.flags, 512);
+        assert_eq!(key.to_string(), text);
+
+        let mut data = Vec::new();
+        key.write_to(&mut data).unwrap();
+        assert_eq!(data[..4], [2, 0, 3, 15]);
+        assert_eq!(KEY::parse(&mut data[..].into()).unwrap(), key);
+
+        let text = "TYPE0 15 0 0 20240101000500 20240101000000 3613 host.example.com. AQID";
+        let RData::SIG(sig) = RData::from_str(TYPE::SIG, text).unwrap() else {
+            unreachable!()
+        };
+        assert!(sig.is_sig0());
+        assert_eq!(sig.to_string(), text);
+
+        let mut data = Vec::new();
+        sig.write_to(&mut data).unwrap();
+        assert_eq!(data[..4], [0, 0, 15, 0]);
+        assert_eq!(SIG::parse(&mut data[..].into()).unwrap(), sig);
+
+        let RData::SIG(sig) = RData::from_str(
+            TYPE::SIG,
+            "A 15 2 3600 20240101000500 20240101000000 3613 example.com. AQID",
+        )
+        .unwrap() else {
+            unreachable!()
+        };
+        assert!(!sig.is_sig0());
+    }
+}
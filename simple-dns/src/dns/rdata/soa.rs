@@ -1,7 +1,10 @@
 use crate::{
     bytes_buffer::BytesBuffer,
-    dns::{Name, WireFormat},
-    lib::{Seek, Write},
+    dns::{
+        presentation_format::{PresentationFormat, Text, TokenReader},
+        Name, WireFormat,
+    },
+    lib::{fmt, Seek, Write},
 };
 
 use super::RR;
@@ -103,6 +106,34 @@ impl<'a> WireFormat<'a> for SOA<'a> {
     }
 }
 
+impl PresentationFormat for SOA<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            mname: tokens.next_name()?,
+            rname: tokens.next_name()?,
+            serial: tokens.next_value()?,
+            refresh: tokens.next_ttl()? as i32,
+            retry: tokens.next_ttl()? as i32,
+            expire: tokens.next_ttl()? as i32,
+            minimum: tokens.next_ttl()?,
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {} {}",
+            Text(&self.mname),
+            Text(&self.rname),
+            self.serial,
+            self.refresh,
+            self.retry,
+            self.expire,
+            self.minimum
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::lib::Vec;
@@ -1,4 +1,12 @@
-use crate::{bytes_buffer::BytesBuffer, dns::WireFormat, lib::Write, Name};
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{PresentationFormat, Text, TokenReader},
+        WireFormat,
+    },
+    lib::{fmt, Write},
+    Name,
+};
 
 use super::RR;
 
@@ -68,6 +76,28 @@ impl<'a> WireFormat<'a> for SRV<'a> {
     }
 }
 
+impl PresentationFormat for SRV<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            priority: tokens.next_value()?,
+            weight: tokens.next_value()?,
+            port: tokens.next_value()?,
+            target: tokens.next_name()?,
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.priority,
+            self.weight,
+            self.port,
+            Text(&self.target)
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
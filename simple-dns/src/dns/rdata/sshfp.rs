@@ -0,0 +1,396 @@
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{write_hex, PresentationFormat, TokenReader},
+        WireFormat,
+    },
+    encoding::base64,
+    lib::{fmt, Cow, Vec, Write},
+    DigestAlgorithm, DigestProvider, SimpleDnsError,
+};
+
+use super::RR;
+
+/// A SSHFP record see [rfc4255](https://www.rfc-editor.org/rfc/rfc4255#section-3)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct SSHFP<'a> {
+    /// The algorithm of the public key
+    pub algorithm: SshfpAlgorithm,
+    /// The message digest algorithm used to calculate the fingerprint
+    pub fingerprint_type: FingerprintType,
+    /// The fingerprint of the public key
+    pub fingerprint: Cow<'a, [u8]>,
+}
+
+/// Public key algorithm of SSHFP records, [RFC 4255](https://www.rfc-editor.org/rfc/rfc4255#section-3.1.1),
+/// [RFC 6594](https://www.rfc-editor.org/rfc/rfc6594), [RFC 7479](https://www.rfc-editor.org/rfc/rfc7479)
+/// and [RFC 8709](https://www.rfc-editor.org/rfc/rfc8709)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SshfpAlgorithm {
+    /// RSA, `ssh-rsa` keys
+    Rsa,
+    /// DSA, `ssh-dss` keys
+    Dsa,
+    /// ECDSA, `ecdsa-sha2-*` keys
+    Ecdsa,
+    /// Ed25519, `ssh-ed25519` keys
+    Ed25519,
+    /// Ed448, `ssh-ed448` keys
+    Ed448,
+    /// Unassigned algorithm
+    Unknown(u8),
+}
+
+impl SshfpAlgorithm {
+    /// Returns the algorithm of an OpenSSH public key type, like `ssh-ed25519`
+    pub fn from_key_type(key_type: &[u8]) -> Option<Self> {
+        match key_type {
+            b"ssh-rsa" => Some(SshfpAlgorithm::Rsa),
+            b"ssh-dss" => Some(SshfpAlgorithm::Dsa),
+            b"ssh-ed25519" => Some(SshfpAlgorithm::Ed25519),
+            b"ssh-ed448" => Some(SshfpAlgorithm::Ed448),
+            _ if key_type.starts_with(b"ecdsa-sha2-") => Some(SshfpAlgorithm::Ecdsa),
+            _ => None,
+        }
+    }
+}
+
+impl From<u8> for SshfpAlgorithm {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => SshfpAlgorithm::Rsa,
+            2 => SshfpAlgorithm::Dsa,
+            3 => SshfpAlgorithm::Ecdsa,
+            4 => SshfpAlgorithm::Ed25519,
+            6 => SshfpAlgorithm::Ed448,
+            v => SshfpAlgorithm::Unknown(v),
+        }
+    }
+}
+
+impl From<SshfpAlgorithm> for u8 {
+    fn from(value: SshfpAlgorithm) -> Self {
+        match value {
+            SshfpAlgorithm::Rsa => 1,
+            SshfpAlgorithm::Dsa => 2,
+            SshfpAlgorithm::Ecdsa => 3,
+            SshfpAlgorithm::Ed25519 => 4,
+            SshfpAlgorithm::Ed448 => 6,
+            SshfpAlgorithm::Unknown(v) => v,
+        }
+    }
+}
+
+/// Fingerprint type of SSHFP records, [RFC 4255](https://www.rfc-editor.org/rfc/rfc4255#section-3.1.2)
+/// and [RFC 6594](https://www.rfc-editor.org/rfc/rfc6594)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FingerprintType {
+    /// SHA-1 digest of the public key
+    Sha1,
+    /// SHA-256 digest of the public key
+    Sha256,
+    /// Unassigned fingerprint type
+    Unknown(u8),
+}
+
+impl From<u8> for FingerprintType {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => FingerprintType::Sha1,
+            2 => FingerprintType::Sha256,
+            v => FingerprintType::Unknown(v),
+        }
+    }
+}
+
+impl From<FingerprintType> for u8 {
+    fn from(value: FingerprintType) -> Self {
+        match value {
+            FingerprintType::Sha1 => 1,
+            FingerprintType::Sha256 => 2,
+            FingerprintType::Unknown(v) => v,
+        }
+    }
+}
+
+impl RR for SSHFP<'_> {
+    const TYPE_CODE: u16 = 44;
+}
+
+impl<'a> WireFormat<'a> for SSHFP<'a> {
+    const MINIMUM_LEN: usize = 2;
+
+    fn parse(data: &mut BytesBuffer<'a>) -> crate::Result<Self>
+    where
+        Self: Sized,
+    {
+        let algorithm = data.get_u8()?.into();
+        let fingerprint_type = data.get_u8()?.into();
+        let fingerprint = Cow::Borrowed(data.get_remaining());
+
+        Ok(Self {
+            algorithm,
+            fingerprint_type,
+            fingerprint,
+        })
+    }
+
+    fn write_to<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        out.write_all(&[self.algorithm.into(), self.fingerprint_type.into()])?;
+        out.write_all(&self.fingerprint)?;
+
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.fingerprint.len() + Self::MINIMUM_LEN
+    }
+}
+
+impl SSHFP<'_> {
+    /// Transforms the inner data into its owned type
+    pub fn into_owned<'b>(self) -> SSHFP<'b> {
+        SSHFP {
+            algorithm: self.algorithm,
+            fingerprint_type: self.fingerprint_type,
+            fingerprint: self.fingerprint.into_owned().into(),
+        }
+    }
+
+    /// Creates the SSHFP record of an OpenSSH public key, in the `ssh-ed25519 AAAA... comment`
+    /// format used by `.pub` and `known_hosts` files, with the fingerprint calculated by the
+    /// `digest_provider`.
+    ///
+    /// Returns [`SimpleDnsError::InvalidPresentationFormat`] if the key is malformed or its type is
+    /// unknown, and [`SimpleDnsError::AttemptedInvalidOperation`] if the `fingerprint_type` is not
+    /// supported by the provider
+    pub fn from_public_key<D: DigestProvider + ?Sized>(
+        public_key: &str,
+        fingerprint_type: FingerprintType,
+        digest_provider: &D,
+    ) -> crate::Result<SSHFP<'static>> {
+        let mut fields = public_key.split_ascii_whitespace();
+        let (Some(key_type), Some(key)) = (fields.next(), fields.next()) else {
+            return Err(SimpleDnsError::InvalidPresentationFormat);
+        };
+
+        let key_blob = base64::decode(key.as_bytes())?;
+        if key_blob_type(&key_blob) != Some(key_type.as_bytes()) {
+            return Err(SimpleDnsError::InvalidPresentationFormat);
+        }
+
+        SSHFP::from_key_blob(&key_blob, fingerprint_type, digest_provider)
+    }
+
+    /// Creates the SSHFP record of a public key blob, in the SSH wire format
+    /// ([RFC 4253](https://www.rfc-editor.org/rfc/rfc4253#section-6.6)), as presented by a server
+    /// during the key exchange.
+    ///
+    /// Returns [`SimpleDnsError::InvalidPresentationFormat`] if the key type is unknown, and
+    /// [`SimpleDnsError::AttemptedInvalidOperation`] if the `fingerprint_type` is not supported by
+    /// the provider
+    pub fn from_key_blob<D: DigestProvider + ?Sized>(
+        key_blob: &[u8],
+        fingerprint_type: FingerprintType,
+        digest_provider: &D,
+    ) -> crate::Result<SSHFP<'static>> {
+        let algorithm = key_blob_type(key_blob)
+            .and_then(SshfpAlgorithm::from_key_type)
+            .ok_or(SimpleDnsError::InvalidPresentationFormat)?;
+        let fingerprint = fingerprint(fingerprint_type, key_blob, digest_provider)
+            .ok_or(SimpleDnsError::AttemptedInvalidOperation)?;
+
+        Ok(SSHFP {
+            algorithm,
+            fingerprint_type,
+            fingerprint: fingerprint.into(),
+        })
+    }
+
+    /// Returns true if the public key blob, in the SSH wire format, matches this record.
+    ///
+    /// Returns false if the fingerprint type is not supported by the provider
+    pub fn matches_key<D: DigestProvider + ?Sized>(
+        &self,
+        key_blob: &[u8],
+        digest_provider: &D,
+    ) -> bool {
+        key_blob_type(key_blob).and_then(SshfpAlgorithm::from_key_type) == Some(self.algorithm)
+            && fingerprint(self.fingerprint_type, key_blob, digest_provider)
+                .is_some_and(|fingerprint| fingerprint == self.fingerprint[..])
+    }
+
+    /// Returns true if the public key blob presented by a server matches any of the SSHFP
+    /// `records` published for the host, see [RFC 4255](https://www.rfc-editor.org/rfc/rfc4255#section-2.1)
+    ///
+    /// ```
+    /// # use simple_dns::{rdata::{FingerprintType, SSHFP}, DigestAlgorithm, DigestProvider};
+    /// # struct Sha256;
+    /// # impl DigestProvider for Sha256 {
+    /// #     fn digest(&self, _: DigestAlgorithm, data: &[u8]) -> Option<Vec<u8>> { Some(data.to_vec()) }
+    /// # }
+    /// let public_key = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIGVd2FmhhW9XHQhEDDTrXSjcG5wJMH3YlOXr7pN/bEjX";
+    /// let record = SSHFP::from_public_key(public_key, FingerprintType::Sha256, &Sha256)?;
+    ///
+    /// # let key_blob = [b"\0\0\0\x0bssh-ed25519\0\0\0\x20".as_slice(), &[0x65, 0x5d, 0xd8, 0x59, 0xa1, 0x85, 0x6f, 0x57, 0x1d, 0x08, 0x44, 0x0c, 0x34, 0xeb, 0x5d, 0x28, 0xdc, 0x1b, 0x9c, 0x09, 0x30, 0x7d, 0xd8, 0x94, 0xe5, 0xeb, 0xee, 0x93, 0x7f, 0x6c, 0x48, 0xd7]].concat();
+    /// assert!(SSHFP::matches_any(&[record], &key_blob, &Sha256));
+    /// # Ok::<(), simple_dns::SimpleDnsError>(())
+    /// ```
+    pub fn matches_any<D: DigestProvider + ?Sized>(
+        records: &[SSHFP<'_>],
+        key_blob: &[u8],
+        digest_provider: &D,
+    ) -> bool {
+        records
+            .iter()
+            .any(|record| record.matches_key(key_blob, digest_provider))
+    }
+}
+
+/// Returns the fingerprint of a public key blob, or None if the fingerprint type is unknown or not
+/// supported by the `digest_provider`
+fn fingerprint<D: DigestProvider + ?Sized>(
+    fingerprint_type: FingerprintType,
+    key_blob: &[u8],
+    digest_provider: &D,
+) -> Option<Vec<u8>> {
+    let algorithm = match fingerprint_type {
+        FingerprintType::Sha1 => DigestAlgorithm::Sha1,
+        FingerprintType::Sha256 => DigestAlgorithm::Sha256,
+        FingerprintType::Unknown(_) => return None,
+    };
+    digest_provider.digest(algorithm, key_blob)
+}
+
+/// Returns the key type, the first string of a public key blob
+fn key_blob_type(key_blob: &[u8]) -> Option<&[u8]> {
+    let length = u32::from_be_bytes(key_blob.get(..4)?.try_into().ok()?) as usize;
+    key_blob.get(4..4usize.checked_add(length)?)
+}
+
+impl PresentationFormat for SSHFP<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            algorithm: tokens.next_value::<u8>()?.into(),
+            fingerprint_type: tokens.next_value::<u8>()?.into(),
+            fingerprint: Cow::Owned(tokens.remaining_hex()?),
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} ",
+            u8::from(self.algorithm),
+            u8::from(self.fingerprint_type)
+        )?;
+        write_hex(f, &self.fingerprint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::dnssec::sha::{self, Sha};
+    use crate::dns::presentation_format::{parse_text, to_text};
+    use crate::lib::{format, vec};
+
+    fn key_blob(key_type: &str, key: &[u8]) -> Vec<u8> {
+        let mut blob = Vec::new();
+        blob.extend_from_slice(&(key_type.len() as u32).to_be_bytes());
+        blob.extend_from_slice(key_type.as_bytes());
+        blob.extend_from_slice(&(key.len() as u32).to_be_bytes());
+        blob.extend_from_slice(key);
+        blob
+    }
+
+    #[test]
+    fn parse_and_write_sshfp() {
+        let sshfp = SSHFP {
+            algorithm: SshfpAlgorithm::Ed25519,
+            fingerprint_type: FingerprintType::Sha256,
+            fingerprint: Cow::Borrowed(&[1, 2, 3, 4, 5]),
+        };
+
+        let mut data = Vec::new();
+        sshfp.write_to(&mut data).unwrap();
+        assert_eq!(data, [4, 2, 1, 2, 3, 4, 5]);
+        assert_eq!(data.len(), sshfp.len());
+
+        let parsed = SSHFP::parse(&mut data[..].into()).unwrap();
+        assert_eq!(parsed, sshfp);
+
+        let text = "2 1 123456789ABCDEF67890123456789ABCDEF67890";
+        let sshfp: SSHFP = parse_text(text).unwrap();
+        assert_eq!(sshfp.algorithm, SshfpAlgorithm::Dsa);
+        assert_eq!(sshfp.fingerprint_type, FingerprintType::Sha1);
+        assert_eq!(sshfp.fingerprint.len(), 20);
+        assert_eq!(to_text(&sshfp), text);
+
+        let sshfp: SSHFP = parse_text("5 3 00").unwrap();
+        assert_eq!(sshfp.algorithm, SshfpAlgorithm::Unknown(5));
+        assert_eq!(sshfp.fingerprint_type, FingerprintType::Unknown(3));
+    }
+
+    #[test]
+    fn create_from_public_key() {
+        let blob = key_blob("ssh-ed25519", &[7; 32]);
+        let public_key = format!("ssh-ed25519 {} user@host", base64::encode(&blob));
+
+        let sshfp = SSHFP::from_public_key(&public_key, FingerprintType::Sha256, &Sha).unwrap();
+        assert_eq!(sshfp.algorithm, SshfpAlgorithm::Ed25519);
+        assert_eq!(sshfp.fingerprint_type, FingerprintType::Sha256);
+        assert_eq!(*sshfp.fingerprint, sha::sha256(&blob));
+
+        let blob = key_blob("ecdsa-sha2-nistp256", &[4; 65]);
+        let sshfp = SSHFP::from_key_blob(&blob, FingerprintType::Sha1, &Sha).unwrap();
+        assert_eq!(sshfp.algorithm, SshfpAlgorithm::Ecdsa);
+        assert_eq!(*sshfp.fingerprint, sha::sha1(&blob));
+
+        // The key type must match the blob
+        let public_key = format!("ssh-rsa {}", base64::encode(&blob));
+        assert_eq!(
+            SSHFP::from_public_key(&public_key, FingerprintType::Sha1, &Sha),
+            Err(SimpleDnsError::InvalidPresentationFormat)
+        );
+        assert_eq!(
+            SSHFP::from_public_key("ssh-ed25519", FingerprintType::Sha1, &Sha),
+            Err(SimpleDnsError::InvalidPresentationFormat)
+        );
+        assert_eq!(
+            SSHFP::from_key_blob(&key_blob("ssh-unknown", &[1]), FingerprintType::Sha1, &Sha),
+            Err(SimpleDnsError::InvalidPresentationFormat)
+        );
+        assert_eq!(
+            SSHFP::from_key_blob(&blob, FingerprintType::Unknown(3), &Sha),
+            Err(SimpleDnsError::AttemptedInvalidOperation)
+        );
+    }
+
+    #[test]
+    fn match_presented_key() {
+        let ed25519 = key_blob("ssh-ed25519", &[7; 32]);
+        let rsa = key_blob("ssh-rsa", &[3; 64]);
+        let records = vec![
+            SSHFP::from_key_blob(&rsa, FingerprintType::Sha1, &Sha).unwrap(),
+            SSHFP::from_key_blob(&ed25519, FingerprintType::Sha256, &Sha).unwrap(),
+        ];
+
+        assert!(records[1].matches_key(&ed25519, &Sha));
+        assert!(!records[1].matches_key(&rsa, &Sha));
+        assert!(SSHFP::matches_any(&records, &ed25519, &Sha));
+        assert!(SSHFP::matches_any(&records, &rsa, &Sha));
+        assert!(!SSHFP::matches_any(
+            &records,
+            &key_blob("ssh-ed25519", &[8; 32]),
+            &Sha
+        ));
+
+        // Same fingerprint, but the algorithm of the record does not match the key
+        let wrong_algorithm = SSHFP {
+            algorithm: SshfpAlgorithm::Ed448,
+            ..records[1].clone()
+        };
+        assert!(!wrong_algorithm.matches_key(&ed25519, &Sha));
+    }
+}
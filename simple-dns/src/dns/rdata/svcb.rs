@@ -1,5 +1,13 @@
-use crate::lib::{BTreeMap, BTreeSet, Cow, Vec};
-use crate::{bytes_buffer::BytesBuffer, dns::WireFormat, lib::Write, CharacterString, Name};
+use crate::lib::{BTreeMap, BTreeSet, Cow, Ipv4Addr, Ipv6Addr, Vec};
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{unescape, write_quoted, PresentationFormat, Text, TokenReader},
+        WireFormat,
+    },
+    lib::{fmt, Write},
+    CharacterString, Name,
+};
 
 use super::RR;
 
@@ -362,6 +370,222 @@ impl<'a> WireFormat<'a> for SVCParam<'a> {
     }
 }
 
+impl PresentationFormat for SVCB<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        let priority = tokens.next_value()?;
+        let target = tokens.next_name()?;
+        let mut svcb = SVCB::new(priority, target);
+
+        while tokens.has_remaining() {
+            let param = SVCParam::parse_text(tokens.next_text()?)?;
+            if svcb.params.contains_key(&param.key_code()) {
+                return Err(crate::SimpleDnsError::InvalidPresentationFormat);
+            }
+            svcb.set_param(param);
+        }
+
+        Ok(svcb)
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.priority, Text(&self.target))?;
+        for param in self.params.values() {
+            write!(f, " {param}")?;
+        }
+        Ok(())
+    }
+}
+
+/// SvcParamKey mnemonics, [RFC 9460](https://datatracker.ietf.org/doc/html/rfc9460#name-initial-contents)
+pub(crate) const SVC_PARAM_KEYS: &[(&str, u16)] = &[
+    ("mandatory", 0),
+    ("alpn", 1),
+    ("no-default-alpn", 2),
+    ("port", 3),
+    ("ipv4hint", 4),
+    ("ech", 5),
+    ("ipv6hint", 6),
+];
+
+impl SVCParam<'_> {
+    /// Parses a single `key[=value]` parameter in presentation format
+    fn parse_text<'b>(text: &str) -> crate::Result<SVCParam<'b>> {
+        let (key, value) = match text.split_once('=') {
+            Some((key, value)) => {
+                let value = value
+                    .strip_prefix('"')
+                    .and_then(|value| value.strip_suffix('"'))
+                    .unwrap_or(value);
+                (key, Some(unescape(value)?))
+            }
+            None => (text, None),
+        };
+
+        let key = parse_key(key)?;
+        let value = match (key, value) {
+            (2, None) => return Ok(SVCParam::NoDefaultAlpn),
+            (2, Some(_)) => return Err(crate::SimpleDnsError::InvalidPresentationFormat),
+            (_, Some(value)) => value,
+            (_, None) if key > 6 => Vec::new(),
+            (_, None) => return Err(crate::SimpleDnsError::InvalidPresentationFormat),
+        };
+
+        let param = match key {
+            0 => {
+                let keys = split_list(&value)
+                    .iter()
+                    .map(|key| {
+                        crate::lib::str::from_utf8(key)
+                            .map_err(|_| crate::SimpleDnsError::InvalidPresentationFormat)
+                            .and_then(parse_key)
+                    })
+                    .collect::<crate::Result<BTreeSet<u16>>>()?;
+                SVCParam::Mandatory(keys)
+            }
+            1 => SVCParam::Alpn(
+                split_list(&value)
+                    .iter()
+                    .map(|alpn| CharacterString::new(alpn).map(|alpn| alpn.into_owned()))
+                    .collect::<crate::Result<_>>()?,
+            ),
+            3 => SVCParam::Port(parse_utf8(&value)?),
+            4 => SVCParam::Ipv4Hint(
+                split_list(&value)
+                    .iter()
+                    .map(|ip| parse_utf8::<Ipv4Addr>(ip).map(Into::into))
+                    .collect::<crate::Result<_>>()?,
+            ),
+            5 => {
+                // The presentation format contains the ECHConfigList, including its length
+                let data = crate::encoding::base64::decode(&value)?;
+                match data.get(2..) {
+                    Some(ech) if u16::from_be_bytes([data[0], data[1]]) as usize == ech.len() => {
+                        SVCParam::Ech(Cow::Owned(ech.to_vec()))
+                    }
+                    _ => return Err(crate::SimpleDnsError::InvalidPresentationFormat),
+                }
+            }
+            6 => SVCParam::Ipv6Hint(
+                split_list(&value)
+                    .iter()
+                    .map(|ip| parse_utf8::<Ipv6Addr>(ip).map(Into::into))
+                    .collect::<crate::Result<_>>()?,
+            ),
+            65535 => return Err(crate::SimpleDnsError::InvalidPresentationFormat),
+            key => SVCParam::Unknown(key, Cow::Owned(value)),
+        };
+
+        Ok(param)
+    }
+}
+
+impl fmt::Display for SVCParam<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_key(f, self.key_code())?;
+
+        match self {
+            SVCParam::Mandatory(keys) => {
+                f.write_str("=")?;
+                for (i, key) in keys.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write_key(f, *key)?;
+                }
+            }
+            SVCParam::Alpn(alpns) => {
+                f.write_str("=\"")?;
+                for (i, alpn) in alpns.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    // Commas and backslashes are escaped twice, once for the list and once for
+                    // the presentation format
+                    for byte in alpn.data.iter() {
+                        match byte {
+                            b',' => f.write_str("\\\\,")?,
+                            b'\\' => f.write_str("\\\\\\\\")?,
+                            b'"' => f.write_str("\\\"")?,
+                            0x20..=0x7e => write!(f, "{}", *byte as char)?,
+                            _ => write!(f, "\\{byte:03}")?,
+                        }
+                    }
+                }
+                f.write_str("\"")?;
+            }
+            SVCParam::NoDefaultAlpn | SVCParam::InvalidKey => {}
+            SVCParam::Port(port) => write!(f, "={port}")?,
+            SVCParam::Ipv4Hint(ips) => {
+                for (i, ip) in ips.iter().enumerate() {
+                    let separator = if i == 0 { "=" } else { "," };
+                    write!(f, "{separator}{}", Ipv4Addr::from(*ip))?;
+                }
+            }
+            SVCParam::Ech(ech) => {
+                let mut data = Vec::with_capacity(ech.len() + 2);
+                data.extend_from_slice(&(ech.len() as u16).to_be_bytes());
+                data.extend_from_slice(ech);
+                write!(f, "={}", crate::encoding::base64::encode(&data))?;
+            }
+            SVCParam::Ipv6Hint(ips) => {
+                for (i, ip) in ips.iter().enumerate() {
+                    let separator = if i == 0 { "=" } else { "," };
+                    write!(f, "{separator}{}", Ipv6Addr::from(*ip))?;
+                }
+            }
+            SVCParam::Unknown(_, value) if value.is_empty() => {}
+            SVCParam::Unknown(_, value) => {
+                f.write_str("=")?;
+                write_quoted(f, value)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes a key name, either its mnemonic or in the `keyNNNNN` format
+fn write_key(f: &mut fmt::Formatter<'_>, key: u16) -> fmt::Result {
+    match SVC_PARAM_KEYS.iter().find(|(_, code)| *code == key) {
+        Some((mnemonic, _)) => f.write_str(mnemonic),
+        None => write!(f, "key{key}"),
+    }
+}
+
+/// Parses a key name, either its mnemonic or in the `keyNNNNN` format
+fn parse_key(key: &str) -> crate::Result<u16> {
+    if let Some((_, code)) = SVC_PARAM_KEYS.iter().find(|(mnemonic, _)| *mnemonic == key) {
+        return Ok(*code);
+    }
+
+    key.strip_prefix("key")
+        .and_then(|code| code.parse().ok())
+        .ok_or(crate::SimpleDnsError::InvalidPresentationFormat)
+}
+
+fn parse_utf8<T: crate::lib::FromStr>(value: &[u8]) -> crate::Result<T> {
+    crate::lib::str::from_utf8(value)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .ok_or(crate::SimpleDnsError::InvalidPresentationFormat)
+}
+
+/// Splits a comma separated list, commas and backslashes can be escaped with a backslash
+fn split_list(value: &[u8]) -> Vec<Vec<u8>> {
+    let mut items = Vec::new();
+    let mut current = Vec::new();
+    let mut bytes = value.iter();
+    while let Some(c) = bytes.next() {
+        match c {
+            b'\\' => current.extend(bytes.next()),
+            b',' => items.push(crate::lib::mem::take(&mut current)),
+            c => current.push(*c),
+        }
+    }
+    items.push(current);
+    items
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -407,6 +631,39 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn parse_presentation_format() {
+        use crate::dns::presentation_format::parse_text;
+
+        let svcb: SVCB = parse_text(
+            r#"1 svc.example. mandatory=alpn,key3 alpn="h2,h3\\,x" no-default-alpn port=8004 key667="hello\210qoo" key9"#,
+        )
+        .unwrap();
+
+        assert_eq!(svcb.target, Name::new_unchecked("svc.example"));
+        let params: Vec<_> = svcb.iter_params().cloned().collect();
+        assert_eq!(
+            params,
+            [
+                SVCParam::Mandatory([1, 3].into()),
+                SVCParam::Alpn(vec![
+                    CharacterString::new(b"h2").unwrap(),
+                    CharacterString::new(b"h3,x").unwrap()
+                ]),
+                SVCParam::NoDefaultAlpn,
+                SVCParam::Port(8004),
+                SVCParam::Unknown(9, Cow::Borrowed(b"")),
+                SVCParam::Unknown(667, Cow::Borrowed(b"hello\xd2qoo")),
+            ]
+        );
+
+        assert!(parse_text::<SVCB>("1 . port=1 port=2").is_err());
+        assert!(parse_text::<SVCB>("1 . no-default-alpn=x").is_err());
+        assert!(parse_text::<SVCB>("1 . port").is_err());
+        assert!(parse_text::<SVCB>("1 . unknown=1").is_err());
+        assert!(parse_text::<SVCB>("1 . ech=AAQA").is_err());
+    }
+
     #[test]
     fn parse_and_write_svcb() {
         // Test vectors are taken from Appendix D.
@@ -478,4 +735,23 @@ mod tests {
             assert_eq!(svcb, &svcb2, "Test {name}");
         }
     }
+
+    #[test]
+    fn write_presentation_format() {
+        use crate::dns::presentation_format::{parse_text, to_text};
+
+        let mut svcb = SVCB::new(1, Name::new_unchecked("svc.example.com"));
+        svcb.set_mandatory([1, 667].into_iter());
+        svcb.set_alpn(&["h2".try_into().unwrap(), "a,b\\c".try_into().unwrap()]);
+        svcb.set_param(SVCParam::NoDefaultAlpn);
+        svcb.set_param(SVCParam::Unknown(9, Cow::Borrowed(b"")));
+        svcb.set_param(SVCParam::Unknown(667, Cow::Borrowed(b"hello\xd2qoo")));
+
+        let text = to_text(&svcb);
+        assert_eq!(
+            text,
+            r#"1 svc.example.com. mandatory=alpn,key667 alpn="h2,a\\,b\\\\c" no-default-alpn key9 key667="hello\210qoo""#
+        );
+        assert_eq!(parse_text::<SVCB>(&text).unwrap(), svcb);
+    }
 }
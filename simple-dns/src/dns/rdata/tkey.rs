@@ -0,0 +1,227 @@
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{write_timestamp, PresentationFormat, Text, TokenReader},
+        Name, WireFormat,
+    },
+    lib::{fmt, Cow, Write},
+    SimpleDnsError,
+};
+
+use super::tsig::{parse_error, parse_sized_base64, write_error, write_sized_base64};
+use super::RR;
+
+/// A TKEY pseudo record, used to establish a shared secret for TSIG between a client and a
+/// server, see [rfc2930](https://www.rfc-editor.org/rfc/rfc2930#section-2)
+///
+/// TKEY exchanges are built with [`crate::tkey`]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct TKEY<'a> {
+    /// The name of the algorithm of the key being established, see [`crate::tsig`] for the
+    /// known algorithms
+    pub algorithm: Name<'a>,
+    /// Start of the validity period of the key, in seconds since Jan 1 1970
+    pub inception: u32,
+    /// End of the validity period of the key, in seconds since Jan 1 1970
+    pub expiration: u32,
+    /// The key assignment method
+    pub mode: TkeyMode,
+    /// Extended RCODE covering TKEY processing, only set in responses
+    pub error: u16,
+    /// Key exchange data, its meaning depends on the mode
+    pub key_data: Cow<'a, [u8]>,
+    /// Reserved for future extensions
+    pub other_data: Cow<'a, [u8]>,
+}
+
+/// The key assignment method of a TKEY record,
+/// see [RFC 2930](https://www.rfc-editor.org/rfc/rfc2930#section-2.5)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TkeyMode {
+    /// The key is assigned by the server
+    ServerAssignment,
+    /// Diffie-Hellman key exchange
+    DiffieHellman,
+    /// GSS-API negotiation, see [RFC 3645](https://www.rfc-editor.org/rfc/rfc3645)
+    GssApi,
+    /// The key is assigned by the resolver
+    ResolverAssignment,
+    /// Deletion of a previously established key
+    KeyDeletion,
+    /// Unassigned mode
+    Unknown(u16),
+}
+
+impl From<u16> for TkeyMode {
+    fn from(value: u16) -> Self {
+        match value {
+            1 => TkeyMode::ServerAssignment,
+            2 => TkeyMode::DiffieHellman,
+            3 => TkeyMode::GssApi,
+            4 => TkeyMode::ResolverAssignment,
+            5 => TkeyMode::KeyDeletion,
+            v => TkeyMode::Unknown(v),
+        }
+    }
+}
+
+impl From<TkeyMode> for u16 {
+    fn from(value: TkeyMode) -> Self {
+        match value {
+            TkeyMode::ServerAssignment => 1,
+            TkeyMode::DiffieHellman => 2,
+            TkeyMode::GssApi => 3,
+            TkeyMode::ResolverAssignment => 4,
+            TkeyMode::KeyDeletion => 5,
+            TkeyMode::Unknown(v) => v,
+        }
+    }
+}
+
+impl RR for TKEY<'_> {
+    const TYPE_CODE: u16 = 249;
+}
+
+impl<'a> WireFormat<'a> for TKEY<'a> {
+    const MINIMUM_LEN: usize = 16;
+
+    fn parse(data: &mut BytesBuffer<'a>) -> crate::Result<Self>
+    where
+        Self: Sized,
+    {
+        let algorithm = Name::parse(data)?;
+        let inception = data.get_u32()?;
+        let expiration = data.get_u32()?;
+        let mode = data.get_u16()?.into();
+        let error = data.get_u16()?;
+        let key_size = data.get_u16()? as usize;
+        let key_data = Cow::Borrowed(data.get_slice(key_size)?);
+        let other_size = data.get_u16()? as usize;
+        let other_data = Cow::Borrowed(data.get_slice(other_size)?);
+
+        Ok(Self {
+            algorithm,
+            inception,
+            expiration,
+            mode,
+            error,
+            key_data,
+            other_data,
+        })
+    }
+
+    fn write_to<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        let key_size =
+            u16::try_from(self.key_data.len()).map_err(|_| SimpleDnsError::InvalidDnsPacket)?;
+        let other_size =
+            u16::try_from(self.other_data.len()).map_err(|_| SimpleDnsError::InvalidDnsPacket)?;
+
+        self.algorithm.write_to(out)?;
+        out.write_all(&self.inception.to_be_bytes())?;
+        out.write_all(&self.expiration.to_be_bytes())?;
+        out.write_all(&u16::from(self.mode).to_be_bytes())?;
+        out.write_all(&self.error.to_be_bytes())?;
+        out.write_all(&key_size.to_be_bytes())?;
+        out.write_all(&self.key_data)?;
+        out.write_all(&other_size.to_be_bytes())?;
+        out.write_all(&self.other_data)?;
+
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.algorithm.len() + self.key_data.len() + self.other_data.len() + Self::MINIMUM_LEN
+    }
+}
+
+impl TKEY<'_> {
+    /// Transforms the inner data into its owned type
+    pub fn into_owned<'b>(self) -> TKEY<'b> {
+        TKEY {
+            algorithm: self.algorithm.into_owned(),
+            inception: self.inception,
+            expiration: self.expiration,
+            mode: self.mode,
+            error: self.error,
+            key_data: self.key_data.into_owned().into(),
+            other_data: self.other_data.into_owned().into(),
+        }
+    }
+}
+
+impl PresentationFormat for TKEY<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            algorithm: tokens.next_name()?,
+            inception: tokens.next_timestamp()?,
+            expiration: tokens.next_timestamp()?,
+            mode: tokens.next_value::<u16>()?.into(),
+            error: parse_error(tokens.next_text()?)?,
+            key_data: parse_sized_base64(tokens)?,
+            other_data: parse_sized_base64(tokens)?,
+        })
+    }
+
+    // Same format used by BIND
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ", Text(&self.algorithm))?;
+        write_timestamp(f, self.inception)?;
+        f.write_str(" ")?;
+        write_timestamp(f, self.expiration)?;
+        write!(f, " {} ", u16::from(self.mode))?;
+        write_error(f, self.error)?;
+        f.write_str(" ")?;
+        write_sized_base64(f, &self.key_data)?;
+        f.write_str(" ")?;
+        write_sized_base64(f, &self.other_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::presentation_format::{parse_text, to_text};
+    use crate::lib::Vec;
+
+    #[test]
+    fn parse_and_write_tkey() {
+        let text = "gss-tsig. 20240101000000 20240102000000 3 NOERROR 4 3q2+7w== 0";
+        let tkey: TKEY = parse_text(text).unwrap();
+        assert_eq!(tkey.algorithm, Name::new_unchecked("gss-tsig"));
+        assert_eq!(tkey.inception, 1_704_067_200);
+        assert_eq!(tkey.expiration, 1_704_153_600);
+        assert_eq!(tkey.mode, TkeyMode::GssApi);
+        assert_eq!(tkey.key_data[..], [0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(to_text(&tkey), text);
+
+        let mut data = Vec::new();
+        tkey.write_to(&mut data).unwrap();
+        assert_eq!(data.len(), tkey.len());
+        assert_eq!(
+            data[10..],
+            [
+                0x65, 0x92, 0x00, 0x80, 0x65, 0x93, 0x52, 0x00, 0, 3, 0, 0, 0, 4, 0xde, 0xad, 0xbe,
+                0xef, 0, 0
+            ]
+        );
+
+        let parsed = TKEY::parse(&mut data[..].into()).unwrap();
+        assert_eq!(parsed, tkey);
+    }
+
+    #[test]
+    fn parse_and_write_mode_and_error() {
+        let text = "hmac-sha256. 20240101000000 20240102000000 9 BADMODE 0 0";
+        let tkey: TKEY = parse_text(text).unwrap();
+        assert_eq!(tkey.mode, TkeyMode::Unknown(9));
+        assert_eq!(tkey.error, 19);
+        assert_eq!(to_text(&tkey), text);
+
+        for mode in 1..=5 {
+            assert_eq!(u16::from(TkeyMode::from(mode)), mode);
+        }
+        assert!(
+            parse_text::<TKEY>("gss-tsig. 20240101000000 20240102000000 3 0 2 3q2+7w== 0").is_err()
+        );
+    }
+}
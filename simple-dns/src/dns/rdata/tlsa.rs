@@ -0,0 +1,511 @@
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{write_hex, PresentationFormat, TokenReader},
+        WireFormat, MAX_NAME_LENGTH,
+    },
+    lib::{fmt, format, Cow, Vec, Write},
+    DigestAlgorithm, DigestProvider, Label, Name,
+};
+
+use super::openpgpkey::email_owner_name;
+use super::{RR, SMIMEA};
+
+/// A TLSA record see [rfc6698](https://www.rfc-editor.org/rfc/rfc6698#section-2)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct TLSA<'a> {
+    /// How the certificate association is used to verify the certificate presented by the server
+    pub certificate_usage: CertificateUsage,
+    /// Which part of the certificate is matched against the association data
+    pub selector: Selector,
+    /// How the association data is presented
+    pub matching_type: MatchingType,
+    /// The certificate association data to be matched
+    pub certificate_data: Cow<'a, [u8]>,
+}
+
+/// Certificate usage field of TLSA and SMIMEA records,
+/// [RFC 7218](https://www.rfc-editor.org/rfc/rfc7218#section-2.1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CertificateUsage {
+    /// CA constraint, the certificate must chain to the CA certificate and pass PKIX validation
+    PkixTa,
+    /// Service certificate constraint, the certificate must match and pass PKIX validation
+    PkixEe,
+    /// Trust anchor assertion, the certificate must chain to the given trust anchor
+    DaneTa,
+    /// Domain-issued certificate, the certificate must match
+    DaneEe,
+    /// Reserved for private use
+    PrivCert,
+    /// Unassigned usage
+    Unknown(u8),
+}
+
+impl From<u8> for CertificateUsage {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => CertificateUsage::PkixTa,
+            1 => CertificateUsage::PkixEe,
+            2 => CertificateUsage::DaneTa,
+            3 => CertificateUsage::DaneEe,
+            255 => CertificateUsage::PrivCert,
+            v => CertificateUsage::Unknown(v),
+        }
+    }
+}
+
+impl From<CertificateUsage> for u8 {
+    fn from(value: CertificateUsage) -> Self {
+        match value {
+            CertificateUsage::PkixTa => 0,
+            CertificateUsage::PkixEe => 1,
+            CertificateUsage::DaneTa => 2,
+            CertificateUsage::DaneEe => 3,
+            CertificateUsage::PrivCert => 255,
+            CertificateUsage::Unknown(v) => v,
+        }
+    }
+}
+
+/// Selector field of TLSA and SMIMEA records,
+/// [RFC 7218](https://www.rfc-editor.org/rfc/rfc7218#section-2.2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Selector {
+    /// The full DER encoded certificate
+    FullCertificate,
+    /// The DER encoded SubjectPublicKeyInfo of the certificate
+    SubjectPublicKeyInfo,
+    /// Reserved for private use
+    PrivSel,
+    /// Unassigned selector
+    Unknown(u8),
+}
+
+impl From<u8> for Selector {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Selector::FullCertificate,
+            1 => Selector::SubjectPublicKeyInfo,
+            255 => Selector::PrivSel,
+            v => Selector::Unknown(v),
+        }
+    }
+}
+
+impl From<Selector> for u8 {
+    fn from(value: Selector) -> Self {
+        match value {
+            Selector::FullCertificate => 0,
+            Selector::SubjectPublicKeyInfo => 1,
+            Selector::PrivSel => 255,
+            Selector::Unknown(v) => v,
+        }
+    }
+}
+
+/// Matching type field of TLSA and SMIMEA records,
+/// [RFC 7218](https://www.rfc-editor.org/rfc/rfc7218#section-2.3)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MatchingType {
+    /// Exact match on the selected content
+    Full,
+    /// SHA-256 hash of the selected content
+    Sha256,
+    /// SHA-512 hash of the selected content
+    Sha512,
+    /// Reserved for private use
+    PrivMatch,
+    /// Unassigned matching type
+    Unknown(u8),
+}
+
+impl From<u8> for MatchingType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => MatchingType::Full,
+            1 => MatchingType::Sha256,
+            2 => MatchingType::Sha512,
+            255 => MatchingType::PrivMatch,
+            v => MatchingType::Unknown(v),
+        }
+    }
+}
+
+impl From<MatchingType> for u8 {
+    fn from(value: MatchingType) -> Self {
+        match value {
+            MatchingType::Full => 0,
+            MatchingType::Sha256 => 1,
+            MatchingType::Sha512 => 2,
+            MatchingType::PrivMatch => 255,
+            MatchingType::Unknown(v) => v,
+        }
+    }
+}
+
+impl RR for TLSA<'_> {
+    const TYPE_CODE: u16 = 52;
+}
+
+impl<'a> WireFormat<'a> for TLSA<'a> {
+    const MINIMUM_LEN: usize = 3;
+
+    fn parse(data: &mut BytesBuffer<'a>) -> crate::Result<Self>
+    where
+        Self: Sized,
+    {
+        let certificate_usage = data.get_u8()?.into();
+        let selector = data.get_u8()?.into();
+        let matching_type = data.get_u8()?.into();
+        let certificate_data = Cow::Borrowed(data.get_remaining());
+
+        Ok(Self {
+            certificate_usage,
+            selector,
+            matching_type,
+            certificate_data,
+        })
+    }
+
+    fn write_to<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        out.write_all(&[
+            self.certificate_usage.into(),
+            self.selector.into(),
+            self.matching_type.into(),
+        ])?;
+        out.write_all(&self.certificate_data)?;
+
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.certificate_data.len() + Self::MINIMUM_LEN
+    }
+}
+
+impl TLSA<'_> {
+    /// Transforms the inner data into its owned type
+    pub fn into_owned<'b>(self) -> TLSA<'b> {
+        TLSA {
+            certificate_usage: self.certificate_usage,
+            selector: self.selector,
+            matching_type: self.matching_type,
+            certificate_data: self.certificate_data.into_owned().into(),
+        }
+    }
+
+    /// Returns the owner name of the TLSA records of a service, in the `_port._protocol.host`
+    /// format, [RFC 6698](https://www.rfc-editor.org/rfc/rfc6698#section-3)
+    ///
+    /// ```
+    /// # use simple_dns::{rdata::TLSA, Name};
+    /// let owner = TLSA::owner_name(25, "tcp", &Name::new("mail.example.com")?)?;
+    /// assert_eq!(owner.to_string(), "_25._tcp.mail.example.com");
+    /// # Ok::<(), simple_dns::SimpleDnsError>(())
+    /// ```
+    pub fn owner_name(port: u16, protocol: &str, host: &Name<'_>) -> crate::Result<Name<'static>> {
+        let mut labels = Vec::with_capacity(host.get_labels().len() + 2);
+        labels.push(Label::new(format!("_{port}").into_bytes())?);
+        labels.push(Label::new(format!("_{protocol}").into_bytes())?);
+        labels.extend(host.get_labels().iter().map(|l| l.clone().into_owned()));
+
+        let name = Name::new_with_labels(&labels);
+        if name.len() > MAX_NAME_LENGTH {
+            return Err(crate::SimpleDnsError::NameTooLong);
+        }
+
+        Ok(name)
+    }
+
+    /// Returns true if the DER encoded `certificate` matches this record, comparing the part of
+    /// the certificate given by the selector, with the digest calculated by the `digest_provider`.
+    ///
+    /// Only the association data is checked, the certificate usage must be applied by the caller
+    /// to the certificate chain presented by the server
+    pub fn matches_certificate<D: DigestProvider + ?Sized>(
+        &self,
+        certificate: &[u8],
+        digest_provider: &D,
+    ) -> bool {
+        let selected = match self.selector {
+            Selector::FullCertificate => Some(certificate),
+            Selector::SubjectPublicKeyInfo => subject_public_key_info(certificate),
+            _ => None,
+        };
+
+        selected.is_some_and(|data| self.matches_data(data, digest_provider))
+    }
+
+    /// Returns true if the DER encoded SubjectPublicKeyInfo `public_key` matches this record,
+    /// with the digest calculated by the `digest_provider`.
+    ///
+    /// Returns false if the selector is not [`Selector::SubjectPublicKeyInfo`]
+    pub fn matches_public_key<D: DigestProvider + ?Sized>(
+        &self,
+        public_key: &[u8],
+        digest_provider: &D,
+    ) -> bool {
+        self.selector == Selector::SubjectPublicKeyInfo
+            && self.matches_data(public_key, digest_provider)
+    }
+
+    fn matches_data<D: DigestProvider + ?Sized>(&self, data: &[u8], digest_provider: &D) -> bool {
+        let algorithm = match self.matching_type {
+            MatchingType::Full => return data == &self.certificate_data[..],
+            MatchingType::Sha256 => DigestAlgorithm::Sha256,
+            MatchingType::Sha512 => DigestAlgorithm::Sha512,
+            _ => return false,
+        };
+
+        digest_provider
+            .digest(algorithm, data)
+            .is_some_and(|digest| digest == self.certificate_data[..])
+    }
+}
+
+impl SMIMEA<'_> {
+    /// Returns the owner name of the SMIMEA records of an email address, in the
+    /// `<hash>._smimecert.<domain>` format, where the hash is the first 28 octets of the SHA-256
+    /// digest of the local-part, [RFC 8162](https://www.rfc-editor.org/rfc/rfc8162#section-3).
+    /// See [`OPENPGPKEY::owner_name`](super::OPENPGPKEY::owner_name)
+    pub fn owner_name<D: DigestProvider + ?Sized>(
+        email: &str,
+        digest_provider: &D,
+    ) -> crate::Result<Name<'static>> {
+        email_owner_name(email, "_smimecert", digest_provider)
+    }
+}
+
+/// Reads the DER element at the start of `data`, returns its tag, its length including the header
+/// and its contents
+fn der_element(data: &[u8]) -> Option<(u8, usize, &[u8])> {
+    let (&tag, rest) = data.split_first()?;
+    let (&first, rest) = rest.split_first()?;
+    let (length, rest) = match first {
+        0..=0x7f => (first as usize, rest),
+        0x81..=0x84 => {
+            let octets = (first & 0x7f) as usize;
+            let length = rest
+                .get(..octets)?
+                .iter()
+                .fold(0usize, |length, byte| length << 8 | *byte as usize);
+            (length, &rest[octets..])
+        }
+        _ => return None,
+    };
+
+    let header_len = data.len() - rest.len();
+    Some((tag, header_len.checked_add(length)?, rest.get(..length)?))
+}
+
+/// Extracts the SubjectPublicKeyInfo of a DER encoded X.509 certificate,
+/// [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#section-4.1)
+fn subject_public_key_info(certificate: &[u8]) -> Option<&[u8]> {
+    const SEQUENCE: u8 = 0x30;
+    const VERSION: u8 = 0xa0;
+
+    let (SEQUENCE, _, certificate) = der_element(certificate)? else {
+        return None;
+    };
+    let (SEQUENCE, _, mut fields) = der_element(certificate)? else {
+        return None;
+    };
+
+    if fields.first() == Some(&VERSION) {
+        fields = &fields[der_element(fields)?.1..];
+    }
+
+    // serialNumber, signature, issuer, validity and subject
+    for _ in 0..5 {
+        fields = &fields[der_element(fields)?.1..];
+    }
+
+    match der_element(fields)? {
+        (SEQUENCE, length, _) => Some(&fields[..length]),
+        _ => None,
+    }
+}
+
+impl PresentationFormat for TLSA<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            certificate_usage: tokens.next_value::<u8>()?.into(),
+            selector: tokens.next_value::<u8>()?.into(),
+            matching_type: tokens.next_value::<u8>()?.into(),
+            certificate_data: Cow::Owned(tokens.remaining_hex()?),
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} ",
+            u8::from(self.certificate_usage),
+            u8::from(self.selector),
+            u8::from(self.matching_type)
+        )?;
+        write_hex(f, &self.certificate_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::dnssec::sha::{self, Sha};
+    use crate::dns::presentation_format::{parse_text, to_text};
+    use crate::lib::{vec, ToString};
+    use crate::rdata::{RData, SMIMEA};
+    use crate::TYPE;
+
+    fn der(tag: u8, contents: &[u8]) -> Vec<u8> {
+        let mut element = vec![tag];
+        if contents.len() < 0x80 {
+            element.push(contents.len() as u8);
+        } else {
+            element.push(0x82);
+            element.extend_from_slice(&(contents.len() as u16).to_be_bytes());
+        }
+        element.extend_from_slice(contents);
+        element
+    }
+
+    /// A certificate with the structure of X.509, without meaningful contents
+    fn x509(with_version: bool, spki: &[u8]) -> Vec<u8> {
+        let mut tbs = Vec::new();
+        if with_version {
+            tbs.extend(der(0xa0, &der(0x02, &[2])));
+        }
+        tbs.extend(der(0x02, &[1]));
+        tbs.extend(der(0x30, &der(0x06, &[0x2a, 0x86, 0x48])));
+        tbs.extend(der(0x30, &[0; 200]));
+        tbs.extend(der(0x30, &[]));
+        tbs.extend(der(0x30, &[0; 20]));
+        tbs.extend_from_slice(spki);
+        tbs.extend(der(0xa3, &[0; 10]));
+
+        let mut certificate = der(0x30, &tbs);
+        certificate.extend(der(0x30, &der(0x06, &[0x2a, 0x86, 0x48])));
+        certificate.extend(der(0x03, &[0; 64]));
+        der(0x30, &certificate)
+    }
+
+    #[test]
+    fn parse_and_write_tlsa() {
+        let tlsa = TLSA {
+            certificate_usage: CertificateUsage::DaneEe,
+            selector: Selector::SubjectPublicKeyInfo,
+            matching_type: MatchingType::Sha256,
+            certificate_data: Cow::Borrowed(&[1, 2, 3, 4, 5]),
+        };
+
+        let mut data = Vec::new();
+        tlsa.write_to(&mut data).unwrap();
+        assert_eq!(data, [3, 1, 1, 1, 2, 3, 4, 5]);
+        assert_eq!(data.len(), tlsa.len());
+
+        let parsed = TLSA::parse(&mut data[..].into()).unwrap();
+        assert_eq!(parsed, tlsa);
+
+        let parsed = TLSA::parse(&mut [4u8, 255, 9][..].into()).unwrap();
+        assert_eq!(parsed.certificate_usage, CertificateUsage::Unknown(4));
+        assert_eq!(parsed.selector, Selector::PrivSel);
+        assert_eq!(parsed.matching_type, MatchingType::Unknown(9));
+        assert!(parsed.certificate_data.is_empty());
+    }
+
+    #[test]
+    fn presentation_format() {
+        let text = "3 1 1 0C72AC70B745AC19998811B131D662C9AC69DBDBE7CB23E5B514B566 64C5D3D6";
+        let tlsa: TLSA = parse_text(text).unwrap();
+        assert_eq!(tlsa.certificate_usage, CertificateUsage::DaneEe);
+        assert_eq!(tlsa.selector, Selector::SubjectPublicKeyInfo);
+        assert_eq!(tlsa.matching_type, MatchingType::Sha256);
+        assert_eq!(tlsa.certificate_data.len(), 32);
+        assert_eq!(to_text(&tlsa), text);
+
+        let smimea = RData::from_str(TYPE::SMIMEA, text).unwrap();
+        assert_eq!(smimea, RData::SMIMEA(SMIMEA(tlsa)));
+        assert_eq!(smimea.to_string(), text);
+    }
+
+    #[test]
+    fn owner_name() {
+        let host = Name::new("mail.example.com").unwrap();
+        assert_eq!(
+            TLSA::owner_name(25, "tcp", &host).unwrap(),
+            Name::new("_25._tcp.mail.example.com").unwrap()
+        );
+        assert!(TLSA::owner_name(443, "t.cp", &host).is_err());
+
+        let long_host = [
+            "a".repeat(63),
+            "a".repeat(63),
+            "a".repeat(63),
+            "a".repeat(59),
+        ]
+        .join(".");
+        assert_eq!(
+            TLSA::owner_name(25, "tcp", &Name::new(&long_host).unwrap()),
+            Err(crate::SimpleDnsError::NameTooLong)
+        );
+
+        // RFC 8162 section 3
+        assert_eq!(
+            SMIMEA::owner_name("hugh@example.com", &Sha)
+                .unwrap()
+                .to_string(),
+            "c93f1e400f26708f98cb19d936620da35eec8f72e57f9eec01c1afd6._smimecert.example.com"
+        );
+    }
+
+    #[test]
+    fn match_certificate() {
+        let spki = der(
+            0x30,
+            &[der(0x30, &[0x06, 0x01, 0x2a]), der(0x03, &[0; 33])].concat(),
+        );
+        let certificate = x509(true, &spki);
+
+        let tlsa = |selector, matching_type, data: Vec<u8>| TLSA {
+            certificate_usage: CertificateUsage::DaneEe,
+            selector,
+            matching_type,
+            certificate_data: data.into(),
+        };
+
+        let full = tlsa(
+            Selector::FullCertificate,
+            MatchingType::Full,
+            certificate.clone(),
+        );
+        assert!(full.matches_certificate(&certificate, &Sha));
+        assert!(!full.matches_public_key(&spki, &Sha));
+
+        let digest = tlsa(
+            Selector::FullCertificate,
+            MatchingType::Sha256,
+            sha::sha256(&certificate).to_vec(),
+        );
+        assert!(digest.matches_certificate(&certificate, &Sha));
+        assert!(!digest.matches_certificate(&spki, &Sha));
+
+        let public_key = tlsa(
+            Selector::SubjectPublicKeyInfo,
+            MatchingType::Sha256,
+            sha::sha256(&spki).to_vec(),
+        );
+        assert!(public_key.matches_certificate(&certificate, &Sha));
+        assert!(public_key.matches_certificate(&x509(false, &spki), &Sha));
+        assert!(public_key.matches_public_key(&spki, &Sha));
+        assert!(!public_key.matches_certificate(&certificate[..100], &Sha));
+
+        // SHA-512 is not supported by the provider
+        let unsupported = tlsa(
+            Selector::SubjectPublicKeyInfo,
+            MatchingType::Sha512,
+            sha::sha256(&spki).to_vec(),
+        );
+        assert!(!unsupported.matches_public_key(&spki, &Sha));
+    }
+}
@@ -0,0 +1,277 @@
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{PresentationFormat, Text, TokenReader},
+        Name, WireFormat,
+    },
+    encoding::base64,
+    lib::{fmt, Cow, Write},
+    SimpleDnsError,
+};
+
+use super::RR;
+
+/// The largest value of the 48 bits time signed field
+const MAX_TIME_SIGNED: u64 = 0xFFFF_FFFF_FFFF;
+
+/// A TSIG pseudo record, used to authenticate DNS messages with a shared secret,
+/// see [rfc8945](https://www.rfc-editor.org/rfc/rfc8945#section-4.2)
+///
+/// TSIG records are not added to the additional records section directly, use
+/// [`Packet::sign_tsig`](crate::Packet::sign_tsig) and
+/// [`Packet::verify_tsig`](crate::Packet::verify_tsig) instead
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct TSIG<'a> {
+    /// The name of the MAC algorithm, see [`crate::tsig`] for the known algorithms
+    pub algorithm: Name<'a>,
+    /// When the message was signed (seconds since Jan 1 1970), only the lower 48 bits are sent
+    pub time_signed: u64,
+    /// Permitted error in the time signed, in seconds
+    pub fudge: u16,
+    /// The message authentication code
+    pub mac: Cow<'a, [u8]>,
+    /// The ID of the message when it was signed
+    pub original_id: u16,
+    /// Extended RCODE covering TSIG processing
+    pub error: u16,
+    /// Additional data, holds the server time when the error is BADTIME
+    pub other_data: Cow<'a, [u8]>,
+}
+
+impl RR for TSIG<'_> {
+    const TYPE_CODE: u16 = 250;
+}
+
+impl<'a> WireFormat<'a> for TSIG<'a> {
+    const MINIMUM_LEN: usize = 16;
+
+    fn parse(data: &mut BytesBuffer<'a>) -> crate::Result<Self>
+    where
+        Self: Sized,
+    {
+        let algorithm = Name::parse(data)?;
+        let time_signed = (data.get_u16()? as u64) << 32 | data.get_u32()? as u64;
+        let fudge = data.get_u16()?;
+        let mac_size = data.get_u16()? as usize;
+        let mac = Cow::Borrowed(data.get_slice(mac_size)?);
+        let original_id = data.get_u16()?;
+        let error = data.get_u16()?;
+        let other_len = data.get_u16()? as usize;
+        let other_data = Cow::Borrowed(data.get_slice(other_len)?);
+
+        Ok(Self {
+            algorithm,
+            time_signed,
+            fudge,
+            mac,
+            original_id,
+            error,
+            other_data,
+        })
+    }
+
+    fn write_to<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        let mac_size =
+            u16::try_from(self.mac.len()).map_err(|_| SimpleDnsError::InvalidDnsPacket)?;
+        let other_len =
+            u16::try_from(self.other_data.len()).map_err(|_| SimpleDnsError::InvalidDnsPacket)?;
+
+        self.algorithm.write_to(out)?;
+        self.write_timers(out)?;
+        out.write_all(&mac_size.to_be_bytes())?;
+        out.write_all(&self.mac)?;
+        out.write_all(&self.original_id.to_be_bytes())?;
+        out.write_all(&self.error.to_be_bytes())?;
+        out.write_all(&other_len.to_be_bytes())?;
+        out.write_all(&self.other_data)?;
+
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.algorithm.len() + self.mac.len() + self.other_data.len() + Self::MINIMUM_LEN
+    }
+}
+
+impl TSIG<'_> {
+    /// Transforms the inner data into its owned type
+    pub fn into_owned<'b>(self) -> TSIG<'b> {
+        TSIG {
+            algorithm: self.algorithm.into_owned(),
+            time_signed: self.time_signed,
+            fudge: self.fudge,
+            mac: self.mac.into_owned().into(),
+            original_id: self.original_id,
+            error: self.error,
+            other_data: self.other_data.into_owned().into(),
+        }
+    }
+
+    /// Writes the time signed and the fudge fields, the "TSIG timers"
+    pub(crate) fn write_timers<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        if self.time_signed > MAX_TIME_SIGNED {
+            return Err(SimpleDnsError::InvalidDnsPacket);
+        }
+
+        out.write_all(&self.time_signed.to_be_bytes()[2..])?;
+        out.write_all(&self.fudge.to_be_bytes())?;
+        Ok(())
+    }
+}
+
+/// Mnemonics of the error field of TSIG and TKEY records,
+/// see [RFC 8945](https://www.rfc-editor.org/rfc/rfc8945#section-3) and
+/// [RFC 2930](https://www.rfc-editor.org/rfc/rfc2930#section-2.6)
+const ERROR_MNEMONICS: [(u16, &str); 19] = [
+    (0, "NOERROR"),
+    (1, "FORMERR"),
+    (2, "SERVFAIL"),
+    (3, "NXDOMAIN"),
+    (4, "NOTIMP"),
+    (5, "REFUSED"),
+    (6, "YXDOMAIN"),
+    (7, "YXRRSET"),
+    (8, "NXRRSET"),
+    (9, "NOTAUTH"),
+    (10, "NOTZONE"),
+    (16, "BADSIG"),
+    (17, "BADKEY"),
+    (18, "BADTIME"),
+    (19, "BADMODE"),
+    (20, "BADNAME"),
+    (21, "BADALG"),
+    (22, "BADTRUNC"),
+    (23, "BADCOOKIE"),
+];
+
+/// Parses an error field from its mnemonic or its numeric value
+pub(super) fn parse_error(text: &str) -> crate::Result<u16> {
+    ERROR_MNEMONICS
+        .iter()
+        .find(|(_, mnemonic)| mnemonic.eq_ignore_ascii_case(text))
+        .map(|(code, _)| Ok(*code))
+        .unwrap_or_else(|| {
+            text.parse()
+                .map_err(|_| SimpleDnsError::InvalidPresentationFormat)
+        })
+}
+
+/// Writes an error field with its mnemonic, or its numeric value when it has none
+pub(super) fn write_error(f: &mut fmt::Formatter<'_>, error: u16) -> fmt::Result {
+    match ERROR_MNEMONICS.iter().find(|(code, _)| *code == error) {
+        Some((_, mnemonic)) => f.write_str(mnemonic),
+        None => write!(f, "{error}"),
+    }
+}
+
+/// Parses a `<length> [<base64>]` pair of tokens, as used by the MAC and the other data
+pub(super) fn parse_sized_base64(
+    tokens: &mut TokenReader<'_>,
+) -> crate::Result<Cow<'static, [u8]>> {
+    let size: usize = tokens.next_value()?;
+    if size == 0 {
+        return Ok(Cow::Borrowed(&[]));
+    }
+
+    let data = base64::decode(tokens.next_text()?.as_bytes())?;
+    if data.len() != size {
+        return Err(SimpleDnsError::InvalidPresentationFormat);
+    }
+
+    Ok(Cow::Owned(data))
+}
+
+/// Writes a `<length> [<base64>]` pair of tokens, as used by the MAC and the other data
+pub(super) fn write_sized_base64(f: &mut fmt::Formatter<'_>, data: &[u8]) -> fmt::Result {
+    write!(f, "{}", data.len())?;
+    if !data.is_empty() {
+        write!(f, " {}", base64::encode(data))?;
+    }
+    Ok(())
+}
+
+impl PresentationFormat for TSIG<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        let algorithm = tokens.next_name()?;
+        let time_signed = tokens.next_value()?;
+        if time_signed > MAX_TIME_SIGNED {
+            return Err(SimpleDnsError::InvalidPresentationFormat);
+        }
+
+        Ok(Self {
+            algorithm,
+            time_signed,
+            fudge: tokens.next_value()?,
+            mac: parse_sized_base64(tokens)?,
+            original_id: tokens.next_value()?,
+            error: parse_error(tokens.next_text()?)?,
+            other_data: parse_sized_base64(tokens)?,
+        })
+    }
+
+    // Same format used by BIND, the time signed is written as a number of seconds
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} ",
+            Text(&self.algorithm),
+            self.time_signed,
+            self.fudge
+        )?;
+        write_sized_base64(f, &self.mac)?;
+        write!(f, " {} ", self.original_id)?;
+        write_error(f, self.error)?;
+        f.write_str(" ")?;
+        write_sized_base64(f, &self.other_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::presentation_format::{parse_text, to_text};
+    use crate::lib::Vec;
+
+    #[test]
+    fn parse_and_write_tsig() {
+        let text = "hmac-sha256. 1700000000 300 4 3q2+7w== 4660 NOERROR 0";
+        let tsig: TSIG = parse_text(text).unwrap();
+        assert_eq!(tsig.algorithm, Name::new_unchecked("hmac-sha256"));
+        assert_eq!(tsig.time_signed, 1_700_000_000);
+        assert_eq!(tsig.mac[..], [0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(tsig.original_id, 0x1234);
+        assert_eq!(to_text(&tsig), text);
+
+        let mut data = Vec::new();
+        tsig.write_to(&mut data).unwrap();
+        assert_eq!(data.len(), tsig.len());
+        assert_eq!(
+            data[13..],
+            [
+                0, 0, 0x65, 0x53, 0xf1, 0, 1, 0x2c, 0, 4, 0xde, 0xad, 0xbe, 0xef, 0x12, 0x34, 0, 0,
+                0, 0
+            ]
+        );
+
+        let parsed = TSIG::parse(&mut data[..].into()).unwrap();
+        assert_eq!(parsed, tsig);
+    }
+
+    #[test]
+    fn parse_and_write_error_and_other_data() {
+        let text = "hmac-sha256. 1700000000 300 0 4660 BADTIME 6 AABlU/FA";
+        let tsig: TSIG = parse_text(text).unwrap();
+        assert_eq!(tsig.error, 18);
+        assert_eq!(tsig.other_data[..], [0, 0, 0x65, 0x53, 0xf1, 0x40]);
+        assert_eq!(to_text(&tsig), text);
+
+        let tsig = TSIG { error: 42, ..tsig };
+        assert_eq!(
+            to_text(&tsig),
+            "hmac-sha256. 1700000000 300 0 4660 42 6 AABlU/FA"
+        );
+
+        assert!(parse_text::<TSIG>("hmac-sha256. 1700000000 300 2 3q2+7w== 4660 0 0").is_err());
+        assert!(parse_text::<TSIG>("hmac-sha256. 281474976710656 300 0 4660 0 0").is_err());
+    }
+}
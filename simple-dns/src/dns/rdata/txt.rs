@@ -1,8 +1,10 @@
 use crate::CharacterString;
 use crate::{
-    dns::{WireFormat, MAX_CHARACTER_STRING_LENGTH},
-    lib::{vec, FromUtf8Error, String, Vec},
-    lib::Write,
+    dns::{
+        presentation_format::{PresentationFormat, TokenReader},
+        WireFormat, MAX_CHARACTER_STRING_LENGTH,
+    },
+    lib::{fmt, vec, FromUtf8Error, String, Vec, Write},
 };
 
 use super::RR;
@@ -228,6 +230,35 @@ impl<'a> WireFormat<'a> for TXT<'a> {
     }
 }
 
+impl PresentationFormat for TXT<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        let mut txt = TXT::new();
+        while tokens.has_remaining() {
+            txt.add_char_string(tokens.next_character_string()?);
+        }
+
+        if txt.strings.is_empty() {
+            return Err(crate::SimpleDnsError::InvalidPresentationFormat);
+        }
+
+        Ok(txt)
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.strings.is_empty() {
+            return f.write_str("\"\"");
+        }
+
+        for (i, string) in self.strings.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            string.write_text(f)?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
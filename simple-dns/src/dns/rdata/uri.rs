@@ -0,0 +1,213 @@
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{write_quoted, PresentationFormat, TokenReader},
+        WireFormat,
+    },
+    lib::{fmt, Cow, Vec, Write},
+};
+
+use super::RR;
+
+/// URI records publish mappings from hostnames to URIs, [RFC 7553](https://www.rfc-editor.org/rfc/rfc7553)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct URI<'a> {
+    /// The priority of the target URI, clients must try the lowest-numbered priority first
+    pub priority: u16,
+    /// A relative weight for entries with the same priority, larger weights have a proportionately
+    /// higher probability of being selected
+    pub weight: u16,
+    /// The URI of the target, it is not a character-string and extends until the end of the rdata
+    pub target: Cow<'a, [u8]>,
+}
+
+impl RR for URI<'_> {
+    const TYPE_CODE: u16 = 256;
+}
+
+impl<'a> URI<'a> {
+    /// Transforms the inner data into its owned type
+    pub fn into_owned<'b>(self) -> URI<'b> {
+        URI {
+            priority: self.priority,
+            weight: self.weight,
+            target: self.target.into_owned().into(),
+        }
+    }
+
+    /// Returns `records` in the order they should be contacted, using the same selection as SRV
+    /// records, described in [RFC 2782](https://www.rfc-editor.org/rfc/rfc2782).
+    ///
+    /// Records are sorted by priority, lowest first. Inside each priority, records are picked at
+    /// random proportionally to their weight. `random(total)` must return a random number between
+    /// 0 and `total`, inclusive.
+    ///
+    /// ```
+    /// # use simple_dns::rdata::URI;
+    /// let records = [
+    ///     URI { priority: 20, weight: 0, target: b"ftp://backup.example.com/".into() },
+    ///     URI { priority: 10, weight: 0, target: b"ftp://ftp.example.com/".into() },
+    /// ];
+    ///
+    /// let ordered = URI::selection_order(&records, |total| total / 2);
+    /// assert_eq!(&*ordered[0].target, b"ftp://ftp.example.com/");
+    /// ```
+    pub fn selection_order<'r>(
+        records: &'r [URI<'a>],
+        mut random: impl FnMut(u32) -> u32,
+    ) -> Vec<&'r URI<'a>> {
+        let mut sorted: Vec<&URI> = records.iter().collect();
+        // Records with weight 0 go first inside each priority, so they have a small chance of
+        // being selected
+        sorted.sort_by_key(|record| (record.priority, record.weight != 0));
+
+        let mut ordered = Vec::with_capacity(sorted.len());
+        let mut start = 0;
+        while start < sorted.len() {
+            let priority = sorted[start].priority;
+            let end = sorted[start..]
+                .iter()
+                .position(|record| record.priority != priority)
+                .map_or(sorted.len(), |len| start + len);
+
+            let mut candidates = sorted[start..end].to_vec();
+            while !candidates.is_empty() {
+                let total: u32 = candidates.iter().map(|record| record.weight as u32).sum();
+                let selected = random(total).min(total);
+
+                let mut running_sum = 0;
+                let index = candidates
+                    .iter()
+                    .position(|record| {
+                        running_sum += record.weight as u32;
+                        running_sum >= selected
+                    })
+                    .unwrap_or_default();
+                ordered.push(candidates.remove(index));
+            }
+
+            start = end;
+        }
+
+        ordered
+    }
+}
+
+impl<'a> WireFormat<'a> for URI<'a> {
+    const MINIMUM_LEN: usize = 4;
+
+    fn parse(data: &mut BytesBuffer<'a>) -> crate::Result<Self>
+    where
+        Self: Sized,
+    {
+        let priority = data.get_u16()?;
+        let weight = data.get_u16()?;
+        let target = Cow::Borrowed(data.get_remaining());
+
+        Ok(Self {
+            priority,
+            weight,
+            target,
+        })
+    }
+
+    fn write_to<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        out.write_all(&self.priority.to_be_bytes())?;
+        out.write_all(&self.weight.to_be_bytes())?;
+        out.write_all(&self.target)?;
+
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.target.len() + Self::MINIMUM_LEN
+    }
+}
+
+impl PresentationFormat for URI<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            priority: tokens.next_value()?,
+            weight: tokens.next_value()?,
+            target: Cow::Owned(tokens.next_bytes()?),
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} ", self.priority, self.weight)?;
+        write_quoted(f, &self.target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::presentation_format::{parse_text, to_text};
+
+    #[test]
+    fn parse_and_write_uri() {
+        let uri = URI {
+            priority: 10,
+            weight: 1,
+            target: Cow::Borrowed(b"ftp://ftp1.example.com/public"),
+        };
+
+        let mut data = Vec::new();
+        uri.write_to(&mut data).unwrap();
+        assert_eq!(&data[..4], [0, 10, 0, 1]);
+        assert_eq!(&data[4..], b"ftp://ftp1.example.com/public");
+        assert_eq!(data.len(), uri.len());
+
+        let parsed = URI::parse(&mut data[..].into()).unwrap();
+        assert_eq!(parsed, uri);
+
+        let text = "10 1 \"ftp://ftp1.example.com/public\"";
+        assert_eq!(to_text(&uri), text);
+        assert_eq!(parse_text::<URI>(text).unwrap(), uri);
+    }
+
+    #[test]
+    fn selection_order() {
+        let uri = |priority, weight, target: &'static [u8]| URI {
+            priority,
+            weight,
+            target: Cow::Borrowed(target),
+        };
+        let records = [
+            uri(20, 10, b"c"),
+            uri(10, 30, b"a"),
+            uri(30, 0, b"e"),
+            uri(10, 0, b"z"),
+            uri(10, 70, b"b"),
+            uri(20, 10, b"d"),
+        ];
+        let targets = |ordered: Vec<&URI>| {
+            ordered
+                .iter()
+                .map(|record| record.target[0])
+                .collect::<Vec<_>>()
+        };
+
+        // The lowest random value always selects the first candidate, weight 0 records first
+        assert_eq!(targets(URI::selection_order(&records, |_| 0)), b"zabcde");
+
+        // The highest random value always selects the last candidate
+        assert_eq!(
+            targets(URI::selection_order(&records, |total| total)),
+            b"bazdce"
+        );
+
+        // Out of range values are clamped to the total weight
+        assert_eq!(
+            targets(URI::selection_order(&records, |_| u32::MAX)),
+            b"bazdce"
+        );
+
+        // Running sums of the first priority are 0, 30 and 100, 31 selects `b`
+        let mut values = [31, 0, 0, 0, 0, 0].into_iter();
+        assert_eq!(
+            targets(URI::selection_order(&records, |_| values.next().unwrap())),
+            b"bzacde"
+        );
+    }
+}
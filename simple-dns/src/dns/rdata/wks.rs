@@ -1,6 +1,12 @@
-use crate::lib::Cow;
 use crate::lib::Write;
-use crate::{bytes_buffer::BytesBuffer, dns::WireFormat};
+use crate::{
+    bytes_buffer::BytesBuffer,
+    dns::{
+        presentation_format::{PresentationFormat, TokenReader},
+        WireFormat,
+    },
+    lib::{fmt, Cow, Ipv4Addr, Vec},
+};
 
 use super::RR;
 
@@ -61,6 +67,86 @@ impl<'a> WireFormat<'a> for WKS<'a> {
     }
 }
 
+/// Well known service names accepted in the presentation format, services are always written
+/// using their port number
+const SERVICES: &[(&str, u16)] = &[
+    ("echo", 7),
+    ("discard", 9),
+    ("daytime", 13),
+    ("ftp-data", 20),
+    ("ftp", 21),
+    ("ssh", 22),
+    ("telnet", 23),
+    ("smtp", 25),
+    ("time", 37),
+    ("whois", 43),
+    ("domain", 53),
+    ("tftp", 69),
+    ("gopher", 70),
+    ("finger", 79),
+    ("http", 80),
+    ("kerberos", 88),
+    ("pop3", 110),
+    ("sunrpc", 111),
+    ("nntp", 119),
+    ("ntp", 123),
+    ("imap", 143),
+    ("snmp", 161),
+    ("bgp", 179),
+    ("ldap", 389),
+    ("https", 443),
+];
+
+impl PresentationFormat for WKS<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        let address: Ipv4Addr = tokens.next_value()?;
+        let protocol = match tokens.next_text()? {
+            protocol if protocol.eq_ignore_ascii_case("tcp") => 6,
+            protocol if protocol.eq_ignore_ascii_case("udp") => 17,
+            protocol => protocol
+                .parse()
+                .map_err(|_| crate::SimpleDnsError::InvalidPresentationFormat)?,
+        };
+
+        let mut bit_map = Vec::new();
+        while tokens.has_remaining() {
+            let port = tokens.next_text()?;
+            let port = match SERVICES
+                .iter()
+                .find(|(service, _)| service.eq_ignore_ascii_case(port))
+            {
+                Some((_, port)) => *port,
+                None => port
+                    .parse::<u16>()
+                    .map_err(|_| crate::SimpleDnsError::InvalidPresentationFormat)?,
+            };
+            let position = port as usize / 8;
+            if bit_map.len() <= position {
+                bit_map.resize(position + 1, 0);
+            }
+            bit_map[position] |= 0x80 >> (port % 8);
+        }
+
+        Ok(Self {
+            address: address.into(),
+            protocol,
+            bit_map: Cow::Owned(bit_map),
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", Ipv4Addr::from(self.address), self.protocol)?;
+        for (position, byte) in self.bit_map.iter().enumerate() {
+            for bit in 0..8 {
+                if byte & (0x80 >> bit) != 0 {
+                    write!(f, " {}", position * 8 + bit)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -86,4 +172,17 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn parse_presentation_format() {
+        use super::WKS;
+        use crate::dns::presentation_format::{parse_text, to_text};
+
+        let wks: WKS = parse_text("10.0.0.1 tcp telnet FTP 0 1 2").unwrap();
+        assert_eq!(wks.protocol, 6);
+        assert_eq!(*wks.bit_map, [0b1110_0000, 0, 0b0000_0101]);
+        assert_eq!(to_text(&wks), "10.0.0.1 6 0 1 2 21 23");
+
+        assert!(parse_text::<WKS>("10.0.0.1 udp unknown-service").is_err());
+    }
 }
@@ -1,7 +1,10 @@
 use crate::{
     bytes_buffer::BytesBuffer,
-    dns::WireFormat,
-    lib::{Cow, Write},
+    dns::{
+        presentation_format::{write_hex, PresentationFormat, TokenReader},
+        WireFormat,
+    },
+    lib::{fmt, Cow, Write},
 };
 
 use super::RR;
@@ -69,6 +72,22 @@ impl ZONEMD<'_> {
     }
 }
 
+impl PresentationFormat for ZONEMD<'_> {
+    fn parse_text(tokens: &mut TokenReader<'_>) -> crate::Result<Self> {
+        Ok(Self {
+            serial: tokens.next_value()?,
+            scheme: tokens.next_value()?,
+            algorithm: tokens.next_value()?,
+            digest: Cow::Owned(tokens.remaining_hex()?),
+        })
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {} ", self.serial, self.scheme, self.algorithm)?;
+        write_hex(f, &self.digest)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::lib::Vec;
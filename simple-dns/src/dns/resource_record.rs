@@ -4,18 +4,28 @@ use crate::{
     QCLASS, QTYPE,
 };
 
-use super::{rdata::RData, Name, WireFormat, CLASS, TYPE};
+use super::{
+    presentation_format::Text,
+    rdata::{RData, CNAME},
+    Name, WireFormat, CLASS, TYPE,
+};
 use core::fmt::Debug;
 
 mod flag {
     pub const CACHE_FLUSH: u16 = 0b1000_0000_0000_0000;
 }
+
+/// The class ANY, carried by the TSIG, TKEY and SIG(0) meta records
+pub(crate) const CLASS_ANY: u16 = 255;
+
 /// Resource Records are used to represent the answer, authority, and additional sections in DNS packets.
 #[derive(Debug, Eq, Clone)]
 pub struct ResourceRecord<'a> {
     /// A [`Name`] to which this resource record pertains.
     pub name: Name<'a>,
     /// A [`CLASS`] that defines the class of the rdata field
+    ///
+    /// Ignored by OPT, TSIG, TKEY and SIG(0) records, which are always written with their own class
     pub class: CLASS,
     /// The time interval (in seconds) that the resource record may becached before it should be discarded.  
     /// Zero values are interpreted to mean that the RR can only be used for the transaction in progress, and should not be cached.
@@ -50,6 +60,15 @@ impl<'a> ResourceRecord<'a> {
         self.clone().with_cache_flush(true)
     }
 
+    /// Returns true if this is a TSIG, TKEY or SIG(0) meta record, which always has the class ANY
+    fn has_class_any(rdata: &RData<'_>) -> bool {
+        match rdata {
+            RData::TSIG(_) | RData::TKEY(_) => true,
+            RData::SIG(sig) => sig.is_sig0(),
+            _ => false,
+        }
+    }
+
     /// Return true if current resource match given query class
     pub fn match_qclass(&self, qclass: QCLASS) -> bool {
         match qclass {
@@ -82,11 +101,47 @@ impl<'a> ResourceRecord<'a> {
         }
     }
 
+    /// Writes this record in canonical form, as defined by [RFC 4034](https://www.rfc-editor.org/rfc/rfc4034#section-6.2):
+    /// without name compression, with the owner name and the names embedded in the rdata
+    /// converted to lowercase. The cache flush flag is not part of the canonical form.
+    pub fn write_canonical_to<T: Write>(&self, out: &mut T) -> crate::Result<()> {
+        ResourceRecord {
+            name: self.name.to_canonical(),
+            class: self.class,
+            ttl: self.ttl,
+            rdata: self.rdata.to_canonical(),
+            cache_flush: false,
+        }
+        .write_to(out)
+    }
+
+    /// Returns the CNAME record synthesized from this DNAME record for `qname`, which a server
+    /// returns next to the DNAME record, see [RFC 6672](https://www.rfc-editor.org/rfc/rfc6672#section-3.1).
+    ///
+    /// The synthesized record is owned by `qname`, has the class and TTL of the DNAME record and
+    /// points to the substituted name, see [`Name::substitute_dname`].
+    /// Returns [`crate::SimpleDnsError::AttemptedInvalidOperation`] if this is not a DNAME record
+    pub fn synthesize_cname(&self, qname: &Name<'_>) -> crate::Result<ResourceRecord<'static>> {
+        let RData::DNAME(dname) = &self.rdata else {
+            return Err(crate::SimpleDnsError::AttemptedInvalidOperation);
+        };
+
+        let target = dname.substitute(&self.name, qname)?;
+        Ok(ResourceRecord::new(
+            qname.clone().into_owned(),
+            self.class,
+            self.ttl,
+            RData::CNAME(CNAME(target)),
+        ))
+    }
+
     fn write_common<T: Write>(&self, out: &mut T) -> crate::Result<()> {
         out.write_all(&u16::from(self.rdata.type_code()).to_be_bytes())?;
 
         if let RData::OPT(ref opt) = self.rdata {
             out.write_all(&opt.udp_packet_size.to_be_bytes())?;
+        } else if Self::has_class_any(&self.rdata) {
+            out.write_all(&CLASS_ANY.to_be_bytes())?;
         } else {
             let class = if self.cache_flush {
                 ((self.class as u16) | flag::CACHE_FLUSH).to_be_bytes()
@@ -116,7 +171,9 @@ impl<'a> WireFormat<'a> for ResourceRecord<'a> {
 
         let rdata = RData::parse(data)?;
 
-        if rdata.type_code() == TYPE::OPT {
+        if rdata.type_code() == TYPE::OPT
+            || (Self::has_class_any(&rdata) && class_value == CLASS_ANY)
+        {
             Ok(Self {
                 name,
                 class: CLASS::IN,
@@ -178,6 +235,37 @@ impl Hash for ResourceRecord<'_> {
     }
 }
 
+impl crate::lib::fmt::Display for ResourceRecord<'_> {
+    /// Formats the record in presentation format, ex: `example.com. 300 IN A 127.0.0.1`
+    fn fmt(&self, f: &mut crate::lib::fmt::Formatter<'_>) -> crate::lib::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {}",
+            Text(&self.name),
+            self.ttl,
+            match Self::has_class_any(&self.rdata) {
+                true => "ANY",
+                false => self.class.mnemonic(),
+            },
+            self.rdata.type_code(),
+            self.rdata
+        )
+    }
+}
+
+impl crate::lib::FromStr for ResourceRecord<'_> {
+    type Err = crate::SimpleDnsError;
+
+    /// Parses a resource record from its presentation format,
+    /// ex: `example.com. 300 IN MX 10 mail.example.com.`
+    ///
+    /// The class is optional and defaults to `IN`, the TTL is required, except for SOA records.
+    /// Names without a trailing dot are considered relative to the root.
+    fn from_str(s: &str) -> crate::Result<Self> {
+        crate::dns::zone::parse_record(s)
+    }
+}
+
 impl PartialEq for ResourceRecord<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.name == other.name && self.class == other.class && self.rdata == other.rdata
@@ -301,6 +389,35 @@ mod tests {
         assert!(!rr.match_qclass(CLASS::CS.into()));
     }
 
+    #[test]
+    fn meta_records_have_class_any() {
+        let sig0 = RData::from_str(
+            TYPE::SIG,
+            "TYPE0 15 0 0 20240101000500 20240101000000 3613 example.com. AQID",
+        )
+        .unwrap();
+        let rr = ResourceRecord::new(Name::new_unchecked(""), CLASS::IN, 0, sig0);
+
+        let mut out = Vec::new();
+        rr.write_to(&mut out).unwrap();
+        assert_eq!(out[1..5], [0, 24, 0, 255]);
+        assert_eq!(ResourceRecord::parse(&mut out[..].into()).unwrap(), rr);
+        assert_eq!(rr.to_string(), format!(". 0 ANY SIG {}", rr.rdata));
+
+        // Class ANY is only valid for meta records
+        let sig = RData::from_str(
+            TYPE::SIG,
+            "A 15 2 3600 20240101000500 20240101000000 3613 example.com. AQID",
+        )
+        .unwrap();
+        let mut out = Vec::new();
+        ResourceRecord::new(Name::new_unchecked(""), CLASS::IN, 0, sig)
+            .write_to(&mut out)
+            .unwrap();
+        out[4] = 255;
+        assert!(ResourceRecord::parse(&mut out[..].into()).is_err());
+    }
+
     #[test]
     fn test_match_qtype() {
         let rr = ResourceRecord {
@@ -316,6 +433,43 @@ mod tests {
         assert!(!rr.match_qtype(TYPE::WKS.into()));
     }
 
+    #[test]
+    fn test_synthesize_cname() {
+        let rr = ResourceRecord::new(
+            Name::new_unchecked("example.com"),
+            CLASS::IN,
+            3600,
+            RData::DNAME(crate::rdata::DNAME {
+                target: Name::new_unchecked("example.net"),
+            }),
+        );
+
+        let cname = rr
+            .synthesize_cname(&Name::new_unchecked("www.example.com"))
+            .unwrap();
+        assert_eq!(cname.name, Name::new_unchecked("www.example.com"));
+        assert_eq!(cname.class, CLASS::IN);
+        assert_eq!(cname.ttl, 3600);
+        assert_eq!(
+            cname.rdata,
+            RData::CNAME(CNAME(Name::new_unchecked("www.example.net")))
+        );
+
+        assert_eq!(
+            rr.synthesize_cname(&Name::new_unchecked("example.com")),
+            Err(crate::SimpleDnsError::AttemptedInvalidOperation)
+        );
+
+        let rr = ResourceRecord {
+            rdata: RData::A(crate::rdata::A { address: 0 }),
+            ..rr
+        };
+        assert_eq!(
+            rr.synthesize_cname(&Name::new_unchecked("www.example.com")),
+            Err(crate::SimpleDnsError::AttemptedInvalidOperation)
+        );
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn test_eq() {
@@ -378,4 +532,95 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_display() {
+        let rr = ResourceRecord::new(
+            Name::new_unchecked("example.com"),
+            CLASS::IN,
+            300,
+            RData::MX(crate::rdata::MX {
+                preference: 10,
+                exchange: Name::new_unchecked("mail.example.com"),
+            }),
+        );
+        assert_eq!(
+            rr.to_string(),
+            "example.com. 300 IN MX 10 mail.example.com."
+        );
+
+        let rr = ResourceRecord::new(
+            Name::new_unchecked("example.com"),
+            CLASS::NONE,
+            0,
+            RData::Empty(TYPE::A),
+        );
+        assert_eq!(rr.to_string(), "example.com. 0 NONE A \\# 0");
+
+        let rr = ResourceRecord::new(
+            Name::new_unchecked("example.com"),
+            CLASS::IN,
+            60,
+            RData::NULL(65280, NULL::new(&[0x0a, 0xff]).unwrap()),
+        );
+        assert_eq!(rr.to_string(), "example.com. 60 IN TYPE65280 \\# 2 0AFF");
+    }
+
+    #[test]
+    fn write_canonical() {
+        let rr: ResourceRecord = "Example.COM. 300 IN MX 10 Mail.Example.com."
+            .parse()
+            .unwrap();
+        let rr = rr.with_cache_flush(true);
+
+        let mut canonical = Vec::new();
+        rr.write_canonical_to(&mut canonical).unwrap();
+        assert_eq!(
+            canonical,
+            b"\x07example\x03com\x00\x00\x0f\x00\x01\x00\x00\x01\x2c\x00\x14\x00\x0a\x04mail\x07example\x03com\x00"
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        let rr: ResourceRecord = "example.com. 300 IN MX 10 mail.example.com."
+            .parse()
+            .unwrap();
+        assert_eq!(rr.name, Name::new_unchecked("example.com"));
+        assert_eq!(rr.class, CLASS::IN);
+        assert_eq!(rr.ttl, 300);
+        assert_eq!(
+            rr.rdata,
+            RData::MX(crate::rdata::MX {
+                preference: 10,
+                exchange: Name::new_unchecked("mail.example.com"),
+            })
+        );
+
+        let rr: ResourceRecord = "  example.com 60 A \\# 4 7F000001".parse().unwrap();
+        assert_eq!(rr.class, CLASS::IN);
+        assert_eq!(
+            rr.rdata,
+            RData::A(crate::rdata::A {
+                address: 0x7f000001
+            })
+        );
+
+        let rr: ResourceRecord = "example.com. CH 0 TXT \\# 0".parse().unwrap();
+        assert_eq!(rr.class, CLASS::CH);
+        assert_eq!(rr.rdata, RData::Empty(TYPE::TXT));
+
+        assert!("example.com. IN A 127.0.0.1"
+            .parse::<ResourceRecord>()
+            .is_err());
+        assert!("example.com. 60 IN A \\# 5 7F00000100"
+            .parse::<ResourceRecord>()
+            .is_err());
+        assert!(
+            "example.com. 60 IN A 127.0.0.1\nexample.com. 60 IN A 127.0.0.2"
+                .parse::<ResourceRecord>()
+                .is_err()
+        );
+        assert!(". 60 IN OPT \\# 0".parse::<ResourceRecord>().is_err());
+    }
 }
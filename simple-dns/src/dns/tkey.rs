@@ -0,0 +1,301 @@
+//! Transaction key establishment (TKEY), see [RFC 2930](https://www.rfc-editor.org/rfc/rfc2930)
+//!
+//! A client negotiates a TSIG key with a [`TkeyClient`], sending the queries it creates until a
+//! response completes the exchange. Servers answer TKEY queries with [`response`]. This crate
+//! does not implement any key agreement method, like Diffie-Hellman or GSS-API, the key data is
+//! produced and consumed by a [`KeyAgreement`] supplied by the caller.
+//!
+//! The established secret is used to sign messages with
+//! [`Packet::sign_tsig`](crate::Packet::sign_tsig), and deleted with a [`query`] in
+//! [`TkeyMode::KeyDeletion`] mode.
+
+use crate::{
+    lib::{Cow, Vec},
+    rdata::{RData, TkeyMode, TKEY},
+    tsig::TsigError,
+    Name, Packet, Question, ResourceRecord, SimpleDnsError, CLASS, QCLASS, TYPE,
+};
+
+/// Name of the GSS-API algorithm, used by GSS-TSIG keys, see
+/// [RFC 3645](https://www.rfc-editor.org/rfc/rfc3645#section-2)
+pub const GSS_TSIG: &str = "gss-tsig";
+
+/// Key agreement method used to establish the shared secret of a TKEY exchange.
+///
+/// `simple-dns` does not implement any key agreement method, this trait must be implemented
+/// on top of a cryptographic or GSS-API library
+pub trait KeyAgreement {
+    /// Returns the key data of the first query of the exchange, like the initial GSS-API token
+    fn initial_key_data(&mut self, mode: TkeyMode) -> crate::Result<Vec<u8>>;
+
+    /// Processes the key data of a response and returns the next step of the exchange
+    fn process(&mut self, mode: TkeyMode, key_data: &[u8]) -> crate::Result<KeyAgreementStep>;
+}
+
+/// Next step of a key agreement, after processing the key data of a response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyAgreementStep {
+    /// Another round is needed, holds the key data of the next query
+    Continue(Vec<u8>),
+    /// The exchange is complete, holds the shared secret
+    Complete(Vec<u8>),
+}
+
+/// Creates a TKEY query for `key_name`, with the `tkey` record in the additional records,
+/// as defined by [RFC 2930](https://www.rfc-editor.org/rfc/rfc2930#section-4)
+pub fn query<'a>(id: u16, key_name: Name<'a>, tkey: TKEY<'a>) -> Packet<'a> {
+    let mut packet = Packet::new_query(id);
+    packet.questions.push(Question::new(
+        key_name.clone(),
+        TYPE::TKEY.into(),
+        QCLASS::ANY,
+        false,
+    ));
+    packet.additional_records.push(ResourceRecord::new(
+        key_name,
+        CLASS::IN,
+        0,
+        RData::TKEY(tkey),
+    ));
+    packet
+}
+
+/// Creates the response to a TKEY `query`, with the `tkey` record in the answers.
+///
+/// Returns [`SimpleDnsError::InvalidDnsPacket`] if `query` does not hold a TKEY record
+pub fn response<'a>(query: &Packet<'a>, tkey: TKEY<'a>) -> crate::Result<Packet<'a>> {
+    let (key_name, _) = find_tkey(query).ok_or(SimpleDnsError::InvalidDnsPacket)?;
+
+    let mut packet = Packet::new_reply(query.id());
+    packet.questions.extend(query.questions.iter().cloned());
+    packet.answers.push(ResourceRecord::new(
+        key_name.clone(),
+        CLASS::IN,
+        0,
+        RData::TKEY(tkey),
+    ));
+    Ok(packet)
+}
+
+/// Returns the key name and the TKEY record of a response, from its answers, or of a query,
+/// from its additional records
+pub fn find_tkey<'p, 'a>(packet: &'p Packet<'a>) -> Option<(&'p Name<'a>, &'p TKEY<'a>)> {
+    packet
+        .answers
+        .iter()
+        .chain(packet.additional_records.iter())
+        .find_map(|rr| match &rr.rdata {
+            RData::TKEY(tkey) => Some((&rr.name, tkey)),
+            _ => None,
+        })
+}
+
+/// Client side of a TKEY exchange, negotiates a shared secret with a server.
+///
+/// Send the packet returned by [`TkeyClient::query`] and pass the response to
+/// [`TkeyClient::process_response`], until it returns the shared secret. Calling
+/// [`TkeyClient::query`] again before processing a response creates the same query, to be
+/// retransmitted
+#[derive(Debug)]
+pub struct TkeyClient<'a, A> {
+    key_name: Name<'a>,
+    algorithm: Name<'a>,
+    mode: TkeyMode,
+    inception: u32,
+    expiration: u32,
+    agreement: A,
+    key_data: Option<Vec<u8>>,
+}
+
+impl<'a, A: KeyAgreement> TkeyClient<'a, A> {
+    /// Creates a client establishing the key `key_name`, for the `algorithm`, using the `mode`
+    /// key agreement. The key is valid from `inception` until `expiration`, in seconds since
+    /// Jan 1 1970
+    pub fn new(
+        key_name: Name<'a>,
+        algorithm: Name<'a>,
+        mode: TkeyMode,
+        inception: u32,
+        expiration: u32,
+        agreement: A,
+    ) -> Self {
+        Self {
+            key_name,
+            algorithm,
+            mode,
+            inception,
+            expiration,
+            agreement,
+            key_data: None,
+        }
+    }
+
+    /// The name of the key being established
+    pub fn key_name(&self) -> &Name<'a> {
+        &self.key_name
+    }
+
+    /// Creates the next query of the exchange
+    pub fn query(&mut self, id: u16) -> crate::Result<Packet<'a>> {
+        let key_data = match &self.key_data {
+            Some(key_data) => key_data.clone(),
+            None => {
+                let key_data = self.agreement.initial_key_data(self.mode)?;
+                self.key_data = Some(key_data.clone());
+                key_data
+            }
+        };
+
+        let tkey = TKEY {
+            algorithm: self.algorithm.clone(),
+            inception: self.inception,
+            expiration: self.expiration,
+            mode: self.mode,
+            error: 0,
+            key_data: Cow::Owned(key_data),
+            other_data: Cow::Borrowed(&[]),
+        };
+        Ok(query(id, self.key_name.clone(), tkey))
+    }
+
+    /// Processes the response to the last query, returning the shared secret once the exchange
+    /// is complete, or None when another query must be sent.
+    ///
+    /// Returns [`SimpleDnsError::TsigFailed`] with [`TsigError::Rejected`] if the server
+    /// reported an error, and [`SimpleDnsError::InvalidDnsPacket`] if the response does not
+    /// hold a TKEY record for the key being established
+    pub fn process_response(&mut self, response: &Packet<'_>) -> crate::Result<Option<Vec<u8>>> {
+        let (key_name, tkey) = find_tkey(response).ok_or(SimpleDnsError::InvalidDnsPacket)?;
+        if key_name.to_canonical() != self.key_name.to_canonical() || tkey.mode != self.mode {
+            return Err(SimpleDnsError::InvalidDnsPacket);
+        }
+        if tkey.error != 0 {
+            return Err(SimpleDnsError::TsigFailed(TsigError::Rejected(tkey.error)));
+        }
+
+        match self.agreement.process(self.mode, &tkey.key_data)? {
+            KeyAgreementStep::Continue(key_data) => {
+                self.key_data = Some(key_data);
+                Ok(None)
+            }
+            KeyAgreementStep::Complete(secret) => {
+                self.key_data = None;
+                Ok(Some(secret))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INCEPTION: u32 = 1_700_000_000;
+    const EXPIRATION: u32 = INCEPTION + 3600;
+
+    /// Exchanges one token per round, the secret is the concatenation of the server tokens
+    struct Rounds {
+        secret: Vec<u8>,
+    }
+
+    impl KeyAgreement for Rounds {
+        fn initial_key_data(&mut self, _mode: TkeyMode) -> crate::Result<Vec<u8>> {
+            Ok(b"init".to_vec())
+        }
+
+        fn process(&mut self, _mode: TkeyMode, key_data: &[u8]) -> crate::Result<KeyAgreementStep> {
+            self.secret.extend_from_slice(key_data);
+            if key_data == b"done" {
+                Ok(KeyAgreementStep::Complete(self.secret.clone()))
+            } else {
+                Ok(KeyAgreementStep::Continue(b"next".to_vec()))
+            }
+        }
+    }
+
+    fn client() -> TkeyClient<'static, Rounds> {
+        TkeyClient::new(
+            Name::new_unchecked("key.example.com"),
+            Name::new_unchecked(GSS_TSIG),
+            TkeyMode::GssApi,
+            INCEPTION,
+            EXPIRATION,
+            Rounds { secret: Vec::new() },
+        )
+    }
+
+    fn server_response(query: &Packet<'static>, key_data: &'static [u8]) -> Packet<'static> {
+        let (_, tkey) = find_tkey(query).unwrap();
+        let tkey = TKEY {
+            key_data: Cow::Borrowed(key_data),
+            ..tkey.clone()
+        };
+        response(query, tkey).unwrap()
+    }
+
+    #[test]
+    fn negotiate_key() {
+        let mut client = client();
+
+        let first = client.query(1).unwrap();
+        assert_eq!(first.questions[0].qname, *client.key_name());
+        assert_eq!(first.questions[0].qtype, TYPE::TKEY.into());
+        assert_eq!(first.questions[0].qclass, QCLASS::ANY);
+        assert!(first.answers.is_empty());
+        let (_, tkey) = find_tkey(&first).unwrap();
+        assert_eq!(tkey.key_data[..], *b"init");
+        assert_eq!(tkey.expiration, EXPIRATION);
+
+        // The query survives a round trip through the wire format
+        let bytes = first.build_bytes_vec().unwrap();
+        let parsed = Packet::parse(&bytes).unwrap();
+        assert_eq!(find_tkey(&parsed).unwrap().1, tkey);
+
+        let reply = server_response(&first, b"srv1");
+        assert_eq!(reply.id(), 1);
+        assert!(reply.additional_records.is_empty());
+        assert_eq!(client.process_response(&reply).unwrap(), None);
+
+        let second = client.query(2).unwrap();
+        assert_eq!(find_tkey(&second).unwrap().1.key_data[..], *b"next");
+        assert_eq!(
+            client.query(2).unwrap().additional_records,
+            second.additional_records
+        );
+
+        let reply = server_response(&second, b"done");
+        assert_eq!(
+            client.process_response(&reply).unwrap(),
+            Some(b"srv1done".to_vec())
+        );
+    }
+
+    #[test]
+    fn reject_invalid_responses() {
+        let mut client = client();
+        let query = client.query(1).unwrap();
+
+        let mut reply = server_response(&query, b"done");
+        if let RData::TKEY(tkey) = &mut reply.answers[0].rdata {
+            tkey.error = 19;
+        }
+        assert_eq!(
+            client.process_response(&reply).unwrap_err(),
+            SimpleDnsError::TsigFailed(TsigError::Rejected(19))
+        );
+
+        let mut reply = server_response(&query, b"done");
+        reply.answers[0].name = Name::new_unchecked("other.example.com");
+        assert_eq!(
+            client.process_response(&reply).unwrap_err(),
+            SimpleDnsError::InvalidDnsPacket
+        );
+
+        let reply = Packet::new_reply(1);
+        assert_eq!(
+            client.process_response(&reply).unwrap_err(),
+            SimpleDnsError::InvalidDnsPacket
+        );
+        assert!(response(&reply, find_tkey(&query).unwrap().1.clone()).is_err());
+    }
+}
@@ -0,0 +1,178 @@
+//! Transaction signatures (TSIG), see [RFC 8945](https://www.rfc-editor.org/rfc/rfc8945)
+//!
+//! Messages are signed with [`Packet::sign_tsig`](crate::Packet::sign_tsig) and verified with
+//! [`Packet::verify_tsig`](crate::Packet::verify_tsig). This crate does not implement any
+//! cryptographic algorithm, the MAC is calculated by a [`HmacProvider`] supplied by the caller.
+
+use crate::{
+    dns::{resource_record::CLASS_ANY, WireFormat},
+    lib::{fmt, Vec},
+    rdata::TSIG,
+    Name,
+};
+
+/// Name of the HMAC-MD5 algorithm
+pub const HMAC_MD5: &str = "hmac-md5.sig-alg.reg.int";
+/// Name of the HMAC-SHA1 algorithm
+pub const HMAC_SHA1: &str = "hmac-sha1";
+/// Name of the HMAC-SHA224 algorithm
+pub const HMAC_SHA224: &str = "hmac-sha224";
+/// Name of the HMAC-SHA256 algorithm, the one every implementation must support
+pub const HMAC_SHA256: &str = "hmac-sha256";
+/// Name of the HMAC-SHA384 algorithm
+pub const HMAC_SHA384: &str = "hmac-sha384";
+/// Name of the HMAC-SHA512 algorithm
+pub const HMAC_SHA512: &str = "hmac-sha512";
+
+/// Fudge used when signing messages, the number of seconds the clocks of both parties may differ,
+/// as recommended by [RFC 8945](https://www.rfc-editor.org/rfc/rfc8945#section-10)
+pub const DEFAULT_FUDGE: u16 = 300;
+
+/// Cryptographic backend used to calculate the MAC of TSIG records.
+///
+/// `simple-dns` does not implement any cryptographic algorithm, this trait must be implemented
+/// on top of a cryptographic library to sign and verify messages
+pub trait HmacProvider {
+    /// Returns the MAC of `data` calculated with the `algorithm` and the shared `secret`, or None
+    /// if the algorithm is not supported. The algorithm names are compared case-insensitively
+    fn hmac(&self, algorithm: &Name<'_>, secret: &[u8], data: &[u8]) -> Option<Vec<u8>>;
+}
+
+/// Position of a message in a signed exchange, which defines the data covered by its MAC,
+/// see [RFC 8945](https://www.rfc-editor.org/rfc/rfc8945#section-4.3)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TsigMessage<'m> {
+    /// A request, or any message that does not answer a signed message
+    Request,
+    /// The response to a signed request, or the first message of a multi-message response,
+    /// holding the MAC of the request
+    Response(&'m [u8]),
+    /// Any message after the first one of a multi-message response, like AXFR, holding the MAC
+    /// of the previous message. These messages are signed with the TSIG timers only
+    Subsequent(&'m [u8]),
+}
+
+/// Reasons for a TSIG signed message to fail verification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TsigError {
+    /// The message has no TSIG record, or it is not the last additional record
+    Unsigned,
+    /// The key name or the algorithm do not match, or the algorithm is not supported
+    BadKey,
+    /// The MAC does not match the message
+    BadSig,
+    /// The time signed is outside of the fudge window
+    BadTime,
+    /// The MAC is truncated to less than the allowed length
+    BadTrunc,
+    /// The other party reported the error code held by the TSIG or TKEY record
+    Rejected(u16),
+}
+
+impl TsigError {
+    /// Returns the value of the error field of the TSIG record sent in response to a message
+    /// that failed verification
+    pub fn error_code(&self) -> u16 {
+        match self {
+            TsigError::Unsigned => 1,
+            TsigError::BadSig => 16,
+            TsigError::BadKey => 17,
+            TsigError::BadTime => 18,
+            TsigError::BadTrunc => 22,
+            TsigError::Rejected(error) => *error,
+        }
+    }
+}
+
+impl fmt::Display for TsigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TsigError::Unsigned => write!(f, "Message is not signed"),
+            TsigError::BadKey => write!(f, "Key is not valid"),
+            TsigError::BadSig => write!(f, "MAC is invalid"),
+            TsigError::BadTime => write!(f, "Time signed is outside of the fudge window"),
+            TsigError::BadTrunc => write!(f, "MAC is truncated"),
+            TsigError::Rejected(error) => write!(f, "Message was rejected with error {error}"),
+        }
+    }
+}
+
+/// Returns the data covered by the MAC of a TSIG record, `message` is the message without the
+/// TSIG record, with its original ID, see [RFC 8945](https://www.rfc-editor.org/rfc/rfc8945#section-4.3)
+pub(crate) fn mac_data(
+    key_name: &Name<'_>,
+    tsig: &TSIG<'_>,
+    message: &[u8],
+    position: TsigMessage<'_>,
+) -> crate::Result<Vec<u8>> {
+    let mut data = Vec::with_capacity(message.len() + 128);
+
+    if let TsigMessage::Response(mac) | TsigMessage::Subsequent(mac) = position {
+        data.extend_from_slice(&(mac.len() as u16).to_be_bytes());
+        data.extend_from_slice(mac);
+    }
+    data.extend_from_slice(message);
+
+    if let TsigMessage::Subsequent(_) = position {
+        tsig.write_timers(&mut data)?;
+        return Ok(data);
+    }
+
+    key_name.to_canonical().write_to(&mut data)?;
+    data.extend_from_slice(&CLASS_ANY.to_be_bytes());
+    data.extend_from_slice(&0u32.to_be_bytes());
+    tsig.algorithm.to_canonical().write_to(&mut data)?;
+    tsig.write_timers(&mut data)?;
+    data.extend_from_slice(&tsig.error.to_be_bytes());
+    data.extend_from_slice(&(tsig.other_data.len() as u16).to_be_bytes());
+    data.extend_from_slice(&tsig.other_data);
+
+    Ok(data)
+}
+
+/// Compares the received `mac` with the `expected` one, accepting MACs truncated up to half
+/// of their length, and no shorter than 10 octets,
+/// see [RFC 8945](https://www.rfc-editor.org/rfc/rfc8945#section-5.2.2.1)
+pub(crate) fn check_mac(mac: &[u8], expected: &[u8]) -> Result<(), TsigError> {
+    let min_len = (expected.len() / 2).max(10).min(expected.len());
+    if mac.len() > expected.len() || mac.len() < min_len {
+        return Err(TsigError::BadTrunc);
+    }
+
+    // Every octet is compared, so the time taken does not depend on the position of a mismatch
+    let diff = mac
+        .iter()
+        .zip(expected)
+        .fold(0, |diff, (a, b)| diff | (a ^ b));
+    if diff != 0 {
+        return Err(TsigError::BadSig);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncated_mac() {
+        let expected = [0xab; 32];
+        assert_eq!(check_mac(&expected, &expected), Ok(()));
+        assert_eq!(check_mac(&expected[..16], &expected), Ok(()));
+        assert_eq!(
+            check_mac(&expected[..15], &expected),
+            Err(TsigError::BadTrunc)
+        );
+        assert_eq!(check_mac(&[0xab; 33], &expected), Err(TsigError::BadTrunc));
+
+        let mut mac = expected;
+        mac[31] = 0;
+        assert_eq!(check_mac(&mac, &expected), Err(TsigError::BadSig));
+
+        // HMAC-MD5, 10 octets is more than half of the MAC
+        assert_eq!(check_mac(&[0xab; 10], &[0xab; 16]), Ok(()));
+        assert_eq!(check_mac(&[0xab; 9], &[0xab; 16]), Err(TsigError::BadTrunc));
+    }
+}
@@ -0,0 +1,9 @@
+//! Zone files (master files) support, as described in
+//! [RFC 1035](https://datatracker.ietf.org/doc/html/rfc1035#section-5)
+
+mod parser;
+pub(crate) use parser::parse_record;
+pub use parser::ZoneParser;
+
+mod writer;
+pub use writer::ZoneWriter;
@@ -0,0 +1,346 @@
+use crate::{
+    dns::presentation_format::{parse_ttl, single_entry, Entry, Lexer, TokenReader},
+    rdata::RData,
+    Name, ResourceRecord, SimpleDnsError, CLASS,
+};
+
+/// Parses a zone file, yielding each of the [`ResourceRecord`]s it contains.
+///
+/// The `$ORIGIN` and `$TTL` directives are supported, `$INCLUDE` is not.
+/// Omitted owner names, classes and TTLs are inherited from the previous records.
+/// When the TTL is omitted, the value of the last `$TTL` directive is used, falling back to the
+/// previous record TTL.
+///
+/// Errors are reported with [`SimpleDnsError::InvalidZoneFile`], which contains the line where
+/// the invalid entry starts.
+///
+/// ```
+/// # use simple_dns::{zone::ZoneParser, rdata::RData};
+/// let zone = r#"
+/// $ORIGIN example.com.
+/// $TTL 3600
+/// @       IN  SOA ns hostmaster ( 2024010101 7200 3600 1209600 300 )
+///             NS  ns
+/// ns          A   192.0.2.1
+/// www     60      CNAME @
+/// "#;
+///
+/// let records = ZoneParser::new(zone).collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(records.len(), 4);
+/// assert_eq!(records[1].name.to_string(), "example.com");
+/// assert_eq!(records[3].ttl, 60);
+/// assert!(matches!(records[3].rdata, RData::CNAME(_)));
+/// ```
+pub struct ZoneParser<'a> {
+    lexer: Lexer<'a>,
+    origin: Option<Name<'static>>,
+    default_ttl: Option<u32>,
+    last_owner: Option<Name<'static>>,
+    last_class: CLASS,
+    last_ttl: Option<u32>,
+}
+
+impl<'a> ZoneParser<'a> {
+    /// Creates a new parser for the given zone file contents
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            lexer: Lexer::new(input),
+            origin: None,
+            default_ttl: None,
+            last_owner: None,
+            last_class: CLASS::IN,
+            last_ttl: None,
+        }
+    }
+
+    /// Set the initial origin, used to complete relative names until a `$ORIGIN` directive is found
+    pub fn with_origin(mut self, origin: Name<'_>) -> Self {
+        self.origin = Some(origin.into_owned());
+        self
+    }
+
+    fn parse_directive(&mut self, entry: &Entry<'_>) -> crate::Result<()> {
+        let mut tokens = TokenReader::new(&entry.tokens, self.origin.as_ref());
+        let directive = tokens.next_text()?;
+
+        if directive.eq_ignore_ascii_case("$ORIGIN") {
+            let origin = tokens.next_name()?;
+            tokens.finish()?;
+            self.origin = Some(origin);
+        } else if directive.eq_ignore_ascii_case("$TTL") {
+            let ttl = parse_ttl(tokens.next_text()?)?;
+            tokens.finish()?;
+            self.default_ttl = Some(ttl);
+        } else {
+            return Err(SimpleDnsError::InvalidPresentationFormat);
+        }
+
+        Ok(())
+    }
+
+    fn parse_record(&mut self, entry: &Entry<'_>) -> crate::Result<ResourceRecord<'static>> {
+        let mut tokens = TokenReader::new(&entry.tokens, self.origin.as_ref());
+
+        let name = match entry.blank_owner {
+            true => self
+                .last_owner
+                .clone()
+                .ok_or(SimpleDnsError::InvalidPresentationFormat)?,
+            false => tokens.next_name()?,
+        };
+
+        // TTL and class are both optional and may appear in any order
+        let mut ttl = None;
+        let mut class = None;
+        while let Some(token) = tokens.peek() {
+            if ttl.is_none() && token.text.starts_with(|c: char| c.is_ascii_digit()) {
+                ttl = Some(tokens.next_ttl()?);
+            } else if let (None, Ok(value)) = (class, token.text.parse::<CLASS>()) {
+                tokens.next_token()?;
+                class = Some(value);
+            } else {
+                break;
+            }
+        }
+
+        let rdatatype = tokens.next_type()?;
+        let rdata = RData::parse_text(rdatatype, &mut tokens)?;
+
+        let ttl = match (ttl, self.default_ttl, self.last_ttl, &rdata) {
+            (Some(ttl), _, _, _) | (None, Some(ttl), _, _) | (None, None, Some(ttl), _) => ttl,
+            (None, None, None, RData::SOA(soa)) => soa.minimum,
+            _ => return Err(SimpleDnsError::InvalidPresentationFormat),
+        };
+        let class = class.unwrap_or(self.last_class);
+
+        self.last_owner = Some(name.clone());
+        self.last_class = class;
+        self.last_ttl = Some(ttl);
+
+        Ok(ResourceRecord::new(name, class, ttl, rdata))
+    }
+}
+
+/// Parses a single resource record, without any zone file context
+pub(crate) fn parse_record(text: &str) -> crate::Result<ResourceRecord<'static>> {
+    let entry = single_entry(text.trim_start())?;
+    ZoneParser::new(text).parse_record(&entry)
+}
+
+impl Iterator for ZoneParser<'_> {
+    type Item = crate::Result<ResourceRecord<'static>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let entry = match self.lexer.next()? {
+                Ok(entry) => entry,
+                Err(_) => return Some(Err(SimpleDnsError::InvalidZoneFile(self.lexer.line()))),
+            };
+
+            let is_directive = !entry.blank_owner
+                && entry
+                    .tokens
+                    .first()
+                    .is_some_and(|token| !token.quoted && token.text.starts_with('$'));
+
+            if is_directive {
+                if self.parse_directive(&entry).is_err() {
+                    return Some(Err(SimpleDnsError::InvalidZoneFile(entry.line)));
+                }
+                continue;
+            }
+
+            return Some(
+                self.parse_record(&entry)
+                    .map_err(|_| SimpleDnsError::InvalidZoneFile(entry.line)),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lib::{ToString, Vec};
+
+    fn parse(zone: &str) -> crate::Result<Vec<ResourceRecord<'static>>> {
+        ZoneParser::new(zone).collect()
+    }
+
+    #[test]
+    fn inherit_owner_class_and_ttl() {
+        let records = parse(
+            "$ORIGIN example.com.\n\
+            @ 300 CH A 127.0.0.1\n\
+            \tA 127.0.0.2\n\
+            www IN 60 A 127.0.0.3\n\
+            \tAAAA ::1",
+        )
+        .unwrap();
+
+        let summary: Vec<_> = records
+            .iter()
+            .map(|r| (r.name.to_string(), r.class, r.ttl))
+            .collect();
+
+        assert_eq!(
+            summary,
+            [
+                ("example.com".to_string(), CLASS::CH, 300),
+                ("example.com".to_string(), CLASS::CH, 300),
+                ("www.example.com".to_string(), CLASS::IN, 60),
+                ("www.example.com".to_string(), CLASS::IN, 60),
+            ]
+        );
+    }
+
+    #[test]
+    fn directives() {
+        let records = parse(
+            "$TTL 1h\n\
+            $ORIGIN example.com.\n\
+            a A 127.0.0.1\n\
+            $ORIGIN sub\n\
+            b 30 A 127.0.0.2\n\
+            c A 127.0.0.3\n\
+            d.other. MX 10 mail",
+        )
+        .unwrap();
+
+        let summary: Vec<_> = records
+            .iter()
+            .map(|r| (r.name.to_string(), r.ttl))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("a.example.com".to_string(), 3600),
+                ("b.sub.example.com".to_string(), 30),
+                ("c.sub.example.com".to_string(), 3600),
+                ("d.other".to_string(), 3600),
+            ]
+        );
+
+        match &records[3].rdata {
+            RData::MX(mx) => assert_eq!(mx.exchange.to_string(), "mail.sub.example.com"),
+            _ => panic!("expected MX record"),
+        }
+    }
+
+    #[test]
+    fn with_origin() {
+        let records = ZoneParser::new("@ 60 NS ns\nwww 60 A 127.0.0.1")
+            .with_origin(Name::new_unchecked("example.com"))
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(records[0].name.to_string(), "example.com");
+        assert_eq!(records[1].name.to_string(), "www.example.com");
+    }
+
+    #[test]
+    fn soa_minimum_as_default_ttl() {
+        let records = parse(
+            "example.com. SOA ns.example.com. hostmaster.example.com. (\n\
+                1      ; serial\n\
+                7200   ; refresh\n\
+                3600   ; retry\n\
+                1w     ; expire\n\
+                300 )  ; minimum\n\
+            ns.example.com. A 127.0.0.1",
+        )
+        .unwrap();
+
+        assert_eq!(records[0].ttl, 300);
+        assert_eq!(records[1].ttl, 300);
+        match &records[0].rdata {
+            RData::SOA(soa) => assert_eq!(soa.expire, 604800),
+            _ => panic!("expected SOA record"),
+        }
+    }
+
+    #[test]
+    fn quoted_strings_and_escapes() {
+        let records = parse(
+            "$TTL 60\n\
+            txt.example.com. TXT \"multi\n\
+            line\" \"with \\\"quotes\\\" and ; semicolon\" unquoted\\032text",
+        )
+        .unwrap();
+
+        let expected = crate::rdata::TXT::new()
+            .with_string("multi\nline")
+            .and_then(|txt| txt.with_string("with \"quotes\" and ; semicolon"))
+            .and_then(|txt| txt.with_string("unquoted text"))
+            .unwrap();
+        assert_eq!(records[0].rdata, RData::TXT(expected));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn parse_samples() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::WireFormat;
+
+        let zone = std::fs::read_to_string("samples/sample.zone")?;
+        let records = parse(&zone)?;
+
+        let mut samples = Vec::new();
+        for file_path in std::fs::read_dir("samples/zonefile")? {
+            let bytes = std::fs::read(file_path?.path())?;
+            let record = ResourceRecord::parse(&mut bytes[..].into())?;
+            samples.push(record.into_owned());
+        }
+
+        assert_eq!(records.len(), samples.len());
+        for sample in samples {
+            let record = records
+                .iter()
+                .find(|record| **record == sample)
+                .unwrap_or_else(|| panic!("missing {sample:?}"));
+            assert_eq!(record.ttl, sample.ttl);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn report_error_line() {
+        let errors = [
+            ("$TTL 60\n\n@ A 127.0.0.1", 3),
+            (
+                "$TTL 60\nexample.com. A 127.0.0.1\nexample.com. A 256.0.0.1",
+                3,
+            ),
+            ("$TTL 60\nexample.com. A 127.0.0.1 127.0.0.2", 2),
+            ("$TTL 60\n\tA 127.0.0.1", 2),
+            ("example.com. A 127.0.0.1", 1),
+            ("$INCLUDE other.zone", 1),
+            ("$TTL 60\nexample.com. UNKNOWN 127.0.0.1", 2),
+            ("$TTL 60\nexample.com. TXT (\n\"a\"", 3),
+        ];
+
+        for (zone, line) in errors {
+            assert_eq!(
+                parse(zone).unwrap_err(),
+                SimpleDnsError::InvalidZoneFile(line),
+                "{zone}"
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn display_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        let zone = std::fs::read_to_string("samples/sample.zone")?;
+        for record in parse(&zone)? {
+            let text = record.to_string();
+            let parsed = parse(&text)?;
+
+            assert_eq!(parsed.len(), 1, "{text}");
+            assert_eq!(parsed[0], record, "{text}");
+            assert_eq!(parsed[0].ttl, record.ttl, "{text}");
+        }
+
+        Ok(())
+    }
+}
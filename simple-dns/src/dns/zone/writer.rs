@@ -0,0 +1,322 @@
+use crate::{
+    dns::presentation_format::write_labels,
+    lib::{fmt, BTreeMap, String, Vec},
+    rdata::RData,
+    Label, Name, ResourceRecord, TYPE,
+};
+
+/// Writes [`ResourceRecord`]s as a zone file (master file), the counterpart of
+/// [`ZoneParser`](super::ZoneParser).
+///
+/// Records are grouped by owner name, with the SOA record first when present. Owner names are
+/// written relative to a `$ORIGIN` directive, which is the owner of the SOA record or the longest
+/// suffix shared by all the owner names, unless set with [`ZoneWriter::with_origin`].
+/// Names inside the rdata are always written fully qualified.
+///
+/// OPT pseudo records do not have a presentation format and are skipped.
+///
+/// ```
+/// # use simple_dns::{zone::{ZoneParser, ZoneWriter}, rdata::{RData, A}, Name, ResourceRecord, CLASS};
+/// let records = [
+///     ResourceRecord::new(Name::new_unchecked("example.com"), CLASS::IN, 3600, RData::A(A { address: 0xc0000201 })),
+///     ResourceRecord::new(Name::new_unchecked("www.example.com"), CLASS::IN, 60, RData::A(A { address: 0xc0000202 })),
+/// ];
+///
+/// let zone = ZoneWriter::new().with_default_ttl(3600).build_string(&records);
+/// assert_eq!(zone, "$ORIGIN example.com.\n$TTL 3600\n@\tIN\tA\t192.0.2.1\nwww\t60\tIN\tA\t192.0.2.2\n");
+///
+/// let parsed = ZoneParser::new(&zone).collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(parsed, records);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ZoneWriter {
+    origin: Option<Name<'static>>,
+    default_ttl: Option<u32>,
+}
+
+impl ZoneWriter {
+    /// Creates a new writer, with the origin picked automatically and without a default TTL
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the origin used for the `$ORIGIN` directive, instead of picking one from the records
+    pub fn with_origin(mut self, origin: Name<'_>) -> Self {
+        self.origin = Some(origin.into_owned());
+        self
+    }
+
+    /// Write a `$TTL` directive with the given value, the TTL is omitted for the records that
+    /// match it
+    pub fn with_default_ttl(mut self, ttl: u32) -> Self {
+        self.default_ttl = Some(ttl);
+        self
+    }
+
+    /// Creates a new [`String`] and write the records into it
+    pub fn build_string<'r, 'b: 'r>(
+        &self,
+        records: impl IntoIterator<Item = &'r ResourceRecord<'b>>,
+    ) -> String {
+        let mut out = String::new();
+        self.write_to(records, &mut out)
+            .expect("writing to a String never fails");
+        out
+    }
+
+    /// Write the records as a zone file into the provided writer
+    pub fn write_to<'r, 'b: 'r, W: fmt::Write>(
+        &self,
+        records: impl IntoIterator<Item = &'r ResourceRecord<'b>>,
+        out: &mut W,
+    ) -> fmt::Result {
+        let groups = group_by_owner(records);
+
+        let origin = match &self.origin {
+            Some(origin) => origin.get_labels(),
+            None => pick_origin(&groups),
+        };
+        if !origin.is_empty() {
+            writeln!(out, "$ORIGIN {}.", Labels(origin))?;
+        }
+        if let Some(ttl) = self.default_ttl {
+            writeln!(out, "$TTL {ttl}")?;
+        }
+
+        for group in groups {
+            for (i, record) in group.iter().enumerate() {
+                if i == 0 {
+                    let owner = record.name.get_labels();
+                    if origin.is_empty() {
+                        write!(out, "{}.", Labels(owner))?;
+                    } else if owner == origin {
+                        out.write_str("@")?;
+                    } else if owner.ends_with(origin) {
+                        write!(out, "{}", Labels(&owner[..owner.len() - origin.len()]))?;
+                    } else {
+                        write!(out, "{}.", Labels(owner))?;
+                    }
+                }
+
+                if self.default_ttl != Some(record.ttl) {
+                    write!(out, "\t{}", record.ttl)?;
+                }
+                writeln!(
+                    out,
+                    "\t{}\t{}\t{}",
+                    record.class,
+                    record.rdata.type_code(),
+                    record.rdata
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Groups the records by owner name, keeping the order in which each owner first appears.
+/// The group of the SOA record is moved to the start, with the SOA record as its first record
+fn group_by_owner<'r, 'b: 'r>(
+    records: impl IntoIterator<Item = &'r ResourceRecord<'b>>,
+) -> Vec<Vec<&'r ResourceRecord<'b>>> {
+    let mut groups: Vec<Vec<&ResourceRecord>> = Vec::new();
+    let mut positions: BTreeMap<&[Label], usize> = BTreeMap::new();
+
+    for record in records {
+        if matches!(record.rdata, RData::OPT(_)) {
+            continue;
+        }
+
+        let position = *positions
+            .entry(record.name.get_labels())
+            .or_insert(groups.len());
+        if position == groups.len() {
+            groups.push(Vec::new());
+        }
+        groups[position].push(record);
+    }
+
+    let is_soa = |record: &&ResourceRecord| record.rdata.type_code() == TYPE::SOA;
+    if let Some(position) = groups.iter().position(|group| group.iter().any(is_soa)) {
+        let mut group = groups.remove(position);
+        group.sort_by_key(|record| !is_soa(record));
+        groups.insert(0, group);
+    }
+
+    groups
+}
+
+/// Picks the owner of the SOA record, or the longest suffix shared by all the owner names
+fn pick_origin<'r>(groups: &[Vec<&'r ResourceRecord<'_>>]) -> &'r [Label<'r>] {
+    let mut owners = groups.iter().map(|group| group[0]);
+    let Some(first) = owners.next() else {
+        return &[];
+    };
+
+    if first.rdata.type_code() == TYPE::SOA {
+        return first.name.get_labels();
+    }
+
+    let mut suffix = first.name.get_labels();
+    for owner in owners {
+        let shared = suffix
+            .iter()
+            .rev()
+            .zip(owner.name.get_labels().iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        suffix = &suffix[suffix.len() - shared..];
+    }
+
+    suffix
+}
+
+/// Displays labels in presentation format, without the trailing dot
+struct Labels<'a>(&'a [Label<'a>]);
+
+impl fmt::Display for Labels<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_labels(f, self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{zone::ZoneParser, CLASS};
+
+    fn parse(zone: &str) -> Vec<ResourceRecord<'static>> {
+        ZoneParser::new(zone)
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn group_records_by_owner() {
+        let records = parse(
+            "www.example.com. 60 IN A 192.0.2.1\n\
+            example.com. 3600 IN NS ns.example.com.\n\
+            www.example.com. 60 IN AAAA 2001:db8::1\n\
+            example.com. 3600 IN SOA ns.example.com. hostmaster.example.com. 1 7200 3600 1209600 300\n\
+            ns.example.com. 3600 CH A 192.0.2.2",
+        );
+
+        let zone = ZoneWriter::new()
+            .with_default_ttl(3600)
+            .build_string(&records);
+        assert_eq!(
+            zone,
+            "$ORIGIN example.com.\n\
+            $TTL 3600\n\
+            @\tIN\tSOA\tns.example.com. hostmaster.example.com. 1 7200 3600 1209600 300\n\
+            \tIN\tNS\tns.example.com.\n\
+            www\t60\tIN\tA\t192.0.2.1\n\
+            \t60\tIN\tAAAA\t2001:db8::1\n\
+            ns\tCH\tA\t192.0.2.2\n"
+        );
+
+        let parsed = parse(&zone);
+        assert_eq!(parsed.len(), records.len());
+        for record in &records {
+            let found = parsed.iter().find(|parsed| *parsed == record).unwrap();
+            assert_eq!(found.ttl, record.ttl);
+        }
+    }
+
+    #[test]
+    fn pick_origin_from_shared_suffix() {
+        let records = parse(
+            "a.sub.example.com. 60 A 192.0.2.1\n\
+            b.example.com. 60 A 192.0.2.2",
+        );
+        let zone = ZoneWriter::new().build_string(&records);
+        assert!(zone.starts_with("$ORIGIN example.com.\na.sub\t60\tIN\tA"));
+        assert_eq!(parse(&zone), records);
+
+        let records = parse("example.com. 60 A 192.0.2.1\nexample.org. 60 A 192.0.2.2");
+        let zone = ZoneWriter::new().build_string(&records);
+        assert!(zone.starts_with("example.com.\t60\tIN\tA"));
+        assert_eq!(parse(&zone), records);
+
+        let zone = ZoneWriter::new()
+            .with_origin(Name::new_unchecked("org"))
+            .build_string(&records);
+        assert!(zone.starts_with("$ORIGIN org.\nexample.com.\t60"));
+        assert!(zone.contains("\nexample\t60"));
+        assert_eq!(parse(&zone), records);
+    }
+
+    #[test]
+    fn escape_special_characters() {
+        let records = [
+            ResourceRecord::new(
+                Name::new_with_labels(&[
+                    Label::new_unchecked(&b"@ \"dots.\" ;"[..]),
+                    Label::new_unchecked(&b"$example"[..]),
+                ]),
+                CLASS::IN,
+                60,
+                RData::TXT(
+                    crate::rdata::TXT::new()
+                        .with_string("(\"quoted\"; \\ \n)")
+                        .unwrap(),
+                ),
+            ),
+            ResourceRecord::new(
+                Name::new_unchecked("$example"),
+                CLASS::IN,
+                60,
+                RData::Empty(TYPE::A),
+            ),
+        ];
+
+        let zone = ZoneWriter::new().build_string(&records);
+        assert_eq!(
+            zone,
+            "$ORIGIN \\$example.\n\
+            \\@\\032\\\"dots\\.\\\"\\032\\;\t60\tIN\tTXT\t\"(\\\"quoted\\\"; \\\\ \\010)\"\n\
+            @\t60\tIN\tA\t\\# 0\n"
+        );
+        assert_eq!(parse(&zone), records);
+    }
+
+    #[test]
+    fn skip_opt_records() {
+        let records = [ResourceRecord::new(
+            Name::new_unchecked(""),
+            CLASS::IN,
+            0,
+            RData::OPT(crate::rdata::OPT {
+                opt_codes: Vec::new(),
+                udp_packet_size: 1232,
+                version: 0,
+            }),
+        )];
+
+        assert_eq!(ZoneWriter::new().build_string(&records), "");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn write_samples() -> Result<(), Box<dyn std::error::Error>> {
+        let zone = std::fs::read_to_string("samples/sample.zone")?;
+        let records = parse(&zone);
+
+        let written = ZoneWriter::new()
+            .with_default_ttl(60)
+            .build_string(&records);
+        let parsed = parse(&written);
+
+        assert_eq!(parsed.len(), records.len());
+        for record in &records {
+            let found = parsed
+                .iter()
+                .find(|parsed| *parsed == record)
+                .unwrap_or_else(|| panic!("missing {record}\n{written}"));
+            assert_eq!(found.ttl, record.ttl);
+        }
+
+        Ok(())
+    }
+}
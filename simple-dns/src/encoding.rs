@@ -0,0 +1,238 @@
+//! Binary to text encodings used by the presentation format of some resource records
+
+pub(crate) mod base64 {
+    use crate::lib::{String, Vec};
+
+    const PAD: u8 = b'=';
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    /// Encodes `data` as standard (padded) base64.
+    pub(crate) fn encode(data: &[u8]) -> String {
+        let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+        for chunk in data.chunks(3) {
+            let mut bytes = [0u8; 4];
+            bytes[1..=chunk.len()].copy_from_slice(chunk);
+            let acc = u32::from_be_bytes(bytes);
+
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    out.push(ALPHABET[(acc >> (18 - 6 * i) & 0x3f) as usize] as char);
+                } else {
+                    out.push(PAD as char);
+                }
+            }
+        }
+
+        out
+    }
+
+    fn decode_char(c: u8) -> Option<u8> {
+        match c {
+            b'A'..=b'Z' => Some(c - b'A'),
+            b'a'..=b'z' => Some(c - b'a' + 26),
+            b'0'..=b'9' => Some(c - b'0' + 52),
+            b'+' => Some(62),
+            b'/' => Some(63),
+            _ => None,
+        }
+    }
+
+    /// Decodes a standard (padded) base64 text.
+    pub(crate) fn decode(text: &[u8]) -> crate::Result<Vec<u8>> {
+        if !text.len().is_multiple_of(4) {
+            return Err(crate::SimpleDnsError::InvalidPresentationFormat);
+        }
+
+        let mut out = Vec::with_capacity(text.len() / 4 * 3);
+        for (i, chunk) in text.chunks(4).enumerate() {
+            let is_last = i == text.len() / 4 - 1;
+            let padding = chunk.iter().rev().take_while(|c| **c == PAD).count();
+            if padding > 2 || (padding > 0 && !is_last) {
+                return Err(crate::SimpleDnsError::InvalidPresentationFormat);
+            }
+
+            let mut acc = 0u32;
+            for c in &chunk[..4 - padding] {
+                let value =
+                    decode_char(*c).ok_or(crate::SimpleDnsError::InvalidPresentationFormat)?;
+                acc = (acc << 6) | value as u32;
+            }
+            acc <<= 6 * padding;
+
+            let bytes = acc.to_be_bytes();
+            out.extend_from_slice(&bytes[1..4 - padding]);
+        }
+
+        Ok(out)
+    }
+}
+
+pub(crate) mod hex {
+    use crate::lib::{String, Vec};
+
+    /// Encodes `data` as uppercase hexadecimal.
+    pub(crate) fn encode(data: &[u8]) -> String {
+        const DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+        let mut out = String::with_capacity(data.len() * 2);
+        for byte in data {
+            out.push(DIGITS[(byte >> 4) as usize] as char);
+            out.push(DIGITS[(byte & 0xf) as usize] as char);
+        }
+
+        out
+    }
+
+    fn decode_char(c: u8) -> Option<u8> {
+        match c {
+            b'0'..=b'9' => Some(c - b'0'),
+            b'a'..=b'f' => Some(c - b'a' + 10),
+            b'A'..=b'F' => Some(c - b'A' + 10),
+            _ => None,
+        }
+    }
+
+    /// Decodes a case insensitive hexadecimal text.
+    pub(crate) fn decode(text: &[u8]) -> crate::Result<Vec<u8>> {
+        if !text.len().is_multiple_of(2) {
+            return Err(crate::SimpleDnsError::InvalidPresentationFormat);
+        }
+
+        text.chunks(2)
+            .map(|pair| match (decode_char(pair[0]), decode_char(pair[1])) {
+                (Some(high), Some(low)) => Ok(high << 4 | low),
+                _ => Err(crate::SimpleDnsError::InvalidPresentationFormat),
+            })
+            .collect()
+    }
+}
+
+pub(crate) mod base32hex {
+    use crate::lib::{String, Vec};
+
+    const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHIJKLMNOPQRSTUV";
+
+    /// Encodes `data` as uppercase base32 with the extended hex alphabet, without padding.
+    pub(crate) fn encode(data: &[u8]) -> String {
+        let mut out = String::with_capacity((data.len() * 8).div_ceil(5));
+        let mut acc = 0u32;
+        let mut bits = 0;
+        for byte in data {
+            acc = (acc << 8) | *byte as u32;
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                out.push(ALPHABET[(acc >> bits & 0x1f) as usize] as char);
+            }
+            acc &= (1 << bits) - 1;
+        }
+
+        if bits > 0 {
+            out.push(ALPHABET[(acc << (5 - bits) & 0x1f) as usize] as char);
+        }
+
+        out
+    }
+
+    fn decode_char(c: u8) -> Option<u8> {
+        match c {
+            b'0'..=b'9' => Some(c - b'0'),
+            b'A'..=b'V' => Some(c - b'A' + 10),
+            b'a'..=b'v' => Some(c - b'a' + 10),
+            _ => None,
+        }
+    }
+
+    /// Decodes a case insensitive base32 text with the extended hex alphabet, without padding.
+    pub(crate) fn decode(text: &[u8]) -> crate::Result<Vec<u8>> {
+        if matches!(text.len() % 8, 1 | 3 | 6) {
+            return Err(crate::SimpleDnsError::InvalidPresentationFormat);
+        }
+
+        let mut out = Vec::with_capacity(text.len() * 5 / 8);
+        let mut acc = 0u32;
+        let mut bits = 0;
+        for c in text {
+            let value = decode_char(*c).ok_or(crate::SimpleDnsError::InvalidPresentationFormat)?;
+            acc = (acc << 5) | value as u32;
+            bits += 5;
+            if bits >= 8 {
+                bits -= 8;
+                out.push((acc >> bits) as u8);
+                acc &= (1 << bits) - 1;
+            }
+        }
+
+        // the unused trailing bits must be zero
+        if acc != 0 {
+            return Err(crate::SimpleDnsError::InvalidPresentationFormat);
+        }
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_base64() {
+        assert_eq!(base64::decode(b"").unwrap(), b"");
+        assert_eq!(base64::decode(b"Zg==").unwrap(), b"f");
+        assert_eq!(base64::decode(b"Zm8=").unwrap(), b"fo");
+        assert_eq!(base64::decode(b"Zm9v").unwrap(), b"foo");
+        assert_eq!(base64::decode(b"Zm9vYmFy").unwrap(), b"foobar");
+
+        assert!(base64::decode(b"Zm9").is_err());
+        assert!(base64::decode(b"Zg==Zm9v").is_err());
+        assert!(base64::decode(b"Z===").is_err());
+        assert!(base64::decode(b"Zm9*").is_err());
+    }
+
+    #[test]
+    fn encode_base64() {
+        assert_eq!(base64::encode(b""), "");
+        assert_eq!(base64::encode(b"f"), "Zg==");
+        assert_eq!(base64::encode(b"fo"), "Zm8=");
+        assert_eq!(base64::encode(b"foo"), "Zm9v");
+        assert_eq!(base64::encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64::encode(&[0xfb, 0xff]), "+/8=");
+    }
+
+    #[test]
+    fn encode_hex() {
+        assert_eq!(hex::encode(b""), "");
+        assert_eq!(hex::encode(&[0x00, 0xff, 0xab]), "00FFAB");
+    }
+
+    #[test]
+    fn decode_hex() {
+        assert_eq!(hex::decode(b"").unwrap(), b"");
+        assert_eq!(hex::decode(b"00ffAb").unwrap(), [0x00, 0xff, 0xab]);
+
+        assert!(hex::decode(b"abc").is_err());
+        assert!(hex::decode(b"zz").is_err());
+    }
+
+    #[test]
+    fn encode_base32hex() {
+        assert_eq!(base32hex::encode(b""), "");
+        assert_eq!(base32hex::encode(b"f"), "CO");
+        assert_eq!(base32hex::encode(b"fo"), "CPNG");
+        assert_eq!(base32hex::encode(b"foo"), "CPNMU");
+        assert_eq!(base32hex::encode(b"foob"), "CPNMUOG");
+        assert_eq!(base32hex::encode(b"fooba"), "CPNMUOJ1");
+        assert_eq!(base32hex::encode(b"foobar"), "CPNMUOJ1E8");
+    }
+
+    #[test]
+    fn decode_base32hex() {
+        assert_eq!(base32hex::decode(b"").unwrap(), b"");
+        assert_eq!(base32hex::decode(b"CO").unwrap(), b"f");
+        assert_eq!(base32hex::decode(b"cpnmuoj1e8").unwrap(), b"foobar");
+
+        assert!(base32hex::decode(b"C").is_err());
+        assert!(base32hex::decode(b"CP").is_err());
+        assert!(base32hex::decode(b"CW").is_err());
+    }
+}
@@ -11,6 +11,7 @@ extern crate std;
 mod bytes_buffer;
 mod cursor;
 mod dns;
+mod encoding;
 mod seek;
 mod simple_dns_error;
 mod write;
@@ -27,6 +28,7 @@ mod lib {
         pub use std::*;
     }
 
+    pub use self::core::net::IpAddr;
     pub use self::core::net::Ipv4Addr;
     pub use self::core::net::Ipv6Addr;
 
@@ -29,6 +29,18 @@ pub enum SimpleDnsError {
     FailedToWrite,
     /// Invalid utf8 string
     InvalidUtf8String(FromUtf8Error),
+    /// Provided text is not valid presentation format
+    InvalidPresentationFormat,
+    /// Zone file is not valid, contains the line where the error was found
+    InvalidZoneFile(usize),
+    /// Signature validation failed, contains the reason of the failure
+    ValidationFailed(crate::dnssec::ValidationError),
+    /// The cryptographic backend failed to create a signature
+    SigningFailed,
+    /// TSIG verification failed, contains the reason of the failure
+    TsigFailed(crate::tsig::TsigError),
+    /// The name resulting of an operation, like a DNAME substitution, exceeds 255 octets
+    NameTooLong,
 }
 
 impl From<TryFromSliceError> for SimpleDnsError {
@@ -81,6 +93,18 @@ impl Display for SimpleDnsError {
             SimpleDnsError::InvalidUtf8String(e) => {
                 write!(f, "Invalid utf8 string: {e}")
             }
+            SimpleDnsError::InvalidPresentationFormat => {
+                write!(f, "Provided text is not in a valid presentation format")
+            }
+            SimpleDnsError::InvalidZoneFile(line) => {
+                write!(f, "Invalid zone file at line {line}")
+            }
+            SimpleDnsError::ValidationFailed(reason) => {
+                write!(f, "Signature validation failed: {reason}")
+            }
+            SimpleDnsError::SigningFailed => write!(f, "Failed to create signature"),
+            SimpleDnsError::TsigFailed(reason) => write!(f, "TSIG verification failed: {reason}"),
+            SimpleDnsError::NameTooLong => write!(f, "Name is longer than 255 octets"),
         }
     }
 }